@@ -0,0 +1,264 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::parse::{Parse, ParseStream, Result};
+use syn::spanned::Spanned;
+use syn::{BinOp, Block, Error, Expr, FnArg, Ident, ItemFn, Lit, Pat, PatIdent, ReturnType, Stmt, Type, UnOp};
+
+/// Methods of f64 which have Dual counterpart with one argument.
+const UNARY_FNS: &[&str] = &[
+    "sin", "cos", "tan", "sqrt", "ln", "exp", "asin", "acos", "atan", "sinh", "cosh", "tanh",
+    "asinh", "acosh", "atanh", "exp2", "exp_m1", "ln_1p", "log2", "log10", "cbrt", "abs",
+    "signum", "recip", "powi",
+];
+
+/// Methods of f64 which have Dual counterpart with two arguments.
+const BINARY_FNS: &[&str] = &["powf", "atan2", "hypot", "min", "max", "log", "copysign"];
+
+/// Differentiable attribute arguments
+pub struct DifferentiableArgs {
+    /// generate gradient function as well
+    pub grad: bool,
+}
+
+impl Parse for DifferentiableArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.is_empty() {
+            return Ok(DifferentiableArgs { grad: false });
+        }
+        let arg: Ident = input.parse()?;
+        if arg != "grad" || !input.is_empty() {
+            return Err(Error::new(arg.span(), "expected `grad`"));
+        }
+        Ok(DifferentiableArgs { grad: true })
+    }
+}
+
+/// Generate Dual version of function and optionally its gradient,
+/// original function is kept as is.
+pub fn differentiable(args: DifferentiableArgs, fun: ItemFn) -> TokenStream {
+    match companions(args, &fun) {
+        Ok(out) => quote! { #fun #out },
+        Err(err) => {
+            let err = err.to_compile_error();
+            quote! { #fun #err }
+        }
+    }
+}
+
+/// Dual version of function and optionally its gradient.
+fn companions(args: DifferentiableArgs, fun: &ItemFn) -> Result<TokenStream> {
+    let params = signature(fun)?;
+    let inputs: Vec<&Ident> = params.iter().map(|pat| &pat.ident).collect();
+    let mut body = (*fun.block).clone();
+    rewrite_block(&mut body)?;
+    let vis = &fun.vis;
+    let name = &fun.sig.ident;
+    let dual = format_ident!("{}_dual", name);
+    let dual_doc = format!("Dual version of `{}`, generated by `#[differentiable]`.", name);
+    let mut out = quote! {
+        #[doc = #dual_doc]
+        #vis fn #dual(#(#params: ::rad::Dual),*) -> ::rad::Dual #body
+    };
+    if args.grad {
+        let grad = format_ident!("{}_grad", name);
+        let grad_doc = format!("Value and gradient of `{}`, generated by `#[differentiable(grad)]`.", name);
+        let n = inputs.len();
+        let ders = if n == 0 {
+            quote! { Vec::new() }
+        } else {
+            quote! { (0..#n).map(|__i| {
+                    let mut __vars = [#(<::rad::Dual>::from(#inputs)),*];
+                    __vars[__i].seed();
+                    let [#(#inputs),*] = __vars;
+                    #dual(#(#inputs),*).der()
+                }).collect()
+            }
+        };
+        out.extend(quote! {
+            #[doc = #grad_doc]
+            #vis fn #grad(#(#inputs: f64),*) -> (f64, Vec<f64>) {
+                (#name(#(#inputs),*), #ders)
+            }
+        });
+    }
+    Ok(out)
+}
+
+/// Check that function takes and returns f64 numbers only,
+/// get its arguments with their 'ref' and 'mut' modifiers.
+fn signature(fun: &ItemFn) -> Result<Vec<PatIdent>> {
+    let sig = &fun.sig;
+    if !sig.generics.params.is_empty() || sig.asyncness.is_some() {
+        return Err(Error::new_spanned(sig, "differentiable function must be plain non generic function"));
+    }
+    match &sig.output {
+        ReturnType::Type(_, ty) if is_f64(ty) => (),
+        _ => return Err(Error::new_spanned(sig, "differentiable function must return f64")),
+    }
+    sig.inputs
+        .iter()
+        .map(|arg| match arg {
+            FnArg::Typed(arg) if is_f64(&arg.ty) => match &*arg.pat {
+                Pat::Ident(pat) if pat.subpat.is_none() => Ok(pat.clone()),
+                pat => Err(Error::new_spanned(pat, "arguments must be identifiers")),
+            },
+            arg => Err(Error::new_spanned(arg, "differentiable function takes f64 arguments only")),
+        })
+        .collect()
+}
+
+/// Is type f64.
+fn is_f64(ty: &Type) -> bool {
+    match ty {
+        Type::Path(ty) => ty.qself.is_none() && ty.path.is_ident("f64"),
+        _ => false,
+    }
+}
+
+/// Rewrite statements of block.
+fn rewrite_block(block: &mut Block) -> Result<()> {
+    for stmt in block.stmts.iter_mut() {
+        match stmt {
+            Stmt::Local(local) => {
+                if let Pat::Type(pat) = &mut local.pat {
+                    if is_f64(&pat.ty) {
+                        *pat.ty = syn::parse_quote_spanned!(pat.ty.span()=> ::rad::Dual);
+                    }
+                }
+                if let Some((_, init)) = &mut local.init {
+                    rewrite(init)?;
+                }
+            }
+            Stmt::Item(item) => return Err(Error::new_spanned(item, "nested items are not supported")),
+            Stmt::Expr(expr) | Stmt::Semi(expr, _) => rewrite(expr)?,
+        }
+    }
+    Ok(())
+}
+
+/// Wrap expression into Dual number, so result of every
+/// operation is evaluated eagerly and may be used many times.
+fn to_dual(expr: &Expr) -> Expr {
+    syn::parse2(quote_spanned! {expr.span()=> <::rad::Dual>::from(#expr) }).unwrap()
+}
+
+/// Rewrite arithmetic on f64 numbers into arithmetic on Dual numbers.
+fn rewrite(expr: &mut Expr) -> Result<()> {
+    match expr {
+        Expr::Lit(lit) => {
+            let float = match &lit.lit {
+                Lit::Float(_) => true,
+                Lit::Int(int) => int.suffix() == "f64",
+                _ => false,
+            };
+            if float {
+                *expr = to_dual(expr);
+            }
+        }
+        Expr::Path(path) => {
+            let segments: Vec<_> = path.path.segments.iter().map(|s| s.ident.to_string()).collect();
+            let constant = segments.len() > 1
+                && (segments[0] == "f64" || segments[segments.len() - 2] == "consts");
+            if constant {
+                *expr = to_dual(expr);
+            }
+        }
+        Expr::Binary(bin) => {
+            rewrite(&mut bin.left)?;
+            rewrite(&mut bin.right)?;
+            match bin.op {
+                BinOp::Add(_) | BinOp::Sub(_) | BinOp::Mul(_) | BinOp::Div(_) => *expr = to_dual(expr),
+                BinOp::Rem(_) => return Err(Error::new_spanned(bin.op, "operator is not supported")),
+                _ => (),
+            }
+        }
+        Expr::AssignOp(assign) => {
+            rewrite(&mut assign.right)?;
+            if let BinOp::RemEq(_) = assign.op {
+                return Err(Error::new_spanned(assign.op, "operator is not supported"));
+            }
+        }
+        Expr::Unary(un) => {
+            rewrite(&mut un.expr)?;
+            if let UnOp::Neg(_) = un.op {
+                *expr = to_dual(expr);
+            }
+        }
+        Expr::MethodCall(call) => {
+            let method = call.method.to_string();
+            if !UNARY_FNS.contains(&method.as_str()) && !BINARY_FNS.contains(&method.as_str()) {
+                return Err(Error::new_spanned(&call.method, "method is not supported for Dual numbers"));
+            }
+            rewrite(&mut call.receiver)?;
+            for arg in call.args.iter_mut() {
+                rewrite(arg)?;
+            }
+            *expr = to_dual(expr);
+        }
+        Expr::Call(call) => {
+            match &mut *call.func {
+                Expr::Path(path) if path.qself.is_none() && path.path.get_ident().is_some() => {
+                    let name = path.path.get_ident().unwrap();
+                    path.path = format_ident!("{}_dual", name, span = name.span()).into();
+                }
+                func => {
+                    return Err(Error::new_spanned(
+                        func,
+                        "only functions marked with #[differentiable] may be called",
+                    ))
+                }
+            }
+            for arg in call.args.iter_mut() {
+                rewrite(arg)?;
+            }
+        }
+        Expr::Cast(cast) => {
+            rewrite(&mut cast.expr)?;
+            if is_f64(&cast.ty) {
+                *expr = to_dual(expr);
+            }
+        }
+        Expr::Paren(paren) => rewrite(&mut paren.expr)?,
+        Expr::Group(group) => rewrite(&mut group.expr)?,
+        Expr::Block(block) => rewrite_block(&mut block.block)?,
+        Expr::If(cond) => {
+            rewrite(&mut cond.cond)?;
+            rewrite_block(&mut cond.then_branch)?;
+            if let Some((_, other)) = &mut cond.else_branch {
+                rewrite(other)?;
+            }
+        }
+        Expr::While(body) => {
+            rewrite(&mut body.cond)?;
+            rewrite_block(&mut body.body)?;
+        }
+        Expr::ForLoop(body) => {
+            rewrite(&mut body.expr)?;
+            rewrite_block(&mut body.body)?;
+        }
+        Expr::Loop(body) => rewrite_block(&mut body.body)?,
+        Expr::Range(range) => {
+            if let Some(from) = &mut range.from {
+                rewrite(from)?;
+            }
+            if let Some(to) = &mut range.to {
+                rewrite(to)?;
+            }
+        }
+        Expr::Assign(assign) => rewrite(&mut assign.right)?,
+        Expr::Return(ret) => {
+            if let Some(val) = &mut ret.expr {
+                rewrite(val)?;
+            }
+        }
+        Expr::Break(brk) => {
+            if let Some(val) = &mut brk.expr {
+                rewrite(val)?;
+            }
+        }
+        Expr::Continue(_) => (),
+        Expr::Macro(mac) => return Err(Error::new_spanned(mac, "macros are not supported")),
+        _ => return Err(Error::new_spanned(expr, "expression is not supported")),
+    }
+    Ok(())
+}
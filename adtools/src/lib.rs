@@ -2,19 +2,24 @@ extern crate proc_macro;
 use self::proc_macro::TokenStream;
 
 use proc_macro_hack::proc_macro_hack;
-use quote::{quote};
+use quote::{format_ident, quote};
 use syn::parse::{Parse, ParseStream, Result};
 use syn::punctuated::Punctuated;
-use syn::{parse_macro_input, Expr, ExprCall, Token};
+use syn::visit_mut::{self, VisitMut};
+use syn::{bracketed, parse_macro_input, token, Expr, ExprCall, Ident, ItemFn, Token};
 
 extern crate xprlib;
 
+mod differentiable;
+
 /// Derivative macro arguments
 struct DerivativeArgs {
     /// target function to calculate derivative
     fun: ExprCall,
     /// variable w.r.t. we want to get derivative
-    wrt: Expr
+    wrt: Expr,
+    /// optional seed weight of variable, 1.0 by default
+    seed: Option<Expr>,
 }
 
 impl Parse for DerivativeArgs {
@@ -22,74 +27,452 @@ impl Parse for DerivativeArgs {
         let fun: ExprCall = input.parse()?;
         input.parse::<Token![,]>()?;
         let wrt: Expr = input.parse()?;
+        let seed = parse_seed(input)?;
         Ok(DerivativeArgs {
             fun,
             wrt,
+            seed,
         })
     }
 }
 
-/// Gradient of scalar function.
+/// Parse optional seed weights separated from variables by semicolon.
+fn parse_seed(input: ParseStream) -> Result<Option<Expr>> {
+    if input.is_empty() {
+        return Ok(None);
+    }
+    input.parse::<Token![;]>()?;
+    Ok(Some(input.parse()?))
+}
+
+/// Gradient of scalar function. Variable is replaced by
+/// its seeded copy, so inputs are never mutated. With seed
+/// weight derivative is scaled by it.
 #[proc_macro_hack]
 pub fn derivative(input: TokenStream) -> TokenStream {
-    let DerivativeArgs {fun, wrt} = parse_macro_input!(input as DerivativeArgs);
-    let out = quote! { {
-            #wrt.seed();
-            let gres = #fun;
-            #wrt.unseed();
-            gres.der()
-        }
+    let DerivativeArgs {mut fun, wrt, seed} = parse_macro_input!(input as DerivativeArgs);
+    let seeded = match seed {
+        Some(seed) => quote! { seeded_with(#seed) },
+        None => quote! { seeded() },
+    };
+    let out = match bind_vars(&mut fun, vec![wrt]) {
+        Ok(vars) => {
+            let Var {place, name} = &vars[0];
+            quote! { {
+                    let #name = #place.#seeded;
+                    #fun.der()
+                }
+            }
+        },
+        Err(err) => err.to_compile_error(),
     };
     out.into()
 }
 
+/// Variables w.r.t. we want to get gradient
+enum Wrt {
+    /// Collections of Dual numbers, function is evaluated once per element.
+    Iterables(Vec<Expr>),
+    /// Variables listed in brackets, their count is known at compile time,
+    /// so function is evaluated once with DualN numbers.
+    Fixed(Vec<Ident>),
+}
+
 /// Gradient arguments
 struct GradientArgs {
     fun: ExprCall,
-    wrt: Vec<Expr>,
+    wrt: Wrt,
+    /// optional seed weights of variables, 1.0 by default
+    seed: Option<Expr>,
 }
 
 impl Parse for GradientArgs {
     fn parse(input: ParseStream) -> Result<Self> {
         let fun: ExprCall = input.parse()?;
         input.parse::<Token![,]>()?;
-        let wrt = Punctuated::<Expr, Token![,]>::parse_terminated(input)?.into_iter().collect();
+        let wrt = if input.peek(token::Bracket) {
+            let content;
+            bracketed!(content in input);
+            Wrt::Fixed(Punctuated::<Ident, Token![,]>::parse_terminated(&content)?.into_iter().collect())
+        } else {
+            let mut wrt = vec![input.parse()?];
+            while input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+                if input.is_empty() || input.peek(Token![;]) {
+                    break;
+                }
+                wrt.push(input.parse()?);
+            }
+            Wrt::Iterables(wrt)
+        };
+        let seed = parse_seed(input)?;
         Ok(GradientArgs {
             fun,
             wrt,
+            seed,
         })
     }
 }
 
-/// Gradient of scalar function.
+impl GradientArgs {
+    /// Arguments of macro which doesn't support seed weights.
+    fn unweighted(self) -> Result<(ExprCall, Wrt)> {
+        match self.seed {
+            Some(seed) => Err(syn::Error::new_spanned(seed, "seed weights are not supported by this macro")),
+            None => Ok((self.fun, self.wrt)),
+        }
+    }
+}
+
+/// Gradient of scalar function. With seed weights directional
+/// derivative, sum of partial derivatives scaled by weights, is
+/// evaluated instead.
 #[proc_macro_hack]
 pub fn gradient(input: TokenStream) -> TokenStream {
-    let GradientArgs {fun, wrt} = parse_macro_input!(input as GradientArgs);
+    let GradientArgs {mut fun, wrt, seed} = parse_macro_input!(input as GradientArgs);
+    let out = match wrt {
+        Wrt::Iterables(wrt) => match bind_vars(&mut fun, wrt) {
+            Ok(vars) => match seed {
+                Some(seed) => directional_iterables(fun, vars, seed),
+                None => gradient_iterables(fun, vars),
+            },
+            Err(err) => err.to_compile_error(),
+        },
+        Wrt::Fixed(wrt) => gradient_fixed(fun, wrt, seed),
+    };
+    out.into()
+}
+
+/// Gradient w.r.t. collections of Dual numbers. Each collection is
+/// shadowed by one copy, its elements are seeded one by one in place,
+/// function is evaluated and element is unseeded again.
+fn gradient_iterables(fun: ExprCall, vars: Vec<Var>) -> proc_macro2::TokenStream {
     // count gradient elements to allocate vector once
-    let count: Vec<_> = wrt.iter().map(|arg| {
+    let count: Vec<_> = vars.iter().map(|Var {place, ..}| {
         quote! {
-            cnt += #arg.len();
+            cnt += #place.len();
         }
     }).collect();
     // eval gradient
-    let grad = wrt.iter().map(|arg| {
+    let grad = vars.iter().map(|Var {name, ..}| {
         quote! {
-            #arg.iter().for_each(|d| {
-                d.seed();
+            for i in 0..#name.len() {
+                #name[i].seed();
                 let v = #fun;
-                d.unseed();
+                #name[i].unseed();
                 ders.push(v.der());
-            })
+            }
         }
     });
+    let copies = vars.iter().map(Var::copy);
     // make evaluations
-    let out = quote! { {
+    quote! { {
             let mut cnt: usize = 0;
             #(#count;)*
+            #(#copies)*
             let mut ders = Vec::with_capacity(cnt);
             #(#grad;)*
             ders
         }
+    }
+}
+
+/// Directional derivative w.r.t. collections of Dual numbers. Collections
+/// are shadowed by copies where k-th element of all collections is seeded
+/// with k-th weight, so function is evaluated once.
+fn directional_iterables(fun: ExprCall, vars: Vec<Var>, seed: Expr) -> proc_macro2::TokenStream {
+    let count: Vec<_> = vars.iter().map(|Var {place, ..}| {
+        quote! {
+            cnt += #place.len();
+        }
+    }).collect();
+    let (places, names): (Vec<_>, Vec<_>) = vars.iter().map(|Var {place, name}| (place, name)).unzip();
+    quote! { {
+            let mut cnt: usize = 0;
+            #(#count;)*
+            let seeds = &#seed;
+            assert_eq!(seeds.len(), cnt, "count of seed weights differs from count of variables");
+            let mut k: usize = 0;
+            #(let #names = {
+                let mut seeded = #places.to_owned();
+                for v in seeded.iter_mut() {
+                    v.seed_with(seeds[k]);
+                    k += 1;
+                }
+                seeded
+            };)*
+            #fun.der()
+        }
+    }
+}
+
+/// Gradient w.r.t. fixed list of variables. Variables are shadowed
+/// by DualN numbers seeded in own directions, so function must
+/// accept DualN and the gradient is evaluated in one pass. Tangent
+/// is linear in seed, so directional derivative is dot product of
+/// gradient and seed weights.
+fn gradient_fixed(fun: ExprCall, wrt: Vec<Ident>, seed: Option<Expr>) -> proc_macro2::TokenStream {
+    let n = wrt.len();
+    let grad = match seed {
+        Some(seed) => quote! {
+            let seeds: [f64; #n] = #seed;
+            grad.iter().zip(seeds.iter()).map(|(d, s)| d * s).sum::<f64>()
+        },
+        None => quote! { grad.to_vec() },
+    };
+    quote! { {
+            let [#(#wrt),*] = ::rad::DualN::<#n>::variables([#(#wrt.val()),*]);
+            let grad = #fun.grad();
+            #grad
+        }
+    }
+}
+
+/// Jacobian of vector valued function.
+#[proc_macro_hack]
+pub fn jacobian(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as GradientArgs);
+    let (mut fun, wrt) = match args.unweighted() {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let out = match wrt {
+        Wrt::Iterables(wrt) => match bind_vars(&mut fun, wrt) {
+            Ok(vars) => jacobian_iterables(fun, vars),
+            Err(err) => err.to_compile_error(),
+        },
+        Wrt::Fixed(wrt) => jacobian_fixed(fun, wrt),
     };
     out.into()
 }
+
+/// Jacobian w.r.t. collections of Dual numbers, elements of copies
+/// of collections are seeded one by one the same way as in gradient!.
+/// Derivatives of all outputs form one column of Jacobian.
+fn jacobian_iterables(fun: ExprCall, vars: Vec<Var>) -> proc_macro2::TokenStream {
+    // count columns to allocate vector once
+    let count: Vec<_> = vars.iter().map(|Var {place, ..}| {
+        quote! {
+            cnt += #place.len();
+        }
+    }).collect();
+    // eval columns
+    let cols = vars.iter().map(|Var {name, ..}| {
+        quote! {
+            for i in 0..#name.len() {
+                #name[i].seed();
+                let v = #fun;
+                #name[i].unseed();
+                cols.push(v.iter().map(|y| y.der()).collect());
+            }
+        }
+    });
+    let copies = vars.iter().map(Var::copy);
+    // make evaluations
+    quote! { {
+            let mut cnt: usize = 0;
+            #(#count;)*
+            #(#copies)*
+            if cnt == 0 {
+                ::rad::Jacobian::without_inputs(#fun.len())
+            } else {
+                let mut cols: Vec<Vec<f64>> = Vec::with_capacity(cnt);
+                #(#cols;)*
+                ::rad::Jacobian::from_columns(cols)
+            }
+        }
+    }
+}
+
+/// Jacobian w.r.t. fixed list of variables. Variables are shadowed
+/// by DualN numbers seeded in own directions, so function is
+/// evaluated once and gradient of each output forms one row.
+fn jacobian_fixed(fun: ExprCall, wrt: Vec<Ident>) -> proc_macro2::TokenStream {
+    let n = wrt.len();
+    quote! { {
+            let [#(#wrt),*] = ::rad::DualN::<#n>::variables([#(#wrt.val()),*]);
+            let v = #fun;
+            ::rad::Jacobian::from_rows(v.iter().map(|y| y.grad().to_vec()).collect())
+        }
+    }
+}
+
+/// Hessian of scalar function.
+#[proc_macro_hack]
+pub fn hessian(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as GradientArgs);
+    let (mut fun, wrt) = match args.unweighted() {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let out = match wrt {
+        Wrt::Iterables(wrt) => match bind_vars(&mut fun, wrt) {
+            Ok(vars) => hessian_iterables(fun, vars),
+            Err(err) => err.to_compile_error(),
+        },
+        Wrt::Fixed(wrt) => hessian_fixed(fun, wrt),
+    };
+    out.into()
+}
+
+/// Variable of macro and identifier which function call uses for it.
+struct Var {
+    /// place expression which holds variable
+    place: Expr,
+    /// identifier bound to seeded copy of variable
+    name: Ident,
+}
+
+impl Var {
+    /// Bind copy of variable, which elements are seeded in place
+    /// one by one, to identifier used in function call.
+    fn copy(&self) -> proc_macro2::TokenStream {
+        let Var {place, name} = self;
+        quote! { let mut #name = #place.to_owned(); }
+    }
+}
+
+/// Bind variables to identifiers. Identifier is shadowed by seeded copy,
+/// other place expressions, like 'self.x' or 'v[0]', are replaced in
+/// function call by local variables bound to seeded copies.
+fn bind_vars(fun: &mut ExprCall, wrt: Vec<Expr>) -> Result<Vec<Var>> {
+    wrt.into_iter()
+        .enumerate()
+        .map(|(i, place)| {
+            if let Expr::Path(path) = &place {
+                if let Some(name) = path.path.get_ident() {
+                    let name = name.clone();
+                    return Ok(Var { place, name });
+                }
+            }
+            let mut replace = Replace {
+                place: quote!(#place).to_string(),
+                name: format_ident!("__rad_var{}", i),
+                found: false,
+            };
+            replace.visit_expr_call_mut(fun);
+            if !replace.found {
+                return Err(syn::Error::new_spanned(place, "variable doesn't occur in function call"));
+            }
+            Ok(Var { place, name: replace.name })
+        })
+        .collect()
+}
+
+/// Replaces place expression by identifier.
+struct Replace {
+    place: String,
+    name: Ident,
+    found: bool,
+}
+
+impl VisitMut for Replace {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        if quote!(#expr).to_string() == self.place {
+            let name = &self.name;
+            *expr = syn::parse_quote!(#name);
+            self.found = true;
+        } else {
+            visit_mut::visit_expr_mut(self, expr);
+        }
+    }
+}
+
+/// Hessian w.r.t. collections of numbers. For each pair of inputs
+/// collections are shadowed by vectors of HyperDual numbers seeded
+/// in pair directions, so function must accept HyperDual numbers.
+fn hessian_iterables(fun: ExprCall, vars: Vec<Var>) -> proc_macro2::TokenStream {
+    let (places, names): (Vec<_>, Vec<_>) = vars.iter().map(|Var {place, name}| (place, name)).unzip();
+    quote! { {
+            let vals: Vec<Vec<f64>> = vec![#(#places.iter().map(|v| v.val()).collect()),*];
+            let cnt: usize = vals.iter().map(Vec::len).sum();
+            let mut hess = vec![vec![0.0; cnt]; cnt];
+            for i in 0..cnt {
+                for j in i..cnt {
+                    let mut k: usize = 0;
+                    let mut vars = vals.iter().map(|c| {
+                        c.iter().map(|v| {
+                            let var = ::rad::HyperDual::seeded(*v, (k == i) as u8 as f64, (k == j) as u8 as f64);
+                            k += 1;
+                            var
+                        }).collect::<Vec<_>>()
+                    });
+                    #(let #names = vars.next().unwrap();)*
+                    let v = #fun;
+                    hess[i][j] = v.der12();
+                    hess[j][i] = v.der12();
+                }
+            }
+            hess
+        }
+    }
+}
+
+/// Hessian w.r.t. fixed list of variables. For each pair of inputs
+/// variables are shadowed by HyperDual numbers seeded in pair
+/// directions, so function must accept HyperDual numbers.
+fn hessian_fixed(fun: ExprCall, wrt: Vec<Ident>) -> proc_macro2::TokenStream {
+    let n = wrt.len();
+    quote! { {
+            let vals = [#(#wrt.val()),*];
+            let mut hess = vec![vec![0.0; #n]; #n];
+            for i in 0..#n {
+                for j in i..#n {
+                    let [#(#wrt),*] = ::rad::HyperDual::variables(vals, i, j);
+                    let v = #fun;
+                    hess[i][j] = v.der12();
+                    hess[j][i] = v.der12();
+                }
+            }
+            hess
+        }
+    }
+}
+
+/// Derivatives macro arguments
+struct DerivativesArgs {
+    /// target function to calculate derivatives
+    fun: ExprCall,
+    /// variable w.r.t. we want to get derivatives
+    wrt: Ident,
+    /// highest order of derivatives, compile time constant
+    order: Expr,
+}
+
+impl Parse for DerivativesArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let fun: ExprCall = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let wrt: Ident = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let order: Expr = input.parse()?;
+        Ok(DerivativesArgs {
+            fun,
+            wrt,
+            order,
+        })
+    }
+}
+
+/// Derivatives of univariate function up to given order. Variable
+/// is shadowed by Taylor series, so function must accept Taylor.
+#[proc_macro_hack]
+pub fn derivatives(input: TokenStream) -> TokenStream {
+    let DerivativesArgs {fun, wrt, order} = parse_macro_input!(input as DerivativesArgs);
+    let out = quote! { {
+            let #wrt = ::rad::Taylor::<{ #order }>::variable(#wrt.val());
+            #fun.derivatives()
+        }
+    };
+    out.into()
+}
+
+/// Generate Dual version 'f_dual' of function 'f' which takes
+/// and returns f64 numbers. With 'grad' argument 'f_grad' returning
+/// value and gradient of function is generated as well.
+#[proc_macro_attribute]
+pub fn differentiable(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as differentiable::DifferentiableArgs);
+    let fun = parse_macro_input!(input as ItemFn);
+    differentiable::differentiable(args, fun).into()
+}
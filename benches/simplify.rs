@@ -0,0 +1,41 @@
+// Expressions which are simplified by Xpr nodes. To compare them with
+// evaluation node by node, run this bench on the tree before constant
+// folding with '-- --save-baseline unsimplified' and then on the
+// current tree with '-- --baseline unsimplified'.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rad::{Dual, DynXpr};
+
+fn identity(c: &mut Criterion) {
+    let x = Dual::from(1.5).seeded();
+    let y = Dual::from(0.5);
+    c.bench_function("identity", |b| {
+        b.iter(|| Dual::from(black_box(x) * 1.0 + 0.0 - black_box(y) / 1.0))
+    });
+}
+
+fn double_negation(c: &mut Criterion) {
+    let x = Dual::from(1.5).seeded();
+    let y = Dual::from(0.5);
+    c.bench_function("double_negation", |b| {
+        b.iter(|| Dual::from(-(-(black_box(x) * black_box(y)))))
+    });
+}
+
+fn constant_operand(c: &mut Criterion) {
+    let x = Dual::from(1.5).seeded();
+    c.bench_function("constant_operand", |b| {
+        b.iter(|| Dual::from(2.0 / black_box(x).exp() - 3.0 * black_box(x).sin()))
+    });
+}
+
+fn constant_folding(c: &mut Criterion) {
+    let x = Dual::from(1.5).seeded();
+    let scale = DynXpr::new((DynXpr::new(2.0) + 3.0) * 0.5);
+    c.bench_function("constant_folding", |b| {
+        b.iter(|| Dual::from(scale.clone() * black_box(x)))
+    });
+}
+
+criterion_group!(benches, identity, double_negation, constant_operand, constant_folding);
+criterion_main!(benches);
@@ -0,0 +1,152 @@
+use std::fmt;
+use xprlib::Dual;
+
+/// Settings of finite difference check.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Config {
+    /// 'step' - relative step of central difference, scaled by max(|x|, 1).
+    pub step: f64,
+    /// 'abs_tol' - derivative passes if absolute error is not greater.
+    pub abs_tol: f64,
+    /// 'rel_tol' - derivative passes if relative error is not greater.
+    pub rel_tol: f64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            step: 1e-3,
+            abs_tol: 1e-6,
+            rel_tol: 1e-6,
+        }
+    }
+}
+
+/// Result of check of one partial derivative.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InputReport {
+    /// 'index' - index of input.
+    pub index: usize,
+    /// 'ad' - derivative evaluated with Dual numbers.
+    pub ad: f64,
+    /// 'central' - central finite difference.
+    pub central: f64,
+    /// 'richardson' - Richardson extrapolation of central differences,
+    /// reference derivative errors are measured against.
+    pub richardson: f64,
+    /// 'abs_err' - absolute error of 'ad'.
+    pub abs_err: f64,
+    /// 'rel_err' - relative error of 'ad'.
+    pub rel_err: f64,
+    /// 'passed' - any of errors is within tolerance.
+    pub passed: bool,
+}
+
+/// Result of gradient check, one entry per input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    /// 'value' - value of function at checked point.
+    pub value: f64,
+    /// 'inputs' - reports of partial derivatives.
+    pub inputs: Vec<InputReport>,
+}
+
+impl Report {
+    /// Check if all partial derivatives passed.
+    pub fn passed(&self) -> bool {
+        self.inputs.iter().all(|input| input.passed)
+    }
+
+    /// Reports of partial derivatives which failed.
+    pub fn failures(&self) -> impl Iterator<Item = &InputReport> {
+        self.inputs.iter().filter(|input| !input.passed)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>5} {:>14} {:>14} {:>14} {:>10} {:>10}  verdict",
+            "input", "ad", "central", "richardson", "abs err", "rel err"
+        )?;
+        for input in self.inputs.iter() {
+            writeln!(
+                f,
+                "{:>5} {:>14.6e} {:>14.6e} {:>14.6e} {:>10.2e} {:>10.2e}  {}",
+                input.index,
+                input.ad,
+                input.central,
+                input.richardson,
+                input.abs_err,
+                input.rel_err,
+                if input.passed { "ok" } else { "FAILED" }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Compare gradient of scalar function evaluated with Dual numbers,
+/// the same way 'gradient!' does, against finite differences of its values.
+///
+/// # Arguments
+///
+/// 'f' - function which gradient we want to check.
+/// 'x' - point at which gradient is checked.
+/// 'config' - step and tolerances of check.
+pub fn gradient<F>(f: F, x: &[f64], config: &Config) -> Report
+where
+    F: Fn(&[Dual]) -> Dual,
+{
+    let (value, grad) = crate::function::gradient(&f, x);
+    let eval = |point: &[f64]| {
+        let point: Vec<Dual> = point.iter().map(|x| Dual::from(*x)).collect();
+        f(&point).val()
+    };
+    let inputs = grad
+        .into_iter()
+        .enumerate()
+        .map(|(index, ad)| {
+            let h = config.step * x[index].abs().max(1.0);
+            let central = |h: f64| {
+                let mut fwd = x.to_vec();
+                let mut bwd = x.to_vec();
+                fwd[index] += h;
+                bwd[index] -= h;
+                (eval(&fwd) - eval(&bwd)) / (2.0 * h)
+            };
+            let (coarse, fine) = (central(h), central(h / 2.0));
+            // error of central difference is O(h^2), so it cancels out
+            let richardson = (4.0 * fine - coarse) / 3.0;
+            let abs_err = (ad - richardson).abs();
+            let scale = ad.abs().max(richardson.abs());
+            let rel_err = if scale > 0.0 { abs_err / scale } else { 0.0 };
+            InputReport {
+                index,
+                ad,
+                central: coarse,
+                richardson,
+                abs_err,
+                rel_err,
+                passed: abs_err <= config.abs_tol || rel_err <= config.rel_tol,
+            }
+        })
+        .collect();
+    Report { value, inputs }
+}
+
+/// Compare derivative of scalar function of one variable evaluated
+/// with Dual numbers against finite differences of its values.
+///
+/// # Arguments
+///
+/// 'f' - function which derivative we want to check.
+/// 'x' - point at which derivative is checked.
+/// 'config' - step and tolerances of check.
+pub fn derivative<F>(f: F, x: f64, config: &Config) -> Report
+where
+    F: Fn(Dual) -> Dual,
+{
+    gradient(|x: &[Dual]| f(x[0]), &[x], config)
+}
@@ -0,0 +1,46 @@
+use xprlib::Dual;
+
+/// Value and derivative of scalar function of one variable.
+/// Unlike 'derivative!' function may be any callable: closure
+/// stored in struct, trait object or generic parameter.
+///
+/// # Arguments
+///
+/// 'f' - function which derivative we want to eval.
+/// 'x' - point at which derivative is evaluated.
+pub fn derivative<F>(f: F, x: f64) -> (f64, f64)
+where
+    F: Fn(Dual) -> Dual,
+{
+    let res = f(Dual::from(x).seeded());
+    (res.val(), res.der())
+}
+
+/// Value and gradient of scalar function. Function is evaluated
+/// for copies of inputs with one seeded element in turn,
+/// the same way 'gradient!' does.
+///
+/// # Arguments
+///
+/// 'f' - function which gradient we want to eval.
+/// 'x' - point at which gradient is evaluated.
+pub fn gradient<F>(f: F, x: &[f64]) -> (f64, Vec<f64>)
+where
+    F: Fn(&[Dual]) -> Dual,
+{
+    let x: Vec<Dual> = x.iter().map(|x| Dual::from(*x)).collect();
+    if x.is_empty() {
+        return (f(&x).val(), Vec::new());
+    }
+    let mut val = 0.0;
+    let grad = (0..x.len())
+        .map(|i| {
+            let mut seeded = x.clone();
+            seeded[i].seed();
+            let res = f(&seeded);
+            val = res.val();
+            res.der()
+        })
+        .collect();
+    (val, grad)
+}
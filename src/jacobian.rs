@@ -0,0 +1,142 @@
+use std::ops::Index;
+use xprlib::Dual;
+
+/// Jacobian matrix of vector valued function. Row 'i'
+/// holds derivatives of i-th output w.r.t. all inputs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Jacobian {
+    /// 'rows' - count of function outputs.
+    rows: usize,
+    /// 'cols' - count of function inputs.
+    cols: usize,
+    /// 'data' - elements in row-major order.
+    data: Vec<f64>,
+}
+
+impl Jacobian {
+    /// Create Jacobian from columns, i-th column
+    /// holds derivatives of all outputs w.r.t. i-th input.
+    ///
+    /// # Arguments
+    ///
+    /// 'columns' - columns of Jacobian.
+    ///
+    /// # Panics
+    ///
+    /// If columns have different length.
+    pub fn from_columns(columns: Vec<Vec<f64>>) -> Self {
+        let rows = columns.first().map_or(0, Vec::len);
+        let cols = columns.len();
+        assert!(
+            columns.iter().all(|c| c.len() == rows),
+            "function returns different count of outputs"
+        );
+        let data = (0..rows)
+            .flat_map(|i| columns.iter().map(move |c| c[i]))
+            .collect();
+        Self { rows, cols, data }
+    }
+
+    /// Create Jacobian from rows, i-th row
+    /// holds derivatives of i-th output w.r.t. all inputs.
+    ///
+    /// # Arguments
+    ///
+    /// 'rows' - rows of Jacobian.
+    ///
+    /// # Panics
+    ///
+    /// If rows have different length.
+    pub fn from_rows(rows: Vec<Vec<f64>>) -> Self {
+        let cols = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|r| r.len() == cols),
+            "rows have different count of inputs"
+        );
+        Self {
+            rows: rows.len(),
+            cols,
+            data: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    /// Create Jacobian of function without inputs, which has rows but no columns.
+    ///
+    /// # Arguments
+    ///
+    /// 'rows' - count of function outputs.
+    pub fn without_inputs(rows: usize) -> Self {
+        Self {
+            rows,
+            cols: 0,
+            data: Vec::new(),
+        }
+    }
+
+    /// Shape of Jacobian, count of outputs and count of inputs.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    /// Derivative of output 'row' w.r.t. input 'col'.
+    pub fn get(&self, row: usize, col: usize) -> f64 {
+        self[(row, col)]
+    }
+
+    /// Derivatives of output 'row' w.r.t. all inputs.
+    pub fn row(&self, row: usize) -> &[f64] {
+        &self.data[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// Derivatives of all outputs w.r.t. input 'col'.
+    pub fn column(&self, col: usize) -> Vec<f64> {
+        (0..self.rows).map(|row| self[(row, col)]).collect()
+    }
+
+    /// Elements in row-major order.
+    pub fn row_major(&self) -> &[f64] {
+        &self.data
+    }
+
+    /// Elements in column-major order.
+    pub fn col_major(&self) -> Vec<f64> {
+        (0..self.cols).flat_map(|col| self.column(col)).collect()
+    }
+}
+
+impl Index<(usize, usize)> for Jacobian {
+    type Output = f64;
+    fn index(&self, (row, col): (usize, usize)) -> &f64 {
+        assert!(col < self.cols, "column index is out of range");
+        &self.data[row * self.cols + col]
+    }
+}
+
+/// Jacobian of vector valued function. Function is evaluated
+/// for copies of inputs with one seeded element in turn,
+/// the same way 'gradient!' does.
+///
+/// # Arguments
+///
+/// 'f' - function which Jacobian we want to eval.
+/// 'x' - point at which Jacobian is evaluated.
+pub fn jacobian<F, Y>(f: F, x: &[f64]) -> Jacobian
+where
+    F: Fn(&[Dual]) -> Y,
+    Y: AsRef<[Dual]>,
+{
+    let x: Vec<Dual> = x.iter().map(|x| Dual::from(*x)).collect();
+    if x.is_empty() {
+        // there are no columns to count outputs, so function is evaluated once
+        return Jacobian::without_inputs(f(&x).as_ref().len());
+    }
+    let columns = (0..x.len())
+        .map(|i| {
+            let mut seeded = x.clone();
+            seeded[i].seed();
+            let v = f(&seeded);
+            v.as_ref().iter().map(Dual::der).collect()
+        })
+        .collect();
+    Jacobian::from_columns(columns)
+}
@@ -3,7 +3,27 @@ use proc_macro_hack::proc_macro_hack;
 extern crate xprlib;
 pub use xprlib::*;
 
+pub mod check;
+pub mod function;
+pub mod jacobian;
+pub mod optim;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+pub mod solvers;
+
+pub use function::*;
+pub use jacobian::*;
+#[cfg(feature = "rayon")]
+pub use parallel::*;
+
 #[proc_macro_hack]
 pub use adtools::gradient;
 #[proc_macro_hack]
 pub use adtools::derivative;
+#[proc_macro_hack]
+pub use adtools::jacobian;
+#[proc_macro_hack]
+pub use adtools::hessian;
+#[proc_macro_hack]
+pub use adtools::derivatives;
+pub use adtools::differentiable;
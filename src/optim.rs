@@ -0,0 +1,575 @@
+use crate::function::gradient;
+use std::collections::VecDeque;
+use std::fmt;
+use xprlib::Dual;
+
+/// Limit of trial steps of one line search.
+const MAX_TRIALS: usize = 60;
+
+/// Rule of step length along search direction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineSearch {
+    /// Step is shrunk until sufficient decrease condition holds.
+    ///
+    /// 'c1' - sufficient decrease parameter.
+    /// 'shrink' - factor step is multiplied by after rejection.
+    Armijo { c1: f64, shrink: f64 },
+    /// Step is bracketed and zoomed until strong Wolfe conditions hold.
+    ///
+    /// 'c1' - sufficient decrease parameter.
+    /// 'c2' - curvature parameter, 0.9 suits quasi-Newton
+    /// methods and 0.1 suits conjugate gradient.
+    Wolfe { c1: f64, c2: f64 },
+}
+
+impl LineSearch {
+    /// Backtracking with common parameters.
+    pub fn armijo() -> Self {
+        Self::Armijo { c1: 1e-4, shrink: 0.5 }
+    }
+
+    /// Strong Wolfe conditions with common parameters.
+    pub fn wolfe() -> Self {
+        Self::Wolfe { c1: 1e-4, c2: 0.9 }
+    }
+}
+
+/// Settings of optimizers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Config {
+    /// 'grad_tol' - converged if Euclidean norm of gradient is not greater.
+    pub grad_tol: f64,
+    /// 'f_tol' - converged if decrease of objective is not greater
+    /// than f_tol * max(|f|, 1), by default only if it doesn't decrease.
+    pub f_tol: f64,
+    /// 'max_iter' - limit of iterations.
+    pub max_iter: usize,
+    /// 'line_search' - step rule of gradient descent, conjugate gradient and L-BFGS.
+    pub line_search: LineSearch,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            grad_tol: 1e-8,
+            f_tol: 0.0,
+            max_iter: 1000,
+            line_search: LineSearch::wolfe(),
+        }
+    }
+}
+
+/// Parameters of Adam optimizer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Adam {
+    /// 'learning_rate' - step size.
+    pub learning_rate: f64,
+    /// 'beta1' - decay rate of the first moment.
+    pub beta1: f64,
+    /// 'beta2' - decay rate of the second moment.
+    pub beta2: f64,
+    /// 'epsilon' - added to denominator to avoid division by zero.
+    pub epsilon: f64,
+}
+
+impl Default for Adam {
+    fn default() -> Self {
+        Self {
+            learning_rate: 1e-3,
+            beta1: 0.9,
+            beta2: 0.999,
+            epsilon: 1e-8,
+        }
+    }
+}
+
+/// Reason optimizer stopped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Termination {
+    /// Norm of gradient is within 'grad_tol'.
+    GradientTolerance,
+    /// Decrease of objective is within 'f_tol'.
+    FunctionTolerance,
+    /// Iteration limit is reached.
+    MaxIterations,
+    /// Line search didn't find acceptable step.
+    LineSearchFailed,
+    /// Objective or its gradient is not finite.
+    NotFinite,
+}
+
+impl fmt::Display for Termination {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::GradientTolerance => "gradient norm is within tolerance",
+            Self::FunctionTolerance => "objective decrease is within tolerance",
+            Self::MaxIterations => "iteration limit is reached",
+            Self::LineSearchFailed => "line search failed",
+            Self::NotFinite => "objective or gradient is not finite",
+        })
+    }
+}
+
+/// Result of optimization.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    /// 'x' - final point.
+    pub x: Vec<f64>,
+    /// 'value' - objective at final point.
+    pub value: f64,
+    /// 'iterations' - count of made steps.
+    pub iterations: usize,
+    /// 'evaluations' - count of objective calls, gradient
+    /// costs one call per input.
+    pub evaluations: usize,
+    /// 'grad_norm' - Euclidean norm of gradient at final point.
+    pub grad_norm: f64,
+    /// 'termination' - reason optimizer stopped.
+    pub termination: Termination,
+}
+
+impl Report {
+    /// Check if optimizer stopped by one of tolerances.
+    pub fn converged(&self) -> bool {
+        matches!(
+            self.termination,
+            Termination::GradientTolerance | Termination::FunctionTolerance
+        )
+    }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b.iter()).map(|(a, b)| a * b).sum()
+}
+
+fn norm(v: &[f64]) -> f64 {
+    dot(v, v).sqrt()
+}
+
+/// Objective which counts its calls.
+struct Objective<F> {
+    f: F,
+    evaluations: usize,
+}
+
+impl<F: Fn(&[Dual]) -> Dual> Objective<F> {
+    /// Value and gradient at point.
+    fn gradient(&mut self, x: &[f64]) -> (f64, Vec<f64>) {
+        self.evaluations += x.len().max(1);
+        gradient(&self.f, x)
+    }
+
+    /// Value and directional derivative at 'x + alpha * d', inputs
+    /// are seeded with direction, so one call is enough.
+    fn directional(&mut self, x: &[f64], d: &[f64], alpha: f64) -> (f64, f64) {
+        self.evaluations += 1;
+        let args: Vec<Dual> = x
+            .iter()
+            .zip(d.iter())
+            .map(|(x, d)| Dual::from(x + alpha * d).seeded_with(*d))
+            .collect();
+        let res = (self.f)(&args);
+        (res.val(), res.der())
+    }
+}
+
+/// One dimensional restriction of objective along search direction.
+struct Ray<'a, F> {
+    objective: &'a mut Objective<F>,
+    x: &'a [f64],
+    d: &'a [f64],
+    /// 'f0' - objective at start.
+    f0: f64,
+    /// 'dphi0' - directional derivative at start, negative.
+    dphi0: f64,
+}
+
+impl<F: Fn(&[Dual]) -> Dual> Ray<'_, F> {
+    fn eval(&mut self, alpha: f64) -> (f64, f64) {
+        let (phi, dphi) = self.objective.directional(self.x, self.d, alpha);
+        // steps into non finite region are treated as too long
+        if phi.is_finite() {
+            (phi, dphi)
+        } else {
+            (f64::INFINITY, f64::NAN)
+        }
+    }
+
+    fn decreases(&self, alpha: f64, phi: f64, c1: f64) -> bool {
+        phi <= self.f0 + c1 * alpha * self.dphi0
+    }
+}
+
+impl LineSearch {
+    /// Find step along direction, result is step and objective there.
+    fn search<F>(&self, ray: &mut Ray<F>, alpha0: f64) -> Option<(f64, f64)>
+    where
+        F: Fn(&[Dual]) -> Dual,
+    {
+        match *self {
+            Self::Armijo { c1, shrink } => {
+                let mut alpha = alpha0;
+                for _ in 0..MAX_TRIALS {
+                    let (phi, _) = ray.eval(alpha);
+                    if ray.decreases(alpha, phi, c1) {
+                        return Some((alpha, phi));
+                    }
+                    alpha *= shrink;
+                }
+                None
+            }
+            Self::Wolfe { c1, c2 } => {
+                let (mut prev, mut phi_prev, mut dphi_prev) = (0.0, ray.f0, ray.dphi0);
+                let mut alpha = alpha0;
+                for i in 0..MAX_TRIALS {
+                    let (phi, dphi) = ray.eval(alpha);
+                    if !ray.decreases(alpha, phi, c1) || (i > 0 && phi >= phi_prev) {
+                        return zoom(ray, (prev, phi_prev, dphi_prev), (alpha, phi), c1, c2);
+                    }
+                    if dphi.abs() <= -c2 * ray.dphi0 {
+                        return Some((alpha, phi));
+                    }
+                    if dphi >= 0.0 {
+                        return zoom(ray, (alpha, phi, dphi), (prev, phi_prev), c1, c2);
+                    }
+                    prev = alpha;
+                    phi_prev = phi;
+                    dphi_prev = dphi;
+                    alpha *= 2.0;
+                }
+                None
+            }
+        }
+    }
+}
+
+/// Shrink interval which contains step satisfying strong Wolfe conditions.
+///
+/// # Arguments
+///
+/// 'ray' - objective along search direction.
+/// 'lo' - step with sufficient decrease, objective and its derivative there.
+/// 'hi' - other end of interval and objective there.
+/// 'c1' - sufficient decrease parameter.
+/// 'c2' - curvature parameter.
+fn zoom<F>(ray: &mut Ray<F>, lo: (f64, f64, f64), hi: (f64, f64), c1: f64, c2: f64) -> Option<(f64, f64)>
+where
+    F: Fn(&[Dual]) -> Dual,
+{
+    let (mut lo, mut phi_lo, mut dphi_lo) = lo;
+    let (mut hi, mut phi_hi) = hi;
+    for _ in 0..MAX_TRIALS {
+        // minimum of quadratic interpolation, kept away from ends of interval
+        let width = hi - lo;
+        let quad = lo - dphi_lo * width * width / (2.0 * (phi_hi - phi_lo - dphi_lo * width));
+        let (a, b) = (lo + 0.1 * width, hi - 0.1 * width);
+        let alpha = if quad.is_finite() && quad >= a.min(b) && quad <= a.max(b) {
+            quad
+        } else {
+            0.5 * (lo + hi)
+        };
+        let (phi, dphi) = ray.eval(alpha);
+        if !ray.decreases(alpha, phi, c1) || phi >= phi_lo {
+            hi = alpha;
+            phi_hi = phi;
+        } else {
+            if dphi.abs() <= -c2 * ray.dphi0 {
+                return Some((alpha, phi));
+            }
+            if dphi * (hi - lo) >= 0.0 {
+                hi = lo;
+                phi_hi = phi_lo;
+            }
+            lo = alpha;
+            phi_lo = phi;
+            dphi_lo = dphi;
+        }
+    }
+    // step with sufficient decrease is better than nothing
+    if lo > 0.0 {
+        Some((lo, phi_lo))
+    } else {
+        None
+    }
+}
+
+/// Rule of search direction of line search methods.
+trait Direction {
+    /// Search direction at point with gradient 'g'.
+    fn direction(&mut self, g: &[f64]) -> Vec<f64>;
+
+    /// Remember accepted step 's' and change of gradient 'y'.
+    fn update(&mut self, _s: &[f64], _y: &[f64]) {}
+
+    /// Forget history after direction which isn't descent one,
+    /// next direction is steepest descent.
+    fn reset(&mut self) {}
+
+    /// Direction is scaled, so unit step should be tried first.
+    fn unit_step(&self) -> bool {
+        false
+    }
+}
+
+/// Steepest descent.
+struct Steepest;
+
+impl Direction for Steepest {
+    fn direction(&mut self, g: &[f64]) -> Vec<f64> {
+        g.iter().map(|g| -g).collect()
+    }
+}
+
+/// Polak–Ribière conjugate gradient with restart on negative beta.
+#[derive(Default)]
+struct Conjugate {
+    /// 'prev' - previous gradient and direction.
+    prev: Option<(Vec<f64>, Vec<f64>)>,
+}
+
+impl Direction for Conjugate {
+    fn direction(&mut self, g: &[f64]) -> Vec<f64> {
+        let mut d: Vec<f64> = g.iter().map(|g| -g).collect();
+        if let Some((g_prev, d_prev)) = self.prev.as_ref() {
+            let change: Vec<f64> = g.iter().zip(g_prev.iter()).map(|(g, p)| g - p).collect();
+            let beta = (dot(g, &change) / dot(g_prev, g_prev)).max(0.0);
+            for (d, p) in d.iter_mut().zip(d_prev.iter()) {
+                *d += beta * p;
+            }
+        }
+        self.prev = Some((g.to_vec(), d.clone()));
+        d
+    }
+
+    fn reset(&mut self) {
+        self.prev = None;
+    }
+}
+
+/// Limited memory BFGS, inverse Hessian is applied by two-loop recursion.
+struct Lbfgs {
+    /// 'memory' - count of kept pairs.
+    memory: usize,
+    /// 'history' - steps, gradient changes and reciprocals of their products.
+    history: VecDeque<(Vec<f64>, Vec<f64>, f64)>,
+}
+
+impl Direction for Lbfgs {
+    fn direction(&mut self, g: &[f64]) -> Vec<f64> {
+        let mut q: Vec<f64> = g.iter().map(|g| -g).collect();
+        let mut alphas = Vec::with_capacity(self.history.len());
+        for (s, y, rho) in self.history.iter().rev() {
+            let alpha = rho * dot(s, &q);
+            for (q, y) in q.iter_mut().zip(y.iter()) {
+                *q -= alpha * y;
+            }
+            alphas.push(alpha);
+        }
+        if let Some((s, y, _)) = self.history.back() {
+            let gamma = dot(s, y) / dot(y, y);
+            q.iter_mut().for_each(|q| *q *= gamma);
+        }
+        for ((s, y, rho), alpha) in self.history.iter().zip(alphas.iter().rev()) {
+            let beta = rho * dot(y, &q);
+            for (q, s) in q.iter_mut().zip(s.iter()) {
+                *q += (alpha - beta) * s;
+            }
+        }
+        q
+    }
+
+    fn update(&mut self, s: &[f64], y: &[f64]) {
+        let sy = dot(s, y);
+        // pairs without positive curvature would break positive definiteness
+        if sy > 0.0 && self.memory > 0 {
+            if self.history.len() == self.memory {
+                self.history.pop_front();
+            }
+            self.history.push_back((s.to_vec(), y.to_vec(), 1.0 / sy));
+        }
+    }
+
+    fn reset(&mut self) {
+        self.history.clear();
+    }
+
+    fn unit_step(&self) -> bool {
+        !self.history.is_empty()
+    }
+}
+
+/// Iterations shared by line search methods.
+fn minimize<F, D>(f: F, x0: &[f64], config: &Config, mut rule: D) -> Report
+where
+    F: Fn(&[Dual]) -> Dual,
+    D: Direction,
+{
+    let mut objective = Objective { f, evaluations: 0 };
+    let mut x = x0.to_vec();
+    let (mut fx, mut g) = objective.gradient(&x);
+    let mut iterations = 0;
+    // step and directional derivative of previous iteration
+    let mut prev: Option<(f64, f64)> = None;
+    let termination = loop {
+        let grad_norm = norm(&g);
+        if !fx.is_finite() || !grad_norm.is_finite() {
+            break Termination::NotFinite;
+        }
+        if grad_norm <= config.grad_tol {
+            break Termination::GradientTolerance;
+        }
+        if iterations == config.max_iter {
+            break Termination::MaxIterations;
+        }
+
+        let mut d = rule.direction(&g);
+        let mut dphi0 = dot(&g, &d);
+        if dphi0 >= 0.0 || dphi0.is_nan() {
+            rule.reset();
+            d = rule.direction(&g);
+            dphi0 = dot(&g, &d);
+        }
+        let alpha0 = match prev {
+            _ if rule.unit_step() => 1.0,
+            Some((alpha, dphi)) => (alpha * dphi / dphi0).min(1.0 / f64::EPSILON),
+            None => (1.0 / grad_norm).min(1.0),
+        };
+        let mut ray = Ray {
+            objective: &mut objective,
+            x: &x,
+            d: &d,
+            f0: fx,
+            dphi0,
+        };
+        let (alpha, _) = match config.line_search.search(&mut ray, alpha0) {
+            Some(step) => step,
+            None => break Termination::LineSearchFailed,
+        };
+        iterations += 1;
+
+        let s: Vec<f64> = d.iter().map(|d| alpha * d).collect();
+        let next: Vec<f64> = x.iter().zip(s.iter()).map(|(x, s)| x + s).collect();
+        let (f_next, g_next) = objective.gradient(&next);
+        let y: Vec<f64> = g_next.iter().zip(g.iter()).map(|(n, g)| n - g).collect();
+        rule.update(&s, &y);
+        let decrease = fx - f_next;
+        x = next;
+        g = g_next;
+        prev = Some((alpha, dphi0));
+        let scale = fx.abs().max(1.0);
+        fx = f_next;
+        if decrease <= config.f_tol * scale && norm(&g) > config.grad_tol {
+            break Termination::FunctionTolerance;
+        }
+    };
+    Report {
+        x,
+        value: fx,
+        iterations,
+        evaluations: objective.evaluations,
+        grad_norm: norm(&g),
+        termination,
+    }
+}
+
+/// Minimize objective by gradient descent with line search
+/// set in 'config'.
+///
+/// # Arguments
+///
+/// 'f' - objective.
+/// 'x0' - initial point.
+/// 'config' - tolerances, limits and line search.
+pub fn gradient_descent<F>(f: F, x0: &[f64], config: &Config) -> Report
+where
+    F: Fn(&[Dual]) -> Dual,
+{
+    minimize(f, x0, config, Steepest)
+}
+
+/// Minimize objective by nonlinear conjugate gradient method with
+/// Polak–Ribière update, direction is reset to steepest descent
+/// if it isn't descent one. Wolfe line search with 'c2' = 0.1 is
+/// recommended.
+///
+/// # Arguments
+///
+/// 'f' - objective.
+/// 'x0' - initial point.
+/// 'config' - tolerances, limits and line search.
+pub fn conjugate_gradient<F>(f: F, x0: &[f64], config: &Config) -> Report
+where
+    F: Fn(&[Dual]) -> Dual,
+{
+    minimize(f, x0, config, Conjugate::default())
+}
+
+/// Minimize objective by limited memory BFGS method.
+///
+/// # Arguments
+///
+/// 'f' - objective.
+/// 'x0' - initial point.
+/// 'memory' - count of last steps which approximate inverse Hessian.
+/// 'config' - tolerances, limits and line search.
+pub fn lbfgs<F>(f: F, x0: &[f64], memory: usize, config: &Config) -> Report
+where
+    F: Fn(&[Dual]) -> Dual,
+{
+    let rule = Lbfgs {
+        memory,
+        history: VecDeque::with_capacity(memory),
+    };
+    minimize(f, x0, config, rule)
+}
+
+/// Minimize objective by Adam method, step of each input is scaled
+/// by running moments of gradient. Line search and 'f_tol' are not
+/// used, iterations stop by 'grad_tol' and 'max_iter'.
+///
+/// # Arguments
+///
+/// 'f' - objective.
+/// 'x0' - initial point.
+/// 'params' - learning rate and decay rates.
+/// 'config' - tolerances and limits.
+pub fn adam<F>(f: F, x0: &[f64], params: &Adam, config: &Config) -> Report
+where
+    F: Fn(&[Dual]) -> Dual,
+{
+    let mut objective = Objective { f, evaluations: 0 };
+    let mut x = x0.to_vec();
+    let mut m = vec![0.0; x.len()];
+    let mut v = vec![0.0; x.len()];
+    let mut iterations = 0;
+    let (fx, g, termination) = loop {
+        let (fx, g) = objective.gradient(&x);
+        let grad_norm = norm(&g);
+        if !fx.is_finite() || !grad_norm.is_finite() {
+            break (fx, g, Termination::NotFinite);
+        }
+        if grad_norm <= config.grad_tol {
+            break (fx, g, Termination::GradientTolerance);
+        }
+        if iterations == config.max_iter {
+            break (fx, g, Termination::MaxIterations);
+        }
+        iterations += 1;
+
+        let t = iterations as i32;
+        let (c1, c2) = (1.0 - params.beta1.powi(t), 1.0 - params.beta2.powi(t));
+        for (((x, m), v), g) in x.iter_mut().zip(m.iter_mut()).zip(v.iter_mut()).zip(g.iter()) {
+            *m = params.beta1 * *m + (1.0 - params.beta1) * g;
+            *v = params.beta2 * *v + (1.0 - params.beta2) * g * g;
+            *x -= params.learning_rate * (*m / c1) / ((*v / c2).sqrt() + params.epsilon);
+        }
+    };
+    Report {
+        x,
+        value: fx,
+        iterations,
+        evaluations: objective.evaluations,
+        grad_norm: norm(&g),
+        termination,
+    }
+}
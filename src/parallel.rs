@@ -0,0 +1,54 @@
+use crate::jacobian::Jacobian;
+use rayon::prelude::*;
+use xprlib::Dual;
+
+/// Gradient of scalar function, directional derivatives are
+/// evaluated in parallel. Result is in the same order as
+/// 'gradient!' returns it.
+///
+/// # Arguments
+///
+/// 'f' - function which gradient we want to eval.
+/// 'x' - point at which gradient is evaluated.
+pub fn par_gradient<F>(f: F, x: &[f64]) -> Vec<f64>
+where
+    F: Fn(&[Dual]) -> Dual + Sync,
+{
+    let x: Vec<Dual> = x.iter().map(|x| Dual::from(*x)).collect();
+    (0..x.len())
+        .into_par_iter()
+        .map(|i| {
+            let mut seeded = x.clone();
+            seeded[i].seed();
+            f(&seeded).der()
+        })
+        .collect()
+}
+
+/// Jacobian of vector valued function, columns are
+/// evaluated in parallel.
+///
+/// # Arguments
+///
+/// 'f' - function which Jacobian we want to eval.
+/// 'x' - point at which Jacobian is evaluated.
+pub fn par_jacobian<F, Y>(f: F, x: &[f64]) -> Jacobian
+where
+    F: Fn(&[Dual]) -> Y + Sync,
+    Y: AsRef<[Dual]>,
+{
+    let x: Vec<Dual> = x.iter().map(|x| Dual::from(*x)).collect();
+    if x.is_empty() {
+        return Jacobian::without_inputs(f(&x).as_ref().len());
+    }
+    let columns = (0..x.len())
+        .into_par_iter()
+        .map(|i| {
+            let mut seeded = x.clone();
+            seeded[i].seed();
+            let v = f(&seeded);
+            v.as_ref().iter().map(Dual::der).collect()
+        })
+        .collect();
+    Jacobian::from_columns(columns)
+}
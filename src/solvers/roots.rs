@@ -0,0 +1,418 @@
+use crate::jacobian::{jacobian, Jacobian};
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::fmt;
+use xprlib::{Dual, HyperDual};
+
+/// Settings of root finders.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Config {
+    /// 'x_tol' - converged if step is not greater than x_tol * max(|x|, 1).
+    pub x_tol: f64,
+    /// 'f_tol' - converged if |f(x)| is not greater, Euclidean norm for systems.
+    pub f_tol: f64,
+    /// 'max_iter' - limit of iterations.
+    pub max_iter: usize,
+    /// 'min_damping' - smallest fraction of step tried by damped methods.
+    pub min_damping: f64,
+    /// 'bracket' - interval where scalar function changes sign. Steps which
+    /// leave it or can't be computed are replaced by bisection.
+    pub bracket: Option<(f64, f64)>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            x_tol: 1e-12,
+            f_tol: 1e-12,
+            max_iter: 100,
+            min_damping: 1e-6,
+            bracket: None,
+        }
+    }
+}
+
+/// Criterion which stopped iterations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Convergence {
+    /// Residual is within 'f_tol'.
+    Residual,
+    /// Step is within 'x_tol'.
+    Step,
+}
+
+/// Found root.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Root<X> {
+    /// 'x' - root.
+    pub x: X,
+    /// 'residual' - |f(x)|, Euclidean norm for systems.
+    pub residual: f64,
+    /// 'iterations' - count of made steps.
+    pub iterations: usize,
+    /// 'evaluations' - count of function calls.
+    pub evaluations: usize,
+    /// 'convergence' - criterion which is met.
+    pub convergence: Convergence,
+}
+
+/// Failure of root finder, 'x' is the last iterate.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RootError<X> {
+    /// Iteration limit is reached.
+    MaxIterations { x: X, residual: f64 },
+    /// Derivative is zero or Jacobian is singular and there is no bracket to fall back to.
+    ZeroDerivative { x: X },
+    /// Function value is not finite.
+    NotFinite { x: X },
+    /// Damped step doesn't decrease residual.
+    LineSearch { x: X, residual: f64 },
+    /// Function has the same sign at both ends of bracket.
+    InvalidBracket { a: f64, b: f64 },
+}
+
+impl<X> fmt::Display for RootError<X> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MaxIterations { residual, .. } => {
+                write!(f, "iteration limit is reached with residual {}", residual)
+            }
+            Self::ZeroDerivative { .. } => f.write_str("derivative is zero or Jacobian is singular"),
+            Self::NotFinite { .. } => f.write_str("function value is not finite"),
+            Self::LineSearch { residual, .. } => {
+                write!(f, "damped step doesn't decrease residual {}", residual)
+            }
+            Self::InvalidBracket { a, b } => {
+                write!(f, "function has the same sign at {} and {}", a, b)
+            }
+        }
+    }
+}
+
+impl<X: fmt::Debug> std::error::Error for RootError<X> {}
+
+/// Result of root finder.
+pub type RootResult<X> = Result<Root<X>, RootError<X>>;
+
+/// Value of function and step to the root from the point,
+/// step is None if it can't be computed.
+type Eval = (f64, Option<f64>);
+
+/// Interval with sign change, function is negative at 'neg' and positive at 'pos'.
+#[derive(Clone, Copy, Debug)]
+struct Bracket {
+    neg: f64,
+    pos: f64,
+}
+
+impl Bracket {
+    fn contains(&self, x: f64) -> bool {
+        x > self.neg.min(self.pos) && x < self.neg.max(self.pos)
+    }
+
+    fn mid(&self) -> f64 {
+        0.5 * (self.neg + self.pos)
+    }
+
+    fn update(&mut self, x: f64, fx: f64) {
+        if fx < 0.0 {
+            self.neg = x;
+        } else {
+            self.pos = x;
+        }
+    }
+}
+
+/// Try damped steps until residual decreases.
+fn line_search<E>(eval: &mut E, x: f64, fx: f64, step: f64, min_damping: f64) -> Option<(f64, Eval)>
+where
+    E: FnMut(f64) -> Eval,
+{
+    let mut damping = 1.0;
+    while damping >= min_damping {
+        let next = x + damping * step;
+        let res = eval(next);
+        if res.0.abs() < fx.abs() {
+            return Some((next, res));
+        }
+        damping *= 0.5;
+    }
+    None
+}
+
+/// Iterations shared by scalar methods.
+///
+/// # Arguments
+///
+/// 'eval' - value of function and step of method at point.
+/// 'x0' - initial guess.
+/// 'config' - tolerances, limits and bracket.
+/// 'damped' - step is damped until residual decreases.
+fn solve<E>(mut eval: E, x0: f64, config: &Config, damped: bool) -> RootResult<f64>
+where
+    E: FnMut(f64) -> Eval,
+{
+    let evaluations = Cell::new(0);
+    let mut eval = |x: f64| {
+        evaluations.set(evaluations.get() + 1);
+        eval(x)
+    };
+    let mut bracket = match config.bracket {
+        Some((a, b)) => {
+            let (fa, fb) = (eval(a).0, eval(b).0);
+            let sign = fa * fb;
+            if sign > 0.0 || sign.is_nan() {
+                return Err(RootError::InvalidBracket { a, b });
+            }
+            Some(if fa < 0.0 {
+                Bracket { neg: a, pos: b }
+            } else {
+                Bracket { neg: b, pos: a }
+            })
+        }
+        None => None,
+    };
+    let mut x = match bracket {
+        Some(bracket) if !bracket.contains(x0) => bracket.mid(),
+        _ => x0,
+    };
+    let (mut fx, mut step) = eval(x);
+    let mut iterations = 0;
+    loop {
+        if !fx.is_finite() {
+            return Err(RootError::NotFinite { x });
+        }
+        if fx.abs() <= config.f_tol {
+            return Ok(Root {
+                x,
+                residual: fx.abs(),
+                iterations,
+                evaluations: evaluations.get(),
+                convergence: Convergence::Residual,
+            });
+        }
+        if iterations == config.max_iter {
+            return Err(RootError::MaxIterations { x, residual: fx.abs() });
+        }
+        iterations += 1;
+        if let Some(bracket) = bracket.as_mut() {
+            bracket.update(x, fx);
+        }
+
+        let target = match (step.filter(|s| s.is_finite()).map(|s| x + s), bracket) {
+            (Some(next), Some(bracket)) if !bracket.contains(next) => None,
+            (None, None) => return Err(RootError::ZeroDerivative { x }),
+            (next, _) => next,
+        };
+        let trial = match target {
+            Some(next) if damped => line_search(&mut eval, x, fx, next - x, config.min_damping),
+            Some(next) => Some((next, eval(next))),
+            None => None,
+        };
+        let (next, res) = match (trial, bracket) {
+            (Some(trial), _) => trial,
+            (None, Some(bracket)) => (bracket.mid(), eval(bracket.mid())),
+            (None, None) => return Err(RootError::LineSearch { x, residual: fx.abs() }),
+        };
+
+        let delta = (next - x).abs();
+        x = next;
+        fx = res.0;
+        step = res.1;
+        if fx.is_finite() && fx.abs() > config.f_tol && delta <= config.x_tol * x.abs().max(1.0) {
+            return Ok(Root {
+                x,
+                residual: fx.abs(),
+                iterations,
+                evaluations: evaluations.get(),
+                convergence: Convergence::Step,
+            });
+        }
+    }
+}
+
+/// Value and Newton step of function at point.
+fn newton_step<F: Fn(Dual) -> Dual>(f: &F, x: f64) -> Eval {
+    let res = f(Dual::from(x).seeded());
+    let step = if res.der() != 0.0 {
+        Some(-res.val() / res.der())
+    } else {
+        None
+    };
+    (res.val(), step)
+}
+
+/// Find root of scalar function by Newton–Raphson method.
+///
+/// # Arguments
+///
+/// 'f' - function which root we want to find.
+/// 'x0' - initial guess, midpoint of bracket is used if guess is outside of it.
+/// 'config' - tolerances, limits and bracket.
+pub fn newton<F>(f: F, x0: f64, config: &Config) -> RootResult<f64>
+where
+    F: Fn(Dual) -> Dual,
+{
+    solve(|x| newton_step(&f, x), x0, config, false)
+}
+
+/// Find root of scalar function by Newton–Raphson method with damping.
+/// Step is halved until |f| decreases, so iterations don't overshoot
+/// far from the root.
+///
+/// # Arguments
+///
+/// 'f' - function which root we want to find.
+/// 'x0' - initial guess, midpoint of bracket is used if guess is outside of it.
+/// 'config' - tolerances, limits and bracket.
+pub fn damped_newton<F>(f: F, x0: f64, config: &Config) -> RootResult<f64>
+where
+    F: Fn(Dual) -> Dual,
+{
+    solve(|x| newton_step(&f, x), x0, config, true)
+}
+
+/// Find root of scalar function by Halley's method, which converges
+/// cubically. Second derivative is taken from HyperDual number.
+///
+/// # Arguments
+///
+/// 'f' - function which root we want to find.
+/// 'x0' - initial guess, midpoint of bracket is used if guess is outside of it.
+/// 'config' - tolerances, limits and bracket.
+pub fn halley<F>(f: F, x0: f64, config: &Config) -> RootResult<f64>
+where
+    F: Fn(HyperDual) -> HyperDual,
+{
+    let step = |x: f64| {
+        let res = f(HyperDual::seeded(x, 1.0, 1.0));
+        let (val, d1, d2) = (res.val(), res.der1(), res.der12());
+        let denom = 2.0 * d1 * d1 - val * d2;
+        let step = if denom != 0.0 {
+            Some(-2.0 * val * d1 / denom)
+        } else {
+            None
+        };
+        (val, step)
+    };
+    solve(step, x0, config, false)
+}
+
+fn norm(v: &[f64]) -> f64 {
+    v.iter().map(|v| v * v).sum::<f64>().sqrt()
+}
+
+/// Solve 'a * x = b' by Gaussian elimination with
+/// partial pivoting, None if matrix is singular.
+fn solve_linear(a: &Jacobian, b: &[f64]) -> Option<Vec<f64>> {
+    let n = b.len();
+    let mut m: Vec<Vec<f64>> = (0..n)
+        .map(|i| {
+            let mut row = a.row(i).to_vec();
+            row.push(b[i]);
+            row
+        })
+        .collect();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| {
+            m[i][col].abs().partial_cmp(&m[j][col].abs()).unwrap_or(Ordering::Equal)
+        })?;
+        if m[pivot][col] == 0.0 || !m[pivot][col].is_finite() {
+            return None;
+        }
+        m.swap(col, pivot);
+        let (top, bottom) = m.split_at_mut(col + 1);
+        let pivot_row = &top[col];
+        for row in bottom.iter_mut() {
+            let k = row[col] / pivot_row[col];
+            for (r, p) in row[col..].iter_mut().zip(pivot_row[col..].iter()) {
+                *r -= k * p;
+            }
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum: f64 = (row + 1..n).map(|col| m[row][col] * x[col]).sum();
+        x[row] = (m[row][n] - sum) / m[row][row];
+    }
+    Some(x)
+}
+
+/// Find root of system of equations by Newton method. Jacobian
+/// is evaluated by seeding one input in turn, step is damped
+/// until norm of residual decreases. Bracket is not used.
+///
+/// # Arguments
+///
+/// 'f' - system, count of equations must be equal to count of unknowns.
+/// 'x0' - initial guess.
+/// 'config' - tolerances and limits.
+pub fn newton_system<F, Y>(f: F, x0: &[f64], config: &Config) -> RootResult<Vec<f64>>
+where
+    F: Fn(&[Dual]) -> Y,
+    Y: AsRef<[Dual]>,
+{
+    let evaluations = Cell::new(0);
+    let residual = |x: &[f64]| -> Vec<f64> {
+        evaluations.set(evaluations.get() + 1);
+        let x: Vec<Dual> = x.iter().map(|x| Dual::from(*x)).collect();
+        f(&x).as_ref().iter().map(Dual::val).collect()
+    };
+    let mut x = x0.to_vec();
+    let mut fx = residual(&x);
+    assert_eq!(fx.len(), x.len(), "count of equations and unknowns is different");
+    let mut iterations = 0;
+    loop {
+        let r = norm(&fx);
+        if !r.is_finite() {
+            return Err(RootError::NotFinite { x });
+        }
+        if r <= config.f_tol {
+            return Ok(Root {
+                x,
+                residual: r,
+                iterations,
+                evaluations: evaluations.get(),
+                convergence: Convergence::Residual,
+            });
+        }
+        if iterations == config.max_iter {
+            return Err(RootError::MaxIterations { x, residual: r });
+        }
+        iterations += 1;
+
+        let jac = jacobian(&f, &x);
+        evaluations.set(evaluations.get() + x.len());
+        let rhs: Vec<f64> = fx.iter().map(|v| -v).collect();
+        let step = match solve_linear(&jac, &rhs) {
+            Some(step) => step,
+            None => return Err(RootError::ZeroDerivative { x }),
+        };
+        let mut damping = 1.0;
+        let (next, f_next) = loop {
+            if damping < config.min_damping {
+                return Err(RootError::LineSearch { x, residual: r });
+            }
+            let next: Vec<f64> = x.iter().zip(step.iter()).map(|(x, s)| x + damping * s).collect();
+            let f_next = residual(&next);
+            if norm(&f_next) < r {
+                break (next, f_next);
+            }
+            damping *= 0.5;
+        };
+
+        let delta: Vec<f64> = next.iter().zip(x.iter()).map(|(n, x)| n - x).collect();
+        x = next;
+        fx = f_next;
+        let r = norm(&fx);
+        if r > config.f_tol && norm(&delta) <= config.x_tol * norm(&x).max(1.0) {
+            return Ok(Root {
+                x,
+                residual: r,
+                iterations,
+                evaluations: evaluations.get(),
+                convergence: Convergence::Step,
+            });
+        }
+    }
+}
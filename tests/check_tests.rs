@@ -0,0 +1,38 @@
+use rad::check::{self, Config};
+use rad::Dual;
+
+#[test]
+fn correct_gradient_test() {
+    let f = |x: &[Dual]| -> Dual { (x[0].sin() * x[1] + x[1].exp() / x[0]).into() };
+    let report = check::gradient(f, &[1.5, 0.5], &Config::default());
+    assert!(report.passed(), "{}", report);
+    assert_eq!(report.value, 1.5f64.sin() * 0.5 + 0.5f64.exp() / 1.5);
+    assert_eq!(report.inputs.len(), 2);
+    assert!(report.inputs.iter().all(|input| input.rel_err < 1e-8));
+}
+
+#[test]
+fn wrong_derivative_test() {
+    // abs has derivative discontinuity at zero, finite difference sees 0
+    let f = |x: Dual| -> Dual { x.abs().into() };
+    let report = check::derivative(f, 0.0, &Config::default());
+    assert!(!report.passed());
+    let failure = report.failures().next().unwrap();
+    assert_eq!((failure.index, failure.ad, failure.richardson), (0, 1.0, 0.0));
+    assert!(report.to_string().contains("FAILED"));
+}
+
+#[test]
+fn tolerance_test() {
+    let f = |x: Dual| -> Dual { (x * x * x).into() };
+    let strict = Config {
+        step: 0.1,
+        abs_tol: 0.0,
+        rel_tol: 0.0,
+    };
+    let report = check::derivative(f, 1.0, &strict);
+    assert!((report.inputs[0].central - 3.01).abs() < 1e-12);
+    assert!((report.inputs[0].richardson - 3.0).abs() < 1e-12);
+    let loose = Config { abs_tol: 1e-9, ..strict };
+    assert!(check::derivative(f, 1.0, &loose).passed());
+}
@@ -0,0 +1,144 @@
+use rad::codegen::{trace, Sym};
+use rad::{gradient, Dual, Real};
+use std::process::Command;
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() <= 1e-12 * a.abs().max(b.abs()).max(1.0)
+}
+
+fn f<T: Real>(x: &[Dual<T>]) -> Dual<T> {
+    let s = x[0] * x[1].sin();
+    (s + s.exp() / x[2].powi(2) - x[2].hypot(x[0]) + x[1].max(x[2]).sqrt() * 0.5).into()
+}
+
+// last point takes other branch of max
+const POINTS: [[f64; 3]; 4] = [[0.5, 1.5, 2.0], [-1.0, 0.3, 1.7], [2.0, -0.4, 3.5], [1.0, 2.5, 0.5]];
+
+fn expected(x: &[f64]) -> (f64, Vec<f64>) {
+    let x: Vec<Dual> = x.iter().map(|&x| Dual::from(x)).collect();
+    let g = |x: &Vec<Dual>| f(x);
+    (g(&x).val(), gradient!(g(&x), x))
+}
+
+#[test]
+fn eval_test() {
+    let program = trace(f, &POINTS[0]);
+    assert_eq!(program.inputs(), 3);
+    for x in POINTS.iter() {
+        let (val, grad) = program.eval(x);
+        let (e_val, e_grad) = expected(x);
+        assert!(close(val, e_val));
+        assert!(grad.iter().zip(e_grad.iter()).all(|(&a, &b)| close(a, b)), "{:?} {:?}", grad, e_grad);
+    }
+}
+
+#[test]
+fn cse_test() {
+    // sin(x) and cos(x) are computed once for value and both partials.
+    let program = trace(|x: &[Dual<Sym>]| x[0].sin() * x[1] + x[0].sin() * x[0].sin(), &[1.0, 2.0]);
+    assert_eq!(program.operations(), 9);
+    assert_eq!(program.to_rust("g").matches(".sin()").count(), 1);
+    assert_eq!(program.to_rust("g").matches(".cos()").count(), 1);
+
+    let linear = trace(|x: &[Dual<Sym>]| x[0] * 3.0 + x[1], &[1.0, 2.0]);
+    assert_eq!(linear.operations(), 2);
+    assert_eq!(linear.eval(&[1.0, 2.0]), (5.0, vec![3.0, 1.0]));
+}
+
+#[test]
+fn branch_test() {
+    let program = trace(|x: &[Dual<Sym>]| x[0].max(x[1]) * 2.0 + x[0].min(x[1]), &[1.0, 2.0]);
+    assert_eq!(program.eval(&[1.0, 2.0]), (5.0, vec![1.0, 2.0]));
+    assert_eq!(program.eval(&[3.0, 2.0]), (8.0, vec![2.0, 1.0]));
+    assert_eq!(program.eval(&[2.0, 2.0]), (6.0, vec![1.5, 1.5]));
+    assert!(program.to_c("g").contains(" == "));
+}
+
+#[test]
+fn nested_trace_test() {
+    let program = trace(
+        |x: &[Dual<Sym>]| {
+            let inner = trace(|y: &[Dual<Sym>]| y[0] * y[0], &[x[0].val().val()]);
+            x[0] * inner.eval(&[3.0]).0
+        },
+        &[2.0],
+    );
+    assert_eq!(program.eval(&[1.0]), (9.0, vec![9.0]));
+}
+
+#[test]
+#[should_panic(expected = "Sym of another trace is used")]
+fn foreign_sym_test() {
+    let outer = std::cell::Cell::new(None);
+    trace(
+        |x: &[Dual<Sym>]| {
+            outer.set(Some(x[0]));
+            x[0]
+        },
+        &[1.0],
+    );
+    trace(|x: &[Dual<Sym>]| x[0] * outer.get().unwrap(), &[1.0]);
+}
+
+#[test]
+#[should_panic(expected = "Sym is used outside of trace")]
+fn outside_trace_test() {
+    let _ = Sym::from_f64(1.0);
+}
+
+#[test]
+fn c_test() {
+    let program = trace(|x: &[Dual<Sym>]| x[0].ln() * x[1], &[2.0, 3.0]);
+    assert_eq!(
+        program.to_c("g"),
+        "#include <math.h>\n\n\
+         double g(const double *x, double *grad) {\n    \
+         const double t0 = 1.0 / x[0];\n    \
+         const double t1 = log(x[0]);\n    \
+         const double t2 = t1 * x[1];\n    \
+         const double t3 = t0 * x[1];\n    \
+         grad[0] = t3;\n    \
+         grad[1] = t1;\n    \
+         return t2;\n}\n"
+    );
+}
+
+#[test]
+fn compile_rust_test() {
+    let program = trace(f, &POINTS[0]);
+    let mut src = program.to_rust("f");
+    src += "\nfn main() {\n";
+    for x in POINTS.iter() {
+        src += &format!(
+            "    let (v, g) = f(&[{:?}, {:?}, {:?}]);\n    println!(\"{{:?}} {{:?}} {{:?}} {{:?}}\", v, g[0], g[1], g[2]);\n",
+            x[0], x[1], x[2]
+        );
+    }
+    src += "}\n";
+
+    let dir = std::env::temp_dir().join(format!("rad_codegen_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("main.rs");
+    let bin = dir.join("main");
+    std::fs::write(&file, src).unwrap();
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let status = Command::new(rustc)
+        .arg("-O")
+        .arg("-o")
+        .arg(&bin)
+        .arg(&file)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let output = Command::new(&bin).output().unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    for (line, x) in stdout.lines().zip(POINTS.iter()) {
+        let res: Vec<f64> = line.split(' ').map(|v| v.parse().unwrap()).collect();
+        let (val, grad) = expected(x);
+        assert!(close(res[0], val));
+        assert!(res[1..].iter().zip(grad.iter()).all(|(&a, &b)| close(a, b)));
+    }
+    assert_eq!(stdout.lines().count(), POINTS.len());
+}
@@ -0,0 +1,98 @@
+#[cfg(test)]
+extern crate quickcheck;
+#[cfg(test)]
+#[macro_use(quickcheck)]
+extern crate quickcheck_macros;
+
+use rad::{complex_step_derivative, complex_step_gradient, derivative, gradient, Complex, Dual};
+
+const EPSILON: f64 = 1e-13;
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() <= EPSILON * a.abs().max(b.abs()).max(1.0)
+}
+
+/// Map arbitrary number into positive range to keep functions well defined.
+fn positive(x: f64) -> f64 {
+    if x.is_finite() {
+        x.sin() + 1.5
+    } else {
+        1.0
+    }
+}
+
+#[quickcheck]
+fn elementary_functions_property(x: f64) -> bool {
+    let x = positive(x);
+    let f = |x: Dual| -> Dual { (x.sin() * x.cos() + x.sqrt() / x.ln().exp()).into() };
+    let g = |x: Complex| x.sin() * x.cos() + x.sqrt() / x.ln().exp();
+    let xd = Dual::from(x);
+    let (val, der) = complex_step_derivative(g, x);
+    close(val, f(xd).val()) && close(der, derivative!(f(xd), xd))
+}
+
+#[quickcheck]
+fn power_functions_property(x: f64, p: f64) -> bool {
+    let (x, p) = (positive(x), positive(p) * 2.0 - 1.0);
+    let f = |x: Dual| -> Dual { (x.powf(p) + x.powf(x) + 3.0 / x - x.powi(3)).into() };
+    let g = |x: Complex| x.powf(p) + x.powf(x) + 3.0 / x - x.powi(3);
+    let xd = Dual::from(x);
+    close(complex_step_derivative(g, x).1, derivative!(f(xd), xd))
+}
+
+#[test]
+fn gradient_test() {
+    let f = |x: &Vec<Dual>| -> Dual { (x[0] * x[1].sin() - x[2] / x[0]).into() };
+    let g = |x: &[Complex]| x[0] * x[1].sin() - x[2] / x[0];
+    let x = vec![Dual::from(1.5), Dual::from(0.3), Dual::from(-2.0)];
+    let (val, grad) = complex_step_gradient(g, &[1.5, 0.3, -2.0]);
+    assert!(close(val, f(&x).val()));
+    let expected = gradient!(f(&x), x);
+    assert!(grad.iter().zip(&expected).all(|(a, b)| close(*a, *b)), "{:?} != {:?}", grad, expected);
+}
+
+#[test]
+fn square_root_test() {
+    // no cancellation for negative real part
+    let z = Complex::new(-4.0, 1e-20).sqrt();
+    assert_eq!(z, Complex::new(2.5e-21, 2.0));
+    assert_eq!(Complex::new(0.0, 0.0).sqrt(), Complex::new(0.0, 0.0));
+}
+
+#[test]
+fn negative_base_test() {
+    assert_eq!(complex_step_derivative(|x| x.powf(2.0), -3.0), (9.0, -6.0));
+    assert_eq!(complex_step_derivative(|x| x.powf(3.0), -2.0), (-8.0, 12.0));
+    assert_eq!(complex_step_derivative(|x| x.powf(-1.0), -2.0), (-0.5, -0.25));
+    assert_eq!(complex_step_derivative(|x| x.powf(0.0), -2.0), (1.0, 0.0));
+    let (val, der) = complex_step_derivative(|x| x.powf(0.5), -2.0);
+    assert!(val.is_nan() && der.is_nan());
+    // derivative of 0^1.5 is 0
+    assert_eq!(complex_step_derivative(|x| x.powf(1.5), 0.0), (0.0, 0.0));
+}
+
+#[test]
+fn complex_base_power_test() {
+    let close_to = |z: Complex, re: f64, im: f64| close(z.re, re) && close(z.im, im);
+    let half = std::f64::consts::FRAC_1_SQRT_2;
+    assert!(close_to(Complex::new(0.0, 1.0).powf(0.5), half, half));
+    // principal root of -1 + i is 2^(1/4) * e^(3i*pi/8)
+    let (norm, arg) = (2f64.powf(0.25), 3.0 * std::f64::consts::PI / 8.0);
+    let z = Complex::new(-1.0, 1.0).powf(0.5);
+    assert!(close_to(z, norm * arg.cos(), norm * arg.sin()));
+    assert!(close_to(z * z, -1.0, 1.0));
+    // principal value of (-2 - 3i)^1.5 from polar form
+    let (norm, arg) = (13f64.powf(0.75), 1.5 * (-3f64).atan2(-2.0));
+    let z = Complex::new(-2.0, -3.0).powf(1.5);
+    assert!(close_to(z, norm * arg.cos(), norm * arg.sin()));
+}
+
+#[quickcheck]
+fn integer_power_property(x: f64, n: i8) -> bool {
+    let x = -2.0 * positive(x);
+    let n = i32::from(n % 8);
+    let f = |x: Dual| -> Dual { x.powi(n).into() };
+    let xd = Dual::from(x);
+    let (val, der) = complex_step_derivative(|x| x.powf(n as f64), x);
+    close(val, f(xd).val()) && close(der, derivative!(f(xd), xd))
+}
@@ -0,0 +1,61 @@
+use rad::{derivative, differentiable, Dual};
+use std::f64::consts::PI;
+
+#[differentiable(grad)]
+fn rosenbrock(x: f64, y: f64) -> f64 {
+    let a = 1.0 - x;
+    let b = y - x * x;
+    a * a + 100.0 * b * b
+}
+
+#[differentiable]
+fn poly(x: f64) -> f64 {
+    let mut acc: f64 = 0.0;
+    for i in 0..4 {
+        acc += (i as f64 + 1.0) * x.powi(i);
+    }
+    acc
+}
+
+#[differentiable(grad)]
+fn branchy(x: f64, y: f64) -> f64 {
+    if x > 0.0 {
+        return x.sin() * y.exp() + poly(x) + x.powf(2.0);
+    }
+    -x.powi(2).max(y) / PI + f64::EPSILON
+}
+
+#[differentiable(grad)]
+fn scale(mut x: f64, y: f64) -> f64 {
+    x *= 2.0;
+    x + y
+}
+
+#[differentiable(grad)]
+fn nothing() -> f64 {
+    2.5
+}
+
+#[test]
+fn dual_version_test() {
+    assert_eq!(poly_dual(Dual::from(2.0)).val(), poly(2.0));
+    let x = Dual::from(2.0);
+    assert_eq!(derivative!(poly_dual(x), x), 2.0 + 6.0 * 2.0 + 12.0 * 4.0);
+    let y = Dual::from(3.0);
+    assert_eq!(rosenbrock_dual(x, y).val(), rosenbrock(2.0, 3.0));
+}
+
+#[test]
+fn grad_test() {
+    let (val, grad) = rosenbrock_grad(2.0, 3.0);
+    assert_eq!(val, rosenbrock(2.0, 3.0));
+    assert_eq!(grad, vec![-2.0 * (1.0 - 2.0) - 400.0 * 2.0 * (3.0 - 4.0), 200.0 * (3.0 - 4.0)]);
+    let (val, grad) = branchy_grad(0.5, 1.0);
+    assert_eq!(val, branchy(0.5, 1.0));
+    assert_eq!(grad, vec![0.5f64.cos() * 1.0f64.exp() + 2.0 + 6.0 * 0.5 + 12.0 * 0.25 + 1.0, 0.5f64.sin() * 1.0f64.exp()]);
+    let (val, grad) = branchy_grad(-0.5, 1.0);
+    assert_eq!(val, branchy(-0.5, 1.0));
+    assert_eq!(grad, vec![0.0, -1.0 / PI]);
+    assert_eq!(nothing_grad(), (2.5, vec![]));
+    assert_eq!(scale_grad(1.5, 2.0), (5.0, vec![2.0, 1.0]));
+}
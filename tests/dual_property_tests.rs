@@ -1,3 +1,7 @@
+// Properties take reference operands and convert duals into themselves
+// on purpose, to cover every operator overload and conversion impl.
+#![allow(clippy::op_ref, clippy::useless_conversion)]
+
 #[cfg(test)]
 extern crate quickcheck;
 #[cfg(test)]
@@ -541,6 +545,261 @@ mod test_math_functions {
     }
 }
 
+#[cfg(test)]
+mod test_elementary_functions {
+    use super::*;
+
+    /// Derivative of function at point 'x'.
+    fn der<F: Fn(Dual) -> Dual>(f: F, x: f64) -> f64 {
+        let x = Dual::from(x);
+        derivative!(f(x), x)
+    }
+
+    #[quickcheck]
+    fn tan_test(x: f64) -> bool {
+        let x = x.sin();
+        let expected = 1.0 / (x.cos() * x.cos());
+        der(|x| x.tan().into(), x).approx_eq(expected, (EPSILON, ULP))
+            && der(|x| (x * 1.0).tan().into(), x).approx_eq(expected, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn asin_test(x: f64) -> bool {
+        let x = 0.9 * x.sin();
+        let expected = 1.0 / (1.0 - x * x).sqrt();
+        der(|x| x.asin().into(), x).approx_eq(expected, (EPSILON, ULP))
+            && der(|x| (x * 1.0).asin().into(), x).approx_eq(expected, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn acos_test(x: f64) -> bool {
+        let x = 0.9 * x.sin();
+        let expected = -1.0 / (1.0 - x * x).sqrt();
+        der(|x| x.acos().into(), x).approx_eq(expected, (EPSILON, ULP))
+            && der(|x| (x * 1.0).acos().into(), x).approx_eq(expected, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn atan_test(x: f64) -> bool {
+        let expected = 1.0 / (1.0 + x * x);
+        der(|x| x.atan().into(), x).approx_eq(expected, (EPSILON, ULP))
+            && der(|x| (x * 1.0).atan().into(), x).approx_eq(expected, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn sinh_test(x: f64) -> bool {
+        let x = x.sin();
+        let expected = x.cosh();
+        der(|x| x.sinh().into(), x).approx_eq(expected, (EPSILON, ULP))
+            && der(|x| (x * 1.0).sinh().into(), x).approx_eq(expected, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn cosh_test(x: f64) -> bool {
+        let x = x.sin();
+        let expected = x.sinh();
+        der(|x| x.cosh().into(), x).approx_eq(expected, (EPSILON, ULP))
+            && der(|x| (x * 1.0).cosh().into(), x).approx_eq(expected, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn tanh_test(x: f64) -> bool {
+        let expected = 1.0 - x.tanh() * x.tanh();
+        der(|x| x.tanh().into(), x).approx_eq(expected, (EPSILON, ULP))
+            && der(|x| (x * 1.0).tanh().into(), x).approx_eq(expected, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn asinh_test(x: f64) -> bool {
+        let expected = 1.0 / (x * x + 1.0).sqrt();
+        der(|x| x.asinh().into(), x).approx_eq(expected, (EPSILON, ULP))
+            && der(|x| (x * 1.0).asinh().into(), x).approx_eq(expected, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn acosh_test(x: f64) -> bool {
+        let x = 2.0 + x.sin();
+        let expected = 1.0 / (x * x - 1.0).sqrt();
+        der(|x| x.acosh().into(), x).approx_eq(expected, (EPSILON, ULP))
+            && der(|x| (x * 1.0).acosh().into(), x).approx_eq(expected, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn atanh_test(x: f64) -> bool {
+        let x = 0.9 * x.sin();
+        let expected = 1.0 / (1.0 - x * x);
+        der(|x| x.atanh().into(), x).approx_eq(expected, (EPSILON, ULP))
+            && der(|x| (x * 1.0).atanh().into(), x).approx_eq(expected, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn exp2_test(x: f64) -> bool {
+        let x = x.sin();
+        let expected = x.exp2() * std::f64::consts::LN_2;
+        der(|x| x.exp2().into(), x).approx_eq(expected, (EPSILON, ULP))
+            && der(|x| (x * 1.0).exp2().into(), x).approx_eq(expected, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn exp_m1_test(x: f64) -> bool {
+        let x = x.sin();
+        let expected = x.exp();
+        der(|x| x.exp_m1().into(), x).approx_eq(expected, (EPSILON, ULP))
+            && der(|x| (x * 1.0).exp_m1().into(), x).approx_eq(expected, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn ln_1p_test(x: f64) -> bool {
+        let x = x.sin() + 1.5;
+        let expected = 1.0 / (1.0 + x);
+        der(|x| x.ln_1p().into(), x).approx_eq(expected, (EPSILON, ULP))
+            && der(|x| (x * 1.0).ln_1p().into(), x).approx_eq(expected, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn log2_test(x: f64) -> bool {
+        let x = x * x + 1.0;
+        let expected = 1.0 / (x * std::f64::consts::LN_2);
+        der(|x| x.log2().into(), x).approx_eq(expected, (EPSILON, ULP))
+            && der(|x| (x * 1.0).log2().into(), x).approx_eq(expected, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn log10_test(x: f64) -> bool {
+        let x = x * x + 1.0;
+        let expected = 1.0 / (x * std::f64::consts::LN_10);
+        der(|x| x.log10().into(), x).approx_eq(expected, (EPSILON, ULP))
+            && der(|x| (x * 1.0).log10().into(), x).approx_eq(expected, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn cbrt_test(x: f64) -> bool {
+        let x = x * x + 1.0;
+        let expected = 1.0 / (3.0 * x.cbrt() * x.cbrt());
+        der(|x| x.cbrt().into(), x).approx_eq(expected, (EPSILON, ULP))
+            && der(|x| (x * 1.0).cbrt().into(), x).approx_eq(expected, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn abs_test(x: f64) -> bool {
+        let expected = x.signum();
+        der(|x| x.abs().into(), x).approx_eq(expected, (EPSILON, ULP))
+            && der(|x| (x * 1.0).abs().into(), x).approx_eq(expected, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn signum_test(x: f64) -> bool {
+        let expected = 0.0;
+        der(|x| x.signum().into(), x).approx_eq(expected, (EPSILON, ULP))
+            && der(|x| (x * 1.0).signum().into(), x).approx_eq(expected, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn recip_test(x: f64) -> bool {
+        let x = x * x + 1.0;
+        let expected = -1.0 / (x * x);
+        der(|x| x.recip().into(), x).approx_eq(expected, (EPSILON, ULP))
+            && der(|x| (x * 1.0).recip().into(), x).approx_eq(expected, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn powi_test(x: f64, n: i8) -> bool {
+        let (x, n) = (x.sin() + 2.0, (n % 8) as i32);
+        let expected = n as f64 * x.powi(n - 1);
+        der(|x| x.powi(n).into(), x).approx_eq(expected, (EPSILON, ULP))
+            && der(|x| (x * 1.0).powi(n).into(), x).approx_eq(expected, (EPSILON, ULP))
+    }
+
+    #[test]
+    fn powi_at_zero_test() {
+        assert_eq!(der(|x| x.powi(0).into(), 0.0), 0.0);
+        assert_eq!(der(|x| x.powi(1).into(), 0.0), 1.0);
+        assert_eq!(der(|x| x.powi(2).into(), 0.0), 0.0);
+        assert_eq!(der(|x| (x * 1.0).powi(0).into(), 0.0), 0.0);
+    }
+
+    #[quickcheck]
+    fn composition_test(x: f64) -> bool {
+        let x = x.sin();
+        let expected = x.cosh() / (1.0 + x.sinh() * x.sinh());
+        der(|x| x.sinh().atan().into(), x).approx_eq(expected, (EPSILON, ULP))
+    }
+}
+
+#[cfg(test)]
+mod test_binary_functions {
+    use super::*;
+
+    #[quickcheck]
+    fn atan2_test(y: Dual, x: Dual) -> bool {
+        let atan2 = |y: Dual, x: Dual| -> Dual { y.atan2(x).into() };
+        let sq = x.val() * x.val() + y.val() * y.val();
+        derivative!(atan2(y, x), y).approx_eq(x.val() / sq, (EPSILON, ULP))
+            && derivative!(atan2(y, x), x).approx_eq(-y.val() / sq, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn atan2_mixed_test(y: Dual, x: f64) -> bool {
+        let atan2_f64 = |y: Dual| -> Dual { y.atan2(x).into() };
+        let atan2_xpr = |y: Dual| -> Dual { (y * 1.0).atan2(x * 1.0).into() };
+        let expected = x / (x * x + y.val() * y.val());
+        derivative!(atan2_f64(y), y).approx_eq(expected, (EPSILON, ULP))
+            && derivative!(atan2_xpr(y), y).approx_eq(expected, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn hypot_test(x: Dual, y: Dual) -> bool {
+        let hypot = |x: Dual, y: Dual| -> Dual { (x + 0.0).hypot(y).into() };
+        let h = x.val().hypot(y.val());
+        derivative!(hypot(x, y), x).approx_eq(x.val() / h, (EPSILON, ULP))
+            && derivative!(hypot(x, y), y).approx_eq(y.val() / h, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn min_max_test(x: Dual, y: Dual) -> bool {
+        let min = |x: Dual, y: Dual| -> Dual { x.min(y * 2.0).into() };
+        let max = |x: Dual, y: Dual| -> Dual { x.max(y * 2.0).into() };
+        let x_is_min = x.val() < 2.0 * y.val();
+        derivative!(min(x, y), x) == if x_is_min { 1.0 } else { 0.0 }
+            && derivative!(min(x, y), y) == if x_is_min { 0.0 } else { 2.0 }
+            && derivative!(max(x, y), x) == if x_is_min { 0.0 } else { 1.0 }
+            && derivative!(max(x, y), y) == if x_is_min { 2.0 } else { 0.0 }
+    }
+
+    #[quickcheck]
+    fn min_max_tie_test(x: f64) -> bool {
+        let x = Dual::from(x);
+        let min = |x: Dual| -> Dual { x.min(x).into() };
+        let max = |x: Dual| -> Dual { (x * 2.0).max(x + x).into() };
+        derivative!(min(x), x) == 1.0 && derivative!(max(x), x) == 2.0
+    }
+
+    #[quickcheck]
+    fn log_test(x: f64, base: f64) -> bool {
+        let (x, base) = (Dual::from(x * x + 1.0), Dual::from(base.sin() + 3.0));
+        let log = |x: Dual, base: Dual| -> Dual { x.log(base).into() };
+        let (ln_x, ln_b) = (x.val().ln(), base.val().ln());
+        derivative!(log(x, base), x).approx_eq(1.0 / (x.val() * ln_b), (EPSILON, ULP))
+            && derivative!(log(x, base), base)
+                .approx_eq(-ln_x / (base.val() * ln_b * ln_b), (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn log_f64_base_test(x: f64) -> bool {
+        let x = Dual::from(x * x + 1.0);
+        let log = |x: Dual| -> Dual { x.log(10.0).into() };
+        derivative!(log(x), x).approx_eq(1.0 / (x.val() * 10.0f64.ln()), (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn copysign_test(x: Dual, y: Dual) -> bool {
+        let copysign = |x: Dual, y: Dual| -> Dual { x.copysign(y).into() };
+        let same_sign = x.val().is_sign_negative() == y.val().is_sign_negative();
+        derivative!(copysign(x, y), x) == if same_sign { 1.0 } else { -1.0 }
+            && derivative!(copysign(x, y), y) == 0.0
+    }
+}
+
 #[cfg(test)]
 mod tests_derivative {
     use super::*;
@@ -563,6 +822,28 @@ mod tests_derivative {
         derivative!(mul_by_c(x), x).approx_eq(c, F64Margin::default())
     }
 
+    #[quickcheck]
+    fn mul_by_constant_on_right_property(x: Dual, y: Dual, c: f64) -> bool {
+        let mul_by_c = |x: Dual| -> Dual { (x * c).into() };
+        let mul_assign = |x: Dual, y: Dual| -> Dual {
+            let mut z = y;
+            z *= x * c;
+            z
+        };
+        derivative!(mul_by_c(x), x).approx_eq(c, F64Margin::default())
+            && derivative!(mul_assign(x, y), x).approx_eq(c * y.val(), F64Margin::default())
+    }
+
+    #[quickcheck]
+    fn div_by_constant_property(x: Dual, c: f64) -> bool {
+        let c = c * c + 1.0;
+        let div_by_c = |x: Dual| -> Dual { (x / c).into() };
+        let c_div = |x: Dual| -> Dual { (c / (x * x + 1.0)).into() };
+        let aux = x.val() * x.val() + 1.0;
+        derivative!(div_by_c(x), x).approx_eq(1.0 / c, F64Margin::default())
+            && derivative!(c_div(x), x).approx_eq(-2.0 * c * x.val() / aux / aux, (EPSILON, ULP))
+    }
+
     #[quickcheck]
     fn sum_property(x: Dual) -> bool {
         let f1 = |x: Dual| -> Dual { (x + x).into() };
@@ -615,9 +896,195 @@ mod compare_tests {
     #[quickcheck]
     fn dauls_are_eq_even_when_ders_are_not_same(val: f64) -> bool {
         let x: Dual = val.into();
-        let y = x;
-        // we can do it here, but it is workaround
-        y.seed();
+        let y = x.seeded();
         x == y
     }
 }
+
+#[cfg(test)]
+mod tests_dual_n {
+    use super::*;
+    use rad::{gradient, DualN};
+
+    #[quickcheck]
+    fn keeps_value(val: f64) -> bool {
+        DualN::<3>::from(val).val().approx_eq(val, F64Margin::default())
+    }
+
+    #[quickcheck]
+    fn mul_by_constant_property(x: f64, c: f64) -> bool {
+        let [x] = DualN::<1>::variables([x]);
+        DualN::from(x * c).der(0).approx_eq(c, F64Margin::default())
+            && DualN::from(c * x).der(0).approx_eq(c, F64Margin::default())
+    }
+
+    #[quickcheck]
+    fn gradient_is_same_as_dual(x: f64, y: f64, z: f64) -> bool {
+        let f = |x: &Vec<Dual>| -> Dual {
+            (x[0] * x[1] - x[2] / (x[0] * x[0] + 1.0) + (x[1] * x[1] + 1.0).ln()).into()
+        };
+        let g = |x: &[DualN<3>; 3]| -> DualN<3> {
+            (x[0] * x[1] - x[2] / (x[0] * x[0] + 1.0) + (x[1] * x[1] + 1.0).ln()).into()
+        };
+        let xs = vec![Dual::from(x), Dual::from(y), Dual::from(z)];
+        let expected = gradient!(f(&xs), xs);
+        let actual = g(&DualN::variables([x, y, z])).grad();
+        expected
+            .iter()
+            .zip(actual.iter())
+            .all(|(e, a)| e.approx_eq(*a, (EPSILON, ULP)))
+    }
+
+    #[quickcheck]
+    fn math_functions_are_same_as_dual(x: f64, y: f64) -> bool {
+        let f = |x: Dual, y: Dual| -> Dual {
+            (x.sin() * y.cos() + (x * x + 1.0).sqrt() - (-y).exp() + x.powf(2.0)).into()
+        };
+        let g = |x: DualN<2>, y: DualN<2>| -> DualN<2> {
+            (x.sin() * y.cos() + (x * x + 1.0).sqrt() - (-y).exp() + x.powf(2.0)).into()
+        };
+        let (xd, yd) = (Dual::from(x), Dual::from(y));
+        let [xn, yn] = DualN::variables([x, y]);
+        let grad = g(xn, yn).grad();
+        derivative!(f(xd, yd), xd).approx_eq(grad[0], (EPSILON, ULP))
+            && derivative!(f(xd, yd), yd).approx_eq(grad[1], (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn assign_ops_property(x: DualN<2>, y: DualN<2>) -> bool {
+        let mut z = x;
+        z += y;
+        z *= x + y;
+        z -= y;
+        z.approx_eq(DualN::from((x + y) * (x + y) - y), (EPSILON, ULP))
+    }
+}
+
+#[cfg(test)]
+mod tests_hyper_dual {
+    use super::*;
+    use rad::{hessian, HyperDual};
+
+    /// Second derivative of univariate function.
+    fn second<F: Fn(HyperDual) -> HyperDual>(f: F, x: f64) -> f64 {
+        f(HyperDual::seeded(x, 1.0, 1.0)).der12()
+    }
+
+    #[quickcheck]
+    fn first_derivative_is_same_as_dual(x: f64, y: f64) -> bool {
+        let f = |x: Dual, y: Dual| -> Dual { (x.sin() * y + (x * x + 1.0).ln() / y.exp()).into() };
+        let g = |x: HyperDual, y: HyperDual| -> HyperDual { (x.sin() * y + (x * x + 1.0).ln() / y.exp()).into() };
+        let (xd, yd) = (Dual::from(x), Dual::from(y));
+        let res = g(HyperDual::seeded(x, 1.0, 0.0), HyperDual::seeded(y, 0.0, 1.0));
+        derivative!(f(xd, yd), xd).approx_eq(res.der1(), (EPSILON, ULP))
+            && derivative!(f(xd, yd), yd).approx_eq(res.der2(), (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn sin_cos_test(x: f64) -> bool {
+        second(|x| x.sin().into(), x).approx_eq(-x.sin(), F64Margin::default())
+            && second(|x| x.cos().into(), x).approx_eq(-x.cos(), F64Margin::default())
+    }
+
+    #[quickcheck]
+    fn exp_test(x: f64) -> bool {
+        second(|x| x.exp().into(), x).approx_eq(x.exp(), F64Margin::default())
+    }
+
+    #[test]
+    fn powi_at_zero_test() {
+        for n in 0..3 {
+            let res = HyperDual::from(HyperDual::seeded(0.0, 1.0, 1.0).powi(n));
+            assert_eq!((res.der1(), res.der12()), ([0.0, 1.0, 0.0][n as usize], [0.0, 0.0, 2.0][n as usize]));
+        }
+        let f = |x: &Vec<HyperDual>| -> HyperDual { (x[0].powi(1) + x[1].powi(0) * x[0]).into() };
+        let xs = [Dual::from(0.0), Dual::from(0.0)];
+        assert_eq!(hessian!(f(&xs), xs), vec![vec![0.0, 0.0], vec![0.0, 0.0]]);
+        // exponent product is computed in f64, so it doesn't overflow
+        let res = HyperDual::from(HyperDual::seeded(1.0, 1.0, 1.0).powi(i32::MAX));
+        assert_eq!(res.der12(), i32::MAX as f64 * (i32::MAX as f64 - 1.0));
+    }
+
+    #[quickcheck]
+    fn ln_sqrt_test(x: f64) -> bool {
+        let x = x * x + 1.0;
+        second(|x| x.ln().into(), x).approx_eq(-1.0 / (x * x), (EPSILON, ULP))
+            && second(|x| x.sqrt().into(), x).approx_eq(-0.25 / (x * x.sqrt()), (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn elementary_functions_test(x: f64) -> bool {
+        let x = 0.5 * x.sin();
+        let (tan, tanh) = (x.tan(), x.tanh());
+        second(|x| x.tan().into(), x).approx_eq(2.0 * tan * (1.0 + tan * tan), (EPSILON, ULP))
+            && second(|x| x.asin().into(), x).approx_eq(x / (1.0 - x * x).powf(1.5), (EPSILON, ULP))
+            && second(|x| x.atan().into(), x).approx_eq(-2.0 * x / (1.0 + x * x).powi(2), (EPSILON, ULP))
+            && second(|x| x.tanh().into(), x).approx_eq(-2.0 * tanh * (1.0 - tanh * tanh), (EPSILON, ULP))
+            && second(|x| x.asinh().into(), x).approx_eq(-x / (x * x + 1.0).powf(1.5), (EPSILON, ULP))
+            && second(|x| x.atanh().into(), x).approx_eq(2.0 * x / (1.0 - x * x).powi(2), (EPSILON, ULP))
+            && second(|x| x.exp2().into(), x).approx_eq(x.exp2() * 2.0f64.ln().powi(2), (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn power_functions_test(x: f64) -> bool {
+        let x = x * x + 1.0;
+        second(|x| x.recip().into(), x).approx_eq(2.0 / (x * x * x), (EPSILON, ULP))
+            && second(|x| x.cbrt().into(), x).approx_eq(-2.0 / 9.0 * x.powf(-5.0 / 3.0), (EPSILON, ULP))
+            && second(|x| x.powi(3).into(), x).approx_eq(6.0 * x, (EPSILON, ULP))
+            && second(|x| x.log10().into(), x).approx_eq(-1.0 / (x * x * 10.0f64.ln()), (EPSILON, ULP))
+            && second(|x| x.acosh().into(), x + 1.0).approx_eq(-(x + 1.0) / ((x + 1.0).powi(2) - 1.0).powf(1.5), (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn binary_functions_test(x: f64, y: f64) -> bool {
+        let (x, y) = (x.sin() + 2.0, y.cos() + 3.0);
+        let atan2 = |x: HyperDual, y: HyperDual| -> HyperDual { x.atan2(y).into() };
+        let hypot = |x: HyperDual, y: HyperDual| -> HyperDual { x.hypot(y).into() };
+        let log = |x: HyperDual, y: HyperDual| -> HyperDual { x.log(y).into() };
+        let sq = x * x + y * y;
+        let h = sq.sqrt();
+        let (ln_x, ln_y) = (x.ln(), y.ln());
+        let mixed = |f: &dyn Fn(HyperDual, HyperDual) -> HyperDual| {
+            f(HyperDual::seeded(x, 1.0, 0.0), HyperDual::seeded(y, 0.0, 1.0)).der12()
+        };
+        let twice = |f: &dyn Fn(HyperDual, HyperDual) -> HyperDual| {
+            f(HyperDual::seeded(x, 0.0, 0.0), HyperDual::seeded(y, 1.0, 1.0)).der12()
+        };
+        mixed(&atan2).approx_eq((x * x - y * y) / (sq * sq), (EPSILON, ULP))
+            && twice(&atan2).approx_eq(2.0 * x * y / (sq * sq), (EPSILON, ULP))
+            && mixed(&hypot).approx_eq(-x * y / (h * h * h), (EPSILON, ULP))
+            && twice(&hypot).approx_eq(x * x / (h * h * h), (EPSILON, ULP))
+            && mixed(&log).approx_eq(-1.0 / (x * y * ln_y * ln_y), (EPSILON, ULP))
+            && twice(&log).approx_eq(ln_x * (ln_y + 2.0) / (y * y * ln_y.powi(3)), (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn mul_div_test(x: f64) -> bool {
+        let x = x * x + 1.0;
+        second(|x| (x * x * x).into(), x).approx_eq(6.0 * x, (EPSILON, ULP))
+            && second(|x| (1.0 / x).into(), x).approx_eq(2.0 / (x * x * x), (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn powf_test(x: f64, deg: f64) -> bool {
+        let x = x.abs() + 1.0;
+        let deg = deg % 10.0;
+        let expected = deg * (deg - 1.0) * x.powf(deg - 2.0);
+        second(|x| x.powf(deg).into(), x).approx_eq(expected, (EPSILON, ULP))
+            && second(|x| x.powf(HyperDual::from(deg)).into(), x).approx_eq(expected, (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn hessian_mixed_test(x: f64, y: f64, z: f64) -> bool {
+        let f = |x: &Vec<HyperDual>| -> HyperDual {
+            (x[0] * x[1].sin() + x[2].exp() * x[0] - x[1] * x[2] / (x[0] * x[0] + 1.0)).into()
+        };
+        let aux = x * x + 1.0;
+        let xs = [Dual::from(x), Dual::from(y), Dual::from(z)];
+        let h = hessian!(f(&xs), xs);
+        h[0][1].approx_eq(y.cos() + 2.0 * x * z / aux / aux, (EPSILON, ULP))
+            && h[0][2].approx_eq(z.exp() + 2.0 * x * y / aux / aux, (EPSILON, ULP))
+            && h[1][2].approx_eq(-1.0 / aux, (EPSILON, ULP))
+            && h[1][1].approx_eq(-x * y.sin(), (EPSILON, ULP))
+    }
+}
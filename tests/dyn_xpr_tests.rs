@@ -0,0 +1,95 @@
+#[cfg(test)]
+extern crate quickcheck;
+#[cfg(test)]
+#[macro_use(quickcheck)]
+extern crate quickcheck_macros;
+
+use rad::{gradient, Dual, DualN, DynXpr};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() <= 1e-12 * a.abs().max(b.abs()).max(1.0)
+}
+
+/// Sum of squares built as runtime expression.
+fn sum_of_squares(x: &[Dual]) -> Dual {
+    let mut sum = DynXpr::new(0.0);
+    for xi in x {
+        sum += *xi * *xi;
+    }
+    sum.into()
+}
+
+#[quickcheck]
+fn sum_in_loop_property(xs: Vec<f64>) -> bool {
+    let x: Vec<Dual> = xs.iter().map(|x| Dual::from(x.sin())).collect();
+    let grad = gradient!(sum_of_squares(&x), x);
+    let val = sum_of_squares(&x).val();
+    close(val, x.iter().map(|x| x.val() * x.val()).sum())
+        && grad.iter().zip(&x).all(|(d, x)| close(*d, 2.0 * x.val()))
+}
+
+#[test]
+fn mixed_with_static_xpr_test() {
+    let x = Dual::from(0.5).seeded();
+    let y = Dual::from(2.0);
+    let product: DynXpr = (x * y).into_dyn();
+    // runtime expression on both sides of static operators
+    let res = Dual::from((product.clone() + x.sin()) * (y - product) / 2.0);
+    let expected = Dual::from((x * y + x.sin()) * (y - x * y) / 2.0);
+    assert!(close(res.val(), expected.val()));
+    assert!(close(res.der(), expected.der()));
+}
+
+#[test]
+fn functions_of_dyn_xpr_test() {
+    let x = Dual::from(0.3).seeded();
+    let mut xpr = DynXpr::new(x);
+    xpr *= 3.0;
+    xpr -= x;
+    xpr /= DynXpr::new(x.exp());
+    let res = Dual::from(xpr.sin().powf(x));
+    let expected = Dual::from(((x * 3.0 - x) / x.exp()).sin().powf(x));
+    assert!(close(res.val(), expected.val()));
+    assert!(close(res.der(), expected.der()));
+}
+
+#[test]
+fn dual_n_test() {
+    let [x, y] = DualN::<2>::variables([1.5, -2.0]);
+    let mut xpr = DynXpr::<DualN<2>>::new(x);
+    for _ in 0..3 {
+        xpr *= y;
+    }
+    let res = xpr.eval();
+    assert_eq!(res.val(), -12.0);
+    assert_eq!(res.grad(), [-8.0, 18.0]);
+}
+
+#[test]
+fn long_chain_test() {
+    // compound assignments don't nest, so chain length is not limited by stack
+    let x = Dual::from(0.5).seeded();
+    let mut sum = DynXpr::new(0.0);
+    for _ in 0..1_000_000 {
+        sum += x;
+    }
+    let shared = sum.clone();
+    sum *= 2.0;
+    assert_eq!(shared.eval().der(), 1e6);
+    let res = sum.eval();
+    assert_eq!(res.val(), 1e6);
+    assert_eq!(res.der(), 2e6);
+}
+
+#[test]
+fn nested_dyn_xpr_test() {
+    // every nested node adds level of recursion, moderate depth is fine
+    let x = Dual::from(0.5).seeded();
+    let mut sum = DynXpr::new(0.0);
+    for _ in 0..1000 {
+        sum = DynXpr::new(sum + x);
+    }
+    let res = sum.eval();
+    assert_eq!(res.val(), 500.0);
+    assert_eq!(res.der(), 1000.0);
+}
@@ -0,0 +1,106 @@
+use num_traits::{Float, NumCast, One, Zero};
+use rad::{derivative, Dual, FloatDual};
+
+const EPSILON: f64 = 1e-9;
+
+/// Generic function written against num_traits.
+fn rosenbrock<F: Float>(x: F, y: F) -> F {
+    let one = F::one();
+    let hundred: F = NumCast::from(100.0).unwrap();
+    (one - x).powi(2) + hundred * (y - x * x).powi(2)
+}
+
+/// Derivative of scalar function 'f' at 'x'.
+fn der<F: Fn(FloatDual) -> FloatDual>(f: F, x: f64) -> f64 {
+    f(FloatDual::new(x, 1.0)).der()
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < EPSILON * expected.abs().max(1.0),
+        "{} != {}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn generic_code_test() {
+    let x = FloatDual::new(1.5, 1.0);
+    let y = FloatDual::new(2.0, 0.0);
+    let res = rosenbrock(x, y);
+    assert_close(res.val(), rosenbrock(1.5, 2.0));
+    assert_close(res.der(), -2.0 * (1.0 - 1.5) - 400.0 * 1.5 * (2.0 - 1.5 * 1.5));
+}
+
+#[test]
+fn derivative_macro_test() {
+    let x = Dual::from(1.5);
+    let f = |x: Dual| -> Dual { rosenbrock(FloatDual(x), FloatDual::one()).into() };
+    assert_close(derivative!(f(x), x), -2.0 * (1.0 - 1.5) - 400.0 * 1.5 * (1.0 - 1.5 * 1.5));
+}
+
+#[test]
+fn elementary_functions_test() {
+    let x = 0.3;
+    assert_close(der(|x| x.tan(), x), 1.0 / x.cos().powi(2));
+    assert_close(der(|x| x.asin(), x), 1.0 / (1.0 - x * x).sqrt());
+    assert_close(der(|x| x.acos(), x), -1.0 / (1.0 - x * x).sqrt());
+    assert_close(der(|x| x.atan(), x), 1.0 / (1.0 + x * x));
+    assert_close(der(|x| x.sinh(), x), x.cosh());
+    assert_close(der(|x| x.cosh(), x), x.sinh());
+    assert_close(der(|x| x.tanh(), x), 1.0 - x.tanh().powi(2));
+    assert_close(der(|x| x.asinh(), x), 1.0 / (x * x + 1.0).sqrt());
+    assert_close(der(|x| (x + FloatDual::one()).acosh(), x), 1.0 / ((x + 1.0).powi(2) - 1.0).sqrt());
+    assert_close(der(|x| x.atanh(), x), 1.0 / (1.0 - x * x));
+    assert_close(der(|x| x.exp2(), x), x.exp2() * 2.0.ln());
+    assert_close(der(|x| x.exp_m1(), x), x.exp());
+    assert_close(der(|x| x.ln_1p(), x), 1.0 / (1.0 + x));
+    assert_close(der(|x| x.log2(), x), 1.0 / (x * 2.0.ln()));
+    assert_close(der(|x| x.log10(), x), 1.0 / (x * 10.0.ln()));
+    assert_close(der(|x| x.cbrt(), x), x.cbrt() / (3.0 * x));
+    assert_close(der(|x| x.recip(), x), -1.0 / (x * x));
+    assert_close(der(|x| x.powi(3), x), 3.0 * x * x);
+    assert_close(der(|x| x.powf(x), x), x.powf(x) * (x.ln() + 1.0));
+    assert_close(der(|x| x.log(x + FloatDual::one()), x), {
+        let (a, b) = (x.ln(), (x + 1.0).ln());
+        (b / x - a / (x + 1.0)) / (b * b)
+    });
+    assert_close(der(|x| x.to_degrees(), x), 180.0 / std::f64::consts::PI);
+}
+
+#[test]
+fn powi_at_zero_test() {
+    assert_eq!(der(|x| x.powi(0), 0.0), 0.0);
+    assert_eq!(der(|x| x.powi(1), 0.0), 1.0);
+    assert_eq!(der(|x| x.powi(2), 0.0), 0.0);
+}
+
+#[test]
+fn nonsmooth_functions_test() {
+    assert_close(der(|x| x.abs(), -2.0), -1.0);
+    assert_close(der(|x| x.abs(), 2.0), 1.0);
+    assert_close(der(|x| x.floor(), 2.5), 0.0);
+    assert_close(der(|x| x.signum(), 2.5), 0.0);
+    assert_close(der(|x| x.fract(), 2.5), 1.0);
+    assert_close(der(|x| x % FloatDual::new(2.0, 0.0), 5.5), 1.0);
+    assert_close(der(|x| FloatDual::new(5.5, 0.0) % x, 2.0), -2.0);
+    assert_close(der(|x| x.max(FloatDual::zero()), 2.0), 1.0);
+    assert_close(der(|x| x.min(FloatDual::zero()), 2.0), 0.0);
+}
+
+#[test]
+fn binary_functions_test() {
+    let y = FloatDual::new(3.0, 0.0);
+    assert_close(der(|x| x.atan2(y), 4.0), 3.0 / 25.0);
+    assert_close(der(|x| y.atan2(x), 4.0), -3.0 / 25.0);
+    assert_close(der(|x| x.hypot(y), 4.0), 4.0 / 5.0);
+    assert_close(der(|x| x.mul_add(x, y), 4.0), 8.0);
+}
+
+#[test]
+fn f32_test() {
+    let x = FloatDual::<f32>::new(0.5, 1.0);
+    let y = x.sin() * x;
+    assert!((y.der() - (0.5f32.cos() * 0.5 + 0.5f32.sin())).abs() < 1e-6);
+}
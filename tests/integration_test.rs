@@ -1,4 +1,4 @@
-use rad::{Dual, gradient, derivative};
+use rad::{Dual, DualN, HyperDual, Jacobian, gradient, derivative, hessian, jacobian};
 
 #[test]
 fn test_sin_xpr(){
@@ -158,3 +158,272 @@ fn gradient_2args_test() {
     let v = gradient!(f(&x, &y), x, y);
     println!("v: {:#?}", v);
 }
+
+#[test]
+fn gradient_fixed_args_test() {
+    let x = Dual::from(1.0);
+    let y = Dual::from(2.0);
+    let z = Dual::from(3.0);
+    let f = |x: DualN<3>, y: DualN<3>, z: DualN<3>| -> DualN<3> { (x * y + y * z.sin() + z / x).into() };
+    let v = gradient!(f(x, y, z), [x, y, z]);
+    assert_eq!(v.len(), 3);
+    assert!((v[0] - (2.0 - 3.0)).abs() < 1e-12);
+    assert!((v[1] - (1.0 + 3.0f64.sin())).abs() < 1e-12);
+    assert!((v[2] - (2.0 * 3.0f64.cos() + 1.0)).abs() < 1e-12);
+}
+
+#[test]
+fn dual_n_variables_test() {
+    let [x, y] = DualN::<2>::variables([3.0, 4.0]);
+    let r = DualN::from((x * x + y * y).sqrt());
+    assert_eq!(r.val(), 5.0);
+    assert!((r.der(0) - 0.6).abs() < 1e-12);
+    assert!((r.der(1) - 0.8).abs() < 1e-12);
+}
+
+#[test]
+fn jacobian_test() {
+    let x = vec![Dual::from(1.0), Dual::from(2.0)];
+    let f = |x: &Vec<Dual>| -> Vec<Dual> {
+        vec![(x[0] * x[1]).into(), (x[0] + 3.0 * x[1]).into(), x[1].sin().into()]
+    };
+    let j = jacobian!(f(&x), x);
+    assert_eq!(j.shape(), (3, 2));
+    assert_eq!(j.row_major(), &[2.0, 1.0, 1.0, 3.0, 0.0, 2.0f64.cos()]);
+    assert_eq!(j.col_major(), vec![2.0, 1.0, 0.0, 1.0, 3.0, 2.0f64.cos()]);
+}
+
+#[test]
+fn jacobian_2args_array_test() {
+    let x = vec![Dual::from(1.0)];
+    let y = vec![Dual::from(2.0)];
+    let f = |x: &Vec<Dual>, y: &Vec<Dual>| -> [Dual; 2] { [(x[0] * y[0]).into(), (x[0] - y[0]).into()] };
+    let j = jacobian!(f(&x, &y), x, y);
+    assert_eq!(j.shape(), (2, 2));
+    assert_eq!(j[(0, 0)], 2.0);
+    assert_eq!(j[(0, 1)], 1.0);
+    assert_eq!(j[(1, 0)], 1.0);
+    assert_eq!(j[(1, 1)], -1.0);
+}
+
+#[test]
+fn jacobian_fixed_args_test() {
+    let x = Dual::from(1.0);
+    let y = Dual::from(2.0);
+    let f = |x: DualN<2>, y: DualN<2>| -> Vec<DualN<2>> { vec![(x * y).into(), (x / y).into()] };
+    let j = jacobian!(f(x, y), [x, y]);
+    assert_eq!(j.shape(), (2, 2));
+    assert_eq!(j.row(0), &[2.0, 1.0]);
+    assert_eq!(j.row(1), &[0.5, -0.25]);
+}
+
+#[test]
+fn jacobian_function_test() {
+    let f = |x: &[Dual]| -> Vec<Dual> { vec![(x[0] * x[1]).into(), (x[0] + 3.0 * x[1]).into()] };
+    let j = rad::jacobian(f, &[1.0, 2.0]);
+    assert_eq!(j, Jacobian::from_rows(vec![vec![2.0, 1.0], vec![1.0, 3.0]]));
+}
+
+/// Point with variables in fields, macros accept them as place expressions.
+struct Point {
+    x: Dual,
+    ys: Vec<Dual>,
+}
+
+impl Point {
+    fn der_x(&self) -> f64 {
+        let f = |x: Dual, y: Dual| -> Dual { (x * x * y).into() };
+        derivative!(f(self.x, self.ys[0]), self.x)
+    }
+}
+
+#[test]
+fn place_expression_test() {
+    let p = Point {
+        x: Dual::from(2.0),
+        ys: vec![Dual::from(3.0), Dual::from(5.0)],
+    };
+    assert_eq!(p.der_x(), 12.0);
+    let f = |x: Dual, y: Dual| -> Dual { (x * x * y).into() };
+    assert_eq!(derivative!(f(p.x, p.ys[1]), p.ys[1]), 4.0);
+    assert_eq!(derivative!(f(p.ys[0], p.ys[0]), p.ys[0]), 27.0);
+    assert_eq!(derivative!(f(p.x, p.ys[0]), p.x; 0.5), 6.0);
+
+    let g = |x: Dual, ys: &Vec<Dual>| -> Dual { (x * ys[0] * ys[1]).into() };
+    assert_eq!(gradient!(g(p.x, &p.ys), p.ys), vec![10.0, 6.0]);
+    let xs = vec![p.x];
+    assert_eq!(gradient!(g(xs[0], &p.ys), xs, p.ys), vec![15.0, 10.0, 6.0]);
+    assert_eq!(gradient!(g(p.x, &p.ys), p.ys; [1.0, 1.0]), 16.0);
+
+    let h = |ys: &Vec<Dual>| -> Vec<Dual> { vec![(ys[0] * ys[1]).into()] };
+    assert_eq!(jacobian!(h(&p.ys), p.ys).row(0), &[5.0, 3.0]);
+    let q = |ys: &Vec<HyperDual>| -> HyperDual { (ys[0] * ys[1]).into() };
+    assert_eq!(hessian!(q(&p.ys), p.ys), vec![vec![0.0, 1.0], vec![1.0, 0.0]]);
+    // inputs are not mutated
+    assert_eq!((p.x.der(), p.ys[0].der()), (0.0, 0.0));
+}
+
+#[test]
+fn jacobian_without_inputs_test() {
+    let f = |_: &[Dual]| -> Vec<Dual> { vec![Dual::from(1.0), Dual::from(2.0), Dual::from(3.0)] };
+    let j = rad::jacobian(f, &[]);
+    assert_eq!(j.shape(), (3, 0));
+    assert!(j.row(2).is_empty());
+    let x: Vec<Dual> = Vec::new();
+    let g = |x: &Vec<Dual>| -> Vec<Dual> { vec![x.iter().fold(Dual::from(1.0), |s, v| (s * *v).into())] };
+    assert_eq!(jacobian!(g(&x), x).shape(), (1, 0));
+}
+
+#[test]
+fn hessian_fixed_args_test() {
+    let x = Dual::from(1.0);
+    let y = Dual::from(2.0);
+    let f = |x: HyperDual, y: HyperDual| -> HyperDual { (x * x * y + y.sin()).into() };
+    let h = hessian!(f(x, y), [x, y]);
+    assert_eq!(h, vec![vec![4.0, 2.0], vec![2.0, -(2.0f64.sin())]]);
+}
+
+#[test]
+fn hessian_2args_test() {
+    let x = [Dual::from(1.0), Dual::from(2.0)];
+    let y = [Dual::from(3.0)];
+    let f = |x: &Vec<HyperDual>, y: &Vec<HyperDual>| -> HyperDual { (x[0] * x[1] * y[0] + y[0] * y[0]).into() };
+    let h = hessian!(f(&x, &y), x, y);
+    assert_eq!(h, vec![vec![0.0, 3.0, 2.0], vec![3.0, 0.0, 1.0], vec![2.0, 1.0, 2.0]]);
+}
+
+#[test]
+fn dual_f32_derivative_test() {
+    let x = Dual::<f32>::new(2.0);
+    let f = |x: Dual<f32>| -> Dual<f32> { (x * x * 3.0 + x.sin()).into() };
+    let d = derivative!(f(x), x);
+    assert!((d - (12.0 + 2.0f32.cos())).abs() < 1e-6);
+}
+
+#[test]
+fn nested_dual_second_derivative_test() {
+    let x = Dual::new(Dual::new(2.0).seeded()).seeded();
+    let f = |x: Dual<Dual>| -> Dual<Dual> { (x * x * x + x.sin()).into() };
+    let y = f(x);
+    assert_eq!(y.val().val(), 8.0 + 2.0f64.sin());
+    assert_eq!(y.der().val(), 12.0 + 2.0f64.cos());
+    assert_eq!(y.der().der(), 12.0 - 2.0f64.sin());
+}
+
+#[test]
+fn dual_is_send_and_sync_test() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Dual>();
+    assert_send_sync::<Dual<f32>>();
+}
+
+#[test]
+fn gradient_from_threads_test() {
+    let x = std::sync::Arc::new(vec![Dual::from(1.0), Dual::from(2.0)]);
+    let handles: Vec<_> = (0..4)
+        .map(|k| {
+            let x = x.clone();
+            std::thread::spawn(move || {
+                let f = |x: &Vec<Dual>| -> Dual { (x[0] * x[1] * k as f64).into() };
+                let x: &Vec<Dual> = &x;
+                gradient!(f(&x), x)
+            })
+        })
+        .collect();
+    for (k, handle) in handles.into_iter().enumerate() {
+        assert_eq!(handle.join().unwrap(), vec![2.0 * k as f64, k as f64]);
+    }
+    assert_eq!(x[0].der(), 0.0);
+}
+
+type Objective = Box<dyn Fn(&[Dual]) -> Dual>;
+
+struct Model {
+    f: Objective,
+}
+
+#[test]
+fn derivative_fn_test() {
+    let f = |x: Dual| -> Dual { (x.sin() * x).into() };
+    let g: &dyn Fn(Dual) -> Dual = &f;
+    let x = Dual::from(0.5);
+    let (val, der) = rad::derivative(g, 0.5);
+    assert_eq!(val, 0.5f64.sin() * 0.5);
+    assert_eq!(der, derivative!(f(x), x));
+}
+
+#[test]
+fn gradient_fn_test() {
+    let model = Model {
+        f: Box::new(|x: &[Dual]| -> Dual { (x[0] * x[1] + x[2].exp()).into() }),
+    };
+    let (val, grad) = rad::gradient(&model.f, &[2.0, 3.0, 0.0]);
+    assert_eq!(val, 7.0);
+    assert_eq!(grad, vec![3.0, 2.0, 1.0]);
+    let (val, grad) = rad::gradient(|_: &[Dual]| Dual::from(4.0), &[]);
+    assert_eq!((val, grad), (4.0, vec![]));
+}
+
+#[test]
+fn seed_with_test() {
+    let mut x = Dual::with_derivative(2.0, 3.0);
+    assert_eq!((x.val(), x.der()), (2.0, 3.0));
+    x.seed_with(0.5);
+    assert_eq!(x.der(), 0.5);
+    let y = Dual::from(x.sin() * x);
+    assert_eq!(y.der(), 0.5 * (2.0f64.cos() * 2.0 + 2.0f64.sin()));
+    assert_eq!(x.seeded_with(-1.0).der(), -1.0);
+}
+
+#[test]
+fn weighted_derivative_test() {
+    let f = |x: Dual| -> Dual { (x * x).into() };
+    let x = Dual::from(3.0);
+    assert_eq!(derivative!(f(x), x; 0.5), 3.0);
+    assert_eq!(x.der(), 0.0);
+}
+
+#[test]
+fn weighted_gradient_test() {
+    let f = |x: &Vec<Dual>, y: &Vec<Dual>| -> Dual { (x[0] * x[1] + y[0]).into() };
+    let x = vec![Dual::from(2.0), Dual::from(3.0)];
+    let y = vec![Dual::from(1.0)];
+    let w = [1.0, 2.0, -1.0];
+    assert_eq!(gradient!(f(&x, &y), x, y; w), 6.0);
+    // function is evaluated once with all inputs seeded
+    let calls = std::cell::Cell::new(0);
+    let h = |x: &Vec<Dual>| -> Dual {
+        calls.set(calls.get() + 1);
+        (x[0] * x[1]).into()
+    };
+    assert_eq!(gradient!(h(&x), x; [1.0, -1.0]), 1.0);
+    assert_eq!(calls.get(), 1);
+    let g = |x: DualN<2>, y: DualN<2>| -> DualN<2> { (x * y).into() };
+    let (x, y) = (DualN::<2>::new(2.0), DualN::<2>::new(3.0));
+    assert_eq!(gradient!(g(x, y), [x, y]; [0.5, 2.0]), 5.5);
+}
+
+#[test]
+fn seeds_in_place_test() {
+    // one copy of collection is evaluated with one seeded element at a time
+    let buffers = std::cell::RefCell::new(Vec::new());
+    let f = |x: &Vec<Dual>, y: &Vec<Dual>| -> Dual {
+        let seeded = x.iter().chain(y).filter(|v| v.der() != 0.0).count();
+        buffers.borrow_mut().push((x.as_ptr(), seeded));
+        (x[0] * x[1] + x[2] * y[0]).into()
+    };
+    let x = vec![Dual::from(1.0), Dual::from(2.0), Dual::from(3.0)];
+    let y = vec![Dual::from(4.0)];
+    assert_eq!(gradient!(f(&x, &y), x, y), vec![2.0, 1.0, 4.0, 3.0]);
+    let buffers = buffers.into_inner();
+    assert!(buffers.iter().all(|&(ptr, seeded)| ptr == buffers[0].0 && seeded == 1));
+    assert!(x.iter().chain(&y).all(|v| v.der() == 0.0));
+}
+
+#[test]
+#[should_panic(expected = "count of seed weights")]
+fn weighted_gradient_mismatch_test() {
+    let f = |x: &Vec<Dual>| -> Dual { x[0] };
+    let x = vec![Dual::from(2.0)];
+    gradient!(f(&x), x; vec![1.0, 2.0]);
+}
@@ -0,0 +1,119 @@
+use rad::optim::{adam, conjugate_gradient, gradient_descent, lbfgs, Adam, Config, LineSearch, Termination};
+use rad::Dual;
+
+fn rosenbrock(x: &[Dual]) -> Dual {
+    ((1.0 - x[0]) * (1.0 - x[0]) + 100.0 * (x[1] - x[0] * x[0]) * (x[1] - x[0] * x[0])).into()
+}
+
+fn quadratic(x: &[Dual]) -> Dual {
+    // ill conditioned bowl with minimum at (1, -2, 3)
+    ((x[0] - 1.0) * (x[0] - 1.0) + 10.0 * (x[1] + 2.0) * (x[1] + 2.0) + 50.0 * (x[2] - 3.0) * (x[2] - 3.0))
+        .into()
+}
+
+fn close(x: &[f64], y: &[f64], tol: f64) -> bool {
+    x.iter().zip(y.iter()).all(|(x, y)| (x - y).abs() <= tol)
+}
+
+#[test]
+fn gradient_descent_test() {
+    let config = Config {
+        max_iter: 5000,
+        ..Config::default()
+    };
+    let report = gradient_descent(quadratic, &[0.0, 0.0, 0.0], &config);
+    assert_eq!(report.termination, Termination::GradientTolerance);
+    assert!(close(&report.x, &[1.0, -2.0, 3.0], 1e-8));
+    assert!(report.grad_norm <= 1e-8);
+    assert!(report.evaluations > 3 * report.iterations);
+
+    let armijo = Config {
+        line_search: LineSearch::armijo(),
+        ..config
+    };
+    let report = gradient_descent(quadratic, &[0.0, 0.0, 0.0], &armijo);
+    assert!(report.converged());
+    assert!(close(&report.x, &[1.0, -2.0, 3.0], 1e-8));
+}
+
+#[test]
+fn conjugate_gradient_test() {
+    let config = Config {
+        line_search: LineSearch::Wolfe { c1: 1e-4, c2: 0.1 },
+        ..Config::default()
+    };
+    let report = conjugate_gradient(rosenbrock, &[-1.2, 1.0], &config);
+    assert!(report.converged(), "{:?}", report);
+    assert!(close(&report.x, &[1.0, 1.0], 1e-6));
+
+    let steepest = gradient_descent(quadratic, &[0.0, 0.0, 0.0], &config);
+    let report = conjugate_gradient(quadratic, &[0.0, 0.0, 0.0], &config);
+    assert!(report.converged());
+    assert!(report.iterations < steepest.iterations);
+}
+
+#[test]
+fn lbfgs_test() {
+    let report = lbfgs(rosenbrock, &[-1.2, 1.0], 5, &Config::default());
+    assert_eq!(report.termination, Termination::GradientTolerance);
+    assert!(close(&report.x, &[1.0, 1.0], 1e-8));
+    assert!(report.iterations < 100);
+    assert!(report.value < 1e-15);
+
+    // extended Rosenbrock in 10 dimensions
+    let f = |x: &[Dual]| -> Dual {
+        let mut sum = Dual::from(0.0);
+        for i in 0..x.len() / 2 {
+            sum = (sum + rosenbrock(&x[2 * i..2 * i + 2])).into();
+        }
+        sum
+    };
+    let x0: Vec<f64> = (0..10).map(|i| if i % 2 == 0 { -1.2 } else { 1.0 }).collect();
+    let report = lbfgs(f, &x0, 7, &Config::default());
+    assert!(report.converged());
+    assert!(close(&report.x, &[1.0; 10], 1e-7));
+}
+
+#[test]
+fn adam_test() {
+    let params = Adam {
+        learning_rate: 0.05,
+        ..Adam::default()
+    };
+    let config = Config {
+        grad_tol: 1e-6,
+        max_iter: 20000,
+        ..Config::default()
+    };
+    let report = adam(quadratic, &[0.0, 0.0, 0.0], &params, &config);
+    assert_eq!(report.termination, Termination::GradientTolerance);
+    assert!(close(&report.x, &[1.0, -2.0, 3.0], 1e-6));
+    assert_eq!(report.evaluations, 3 * (report.iterations + 1));
+}
+
+#[test]
+fn termination_test() {
+    let config = Config {
+        max_iter: 3,
+        ..Config::default()
+    };
+    let report = gradient_descent(rosenbrock, &[-1.2, 1.0], &config);
+    assert_eq!(report.termination, Termination::MaxIterations);
+    assert_eq!(report.iterations, 3);
+    assert!(!report.converged());
+
+    let config = Config {
+        f_tol: 1e-3,
+        ..Config::default()
+    };
+    let report = lbfgs(rosenbrock, &[-1.2, 1.0], 5, &config);
+    assert_eq!(report.termination, Termination::FunctionTolerance);
+
+    let report = lbfgs(|x: &[Dual]| (x[0] - 1.0).ln().into(), &[0.5], 5, &Config::default());
+    assert_eq!(report.termination, Termination::NotFinite);
+    assert_eq!(Termination::NotFinite.to_string(), "objective or gradient is not finite");
+
+    // unbounded objective, line search keeps doubling the step
+    let report = lbfgs(|x: &[Dual]| (-x[0]).into(), &[0.0], 5, &Config::default());
+    assert_eq!(report.termination, Termination::LineSearchFailed);
+}
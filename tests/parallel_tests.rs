@@ -0,0 +1,25 @@
+#![cfg(feature = "rayon")]
+
+use rad::{gradient, jacobian, par_gradient, par_jacobian, Dual};
+
+#[test]
+fn par_gradient_test() {
+    let f = |x: &[Dual]| -> Dual {
+        x.iter()
+            .enumerate()
+            .fold(Dual::from(0.0), |acc, (i, x)| (acc + (i as f64 + 1.0) * x.sin() * *x).into())
+    };
+    let vals: Vec<f64> = (0..64).map(|i| i as f64 * 0.1).collect();
+    let xs: Vec<Dual> = vals.iter().map(|v| Dual::from(*v)).collect();
+    assert_eq!(par_gradient(f, &vals), gradient!(f(&xs), xs));
+}
+
+#[test]
+fn par_jacobian_test() {
+    let f = |x: &[Dual]| -> Vec<Dual> {
+        vec![(x[0] * x[1]).into(), (x[0] + 3.0 * x[2]).into(), x[1].exp().into()]
+    };
+    let x = [1.0, 2.0, 3.0];
+    assert_eq!(par_jacobian(f, &x), jacobian(f, &x));
+    assert_eq!(par_jacobian(|_: &[Dual]| vec![Dual::from(1.0)], &[]).shape(), (1, 0));
+}
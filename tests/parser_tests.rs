@@ -0,0 +1,72 @@
+use rad::parser::{Formula, ParseError, UnboundVariable};
+use rad::{gradient, Dual};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() <= 1e-12 * a.abs().max(b.abs()).max(1.0)
+}
+
+fn eval(src: &str, vars: &[(&str, f64)]) -> f64 {
+    Formula::parse(src).unwrap().eval(vars).unwrap()
+}
+
+fn error(src: &str) -> ParseError {
+    Formula::parse(src).unwrap_err()
+}
+
+#[test]
+fn precedence_test() {
+    assert_eq!(eval("1 + 2 * 3 - 4 / 2", &[]), 5.0);
+    assert_eq!(eval("-2^2", &[]), -4.0);
+    assert_eq!(eval("2^3^2", &[]), 512.0);
+    assert_eq!(eval("2^-1", &[]), 0.5);
+    assert_eq!(eval("(1 + 2) * 3", &[]), 9.0);
+    assert_eq!(eval("1.5e1 + .5 + 2E-1", &[]), 15.7);
+    assert_eq!(eval("8 / 2 / 2 - 1 - 1", &[]), 0.0);
+}
+
+#[test]
+fn gradient_test() {
+    let formula = Formula::parse("x*sin(y) + exp(-z^2)").unwrap();
+    assert_eq!(formula.variables(), ["x", "y", "z"]);
+    let f = |v: &Vec<Dual>| -> Dual { (v[0] * v[1].sin() + (-(v[2] * v[2])).exp()).into() };
+    let v = vec![Dual::from(1.5), Dual::from(0.3), Dual::from(-0.7)];
+    let (val, grad) = formula.gradient(&[("x", 1.5), ("y", 0.3), ("z", -0.7)]).unwrap();
+    let expected = gradient!(f(&v), v);
+    assert!(close(val, f(&v).val()));
+    assert!(grad.iter().zip(&expected).all(|(a, b)| close(*a, *b)), "{:?} != {:?}", grad, expected);
+}
+
+#[test]
+fn functions_test() {
+    let vars = [("a", 0.5), ("b", 2.0)];
+    assert!(close(eval("sqrt(b) * ln(b) + cos(a) - tan(a)", &vars), 2f64.sqrt() * 2f64.ln() + 0.5f64.cos() - 0.5f64.tan()));
+    assert!(close(eval("powf(b, a) + atan2(a, b) + hypot(a, b)", &vars), 2f64.powf(0.5) + 0.5f64.atan2(2.0) + 0.5f64.hypot(2.0)));
+    assert_eq!(eval("min(a, b) + max(a, b) + abs(-a)", &vars), 3.0);
+    let formula = Formula::parse("b^a").unwrap();
+    let (_, grad) = formula.gradient(&[("b", 2.0), ("a", 0.5), ("c", 1.0)]).unwrap();
+    assert!(close(grad[0], 0.5 * 2f64.powf(-0.5)));
+    assert!(close(grad[1], 2f64.powf(0.5) * 2f64.ln()));
+    assert_eq!(grad[2], 0.0);
+}
+
+#[test]
+fn unbound_variable_test() {
+    let formula = Formula::parse("x + y").unwrap();
+    let err = formula.eval(&[("x", 1.0)]).unwrap_err();
+    assert_eq!(err, UnboundVariable { name: "y".to_string() });
+    assert_eq!(err.to_string(), "variable 'y' has no value");
+}
+
+#[test]
+fn parse_errors_test() {
+    assert_eq!(error("x + * y"), ParseError { position: 4, message: "unexpected '*', expected expression".to_string() });
+    assert_eq!(error("sin(x").position, 5);
+    assert_eq!(error("sin(x").message, "expected ')'");
+    assert_eq!(error("2 * foo(x)").message, "unknown function 'foo'");
+    assert_eq!(error("2 * foo(x)").position, 4);
+    assert_eq!(error("atan2(x)").message, "function 'atan2' takes 2 argument(s), 1 given");
+    assert_eq!(error("x y").to_string(), "unexpected 'y' after expression at position 2");
+    assert_eq!(error("1e+").message, "invalid number '1e+'");
+    assert_eq!(error("x +").message, "unexpected end of formula, expected expression");
+    assert_eq!(error("x # 2").position, 2);
+}
@@ -0,0 +1,71 @@
+use rad::{Dual, DualN, Operation, Visit, Visitor};
+
+#[test]
+fn dual_display_test() {
+    assert_eq!(Dual::from(1.5).seeded().to_string(), "1.5 + 1ε");
+    assert_eq!(Dual::from(1.5).seeded_with(-2.0).to_string(), "1.5 - 2ε");
+    assert_eq!(Dual::new(0.5f32).to_string(), "0.5 + 0ε");
+}
+
+#[test]
+fn text_test() {
+    let x = Dual::from(1.5).seeded();
+    let y = Dual::from(2.0);
+    assert_eq!((x * y.sin() + 3.0).to_string(), "(1.5 + 1ε) * sin(2 + 0ε) + 3");
+    assert_eq!((x - (y + 1.0)).to_string(), "1.5 + 1ε - (2 + 0ε + 1)");
+    assert_eq!((2.0 / (x * 3.0)).to_string(), "2 / ((1.5 + 1ε) * 3)");
+    assert_eq!((-(x.powi(2)) + x.powi(-1)).to_string(), "-(1.5 + 1ε)^2 + (1.5 + 1ε)^(-1)");
+    assert_eq!((-(-x)).to_string(), "-(-(1.5 + 1ε))");
+    assert_eq!(x.powf(y.powf(2.0)).to_string(), "(1.5 + 1ε)^(2 + 0ε)^2");
+    assert_eq!((x.powf(y) * 1.0).powf(2.0).to_string(), "((1.5 + 1ε)^(2 + 0ε) * 1)^2");
+    assert_eq!(x.atan2(-1.0).to_string(), "atan2(1.5 + 1ε, -1)");
+}
+
+#[test]
+fn latex_test() {
+    let x = Dual::from(1.5).seeded();
+    let y = Dual::from(2.0);
+    assert_eq!(
+        ((x + 1.0) * y.sin() / x.sqrt()).to_latex(),
+        "\\frac{\\left(1.5 + 1\\varepsilon + 1\\right) \\cdot \\sin\\left(2 + 0\\varepsilon\\right)}{\\sqrt{1.5 + 1\\varepsilon}}"
+    );
+    assert_eq!((x.exp() - x.powi(3)).to_latex(), "e^{1.5 + 1\\varepsilon} - \\left(1.5 + 1\\varepsilon\\right)^{3}");
+    assert_eq!(x.log(2.0).abs().to_latex(), "\\left|\\log_{2}\\left(1.5 + 1\\varepsilon\\right)\\right|");
+    assert_eq!(x.hypot(y).to_latex(), "\\operatorname{hypot}\\left(1.5 + 1\\varepsilon, 2 + 0\\varepsilon\\right)");
+}
+
+#[test]
+fn dot_test() {
+    let [x, y] = DualN::<2>::variables([1.5, 2.0]);
+    let dot = (x * y + 2.0).sin().to_dot();
+    assert_eq!(
+        dot,
+        "digraph xpr {\n    n0 [shape=box, label=\"val = 1.5\\nder = [1, 0]\"];\n    n1 [shape=box, label=\"val = 2\\nder = [0, 1]\"];\n    n2 [label=\"*\"];\n    n2 -> n0 [label=\"l\"];\n    n2 -> n1 [label=\"r\"];\n    n3 [shape=plaintext, label=\"2\"];\n    n4 [label=\"+\"];\n    n4 -> n2 [label=\"l\"];\n    n4 -> n3 [label=\"r\"];\n    n5 [label=\"sin\"];\n    n5 -> n4;\n}\n"
+    );
+}
+
+/// Counts nodes of expression tree.
+struct Counter;
+
+impl Visitor for Counter {
+    type Output = usize;
+    fn leaf(&mut self, _: f64, _: &[f64]) -> usize {
+        1
+    }
+    fn constant(&mut self, _: f64) -> usize {
+        1
+    }
+    fn unary(&mut self, op: Operation, arg: usize) -> usize {
+        assert_eq!(op, Operation::Powi(3));
+        arg + 1
+    }
+    fn binary(&mut self, _: Operation, l: usize, r: usize) -> usize {
+        l + r + 1
+    }
+}
+
+#[test]
+fn custom_visitor_test() {
+    let x = Dual::from(1.5);
+    assert_eq!((x * 2.0 + x).powi(3).accept(&mut Counter), 6);
+}
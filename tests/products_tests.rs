@@ -0,0 +1,77 @@
+#[cfg(test)]
+extern crate quickcheck;
+#[cfg(test)]
+#[macro_use(quickcheck)]
+extern crate quickcheck_macros;
+
+use rad::{jacobian, jvp, vjp, Dual, Var};
+
+const EPSILON: f64 = 1e-12;
+
+fn forward(x: &[Dual]) -> Vec<Dual> {
+    vec![
+        (x[0] * x[1] + x[2].sin()).into(),
+        (x[0].exp() / x[1]).into(),
+        (x[2] * x[2] * 3.0).into(),
+        (x[1] - x[0]).into(),
+    ]
+}
+
+fn reverse<'t>(x: &[Var<'t>]) -> Vec<Var<'t>> {
+    vec![
+        x[0] * x[1] + x[2].sin(),
+        x[0].exp() / x[1],
+        x[2] * x[2] * 3.0,
+        x[1] - x[0],
+    ]
+}
+
+fn close(a: &[f64], b: &[f64]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(a, b)| (a - b).abs() <= EPSILON * a.abs().max(b.abs()).max(1.0))
+}
+
+/// Map arbitrary number into small range to keep function well defined.
+fn small(x: f64) -> f64 {
+    if x.is_finite() {
+        x.sin() + 1.5
+    } else {
+        1.0
+    }
+}
+
+#[quickcheck]
+fn jvp_property(x: (f64, f64, f64), v: (f64, f64, f64)) -> bool {
+    let x = [small(x.0), small(x.1), small(x.2)];
+    let v = [small(v.0), small(v.1), -small(v.2)];
+    let jac = jacobian(forward, &x);
+    let expected: Vec<f64> = (0..4).map(|i| jac.row(i).iter().zip(&v).map(|(j, v)| j * v).sum()).collect();
+    let (vals, prod) = jvp(forward, &x, &v);
+    close(&prod, &expected) && vals.len() == 4
+}
+
+#[quickcheck]
+fn vjp_property(x: (f64, f64, f64), v: (f64, f64, f64, f64)) -> bool {
+    let x = [small(x.0), small(x.1), small(x.2)];
+    let v = [small(v.0), -small(v.1), small(v.2), small(v.3)];
+    let jac = jacobian(forward, &x);
+    let expected: Vec<f64> = (0..3).map(|j| jac.column(j).iter().zip(&v).map(|(j, v)| j * v).sum()).collect();
+    let (vals, prod) = vjp(reverse, &x, &v);
+    let (fvals, _) = jvp(forward, &x, &[0.0; 3]);
+    close(&prod, &expected) && close(&vals, &fvals)
+}
+
+#[test]
+fn vjp_repeated_output_test() {
+    fn f<'t>(x: &[Var<'t>]) -> Vec<Var<'t>> {
+        vec![x[0] * 2.0, x[0] * 2.0, x[1]]
+    }
+    let (_, prod) = vjp(f, &[1.0, 1.0], &[1.0, 0.5, 0.0]);
+    assert_eq!(prod, vec![3.0, 0.0]);
+}
+
+#[test]
+#[should_panic(expected = "different length")]
+fn jvp_length_mismatch_test() {
+    jvp(forward, &[1.0, 2.0, 3.0], &[1.0]);
+}
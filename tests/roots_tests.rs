@@ -0,0 +1,139 @@
+use rad::solvers::roots::{damped_newton, halley, newton, newton_system, Config, Convergence, RootError};
+use rad::{Dual, HyperDual};
+
+#[test]
+fn newton_test() {
+    let root = newton(|x: Dual| (x * x - 2.0).into(), 1.0, &Config::default()).unwrap();
+    assert!((root.x - 2f64.sqrt()).abs() < 1e-15);
+    assert_eq!(root.convergence, Convergence::Residual);
+    assert!(root.iterations <= 6);
+    assert_eq!(root.evaluations, root.iterations + 1);
+
+    let root = newton(|x: Dual| (x.cos() - x).into(), 0.0, &Config::default()).unwrap();
+    assert!((root.x.cos() - root.x).abs() <= 1e-12);
+}
+
+#[test]
+fn zero_derivative_test() {
+    let f = |x: Dual| -> Dual { (x * x - 2.0).into() };
+    assert_eq!(
+        newton(f, 0.0, &Config::default()),
+        Err(RootError::ZeroDerivative { x: 0.0 })
+    );
+
+    // bisection steps in when derivative vanishes
+    let config = Config {
+        bracket: Some((-0.5, 2.0)),
+        ..Config::default()
+    };
+    let root = newton(f, 0.0, &config).unwrap();
+    assert!((root.x - 2f64.sqrt()).abs() < 1e-12);
+}
+
+#[test]
+fn bracket_test() {
+    // Newton steps of atan overshoot from x0 = 2 and diverge until derivative underflows
+    let f = |x: Dual| -> Dual { x.atan().into() };
+    match newton(f, 2.0, &Config::default()) {
+        Err(RootError::ZeroDerivative { x }) => assert!(x.abs() > 1e100),
+        res => panic!("unexpected result {:?}", res),
+    }
+
+    let config = Config {
+        bracket: Some((-1.0, 3.0)),
+        ..Config::default()
+    };
+    let root = newton(f, 2.0, &config).unwrap();
+    assert!(root.x.abs() < 1e-12);
+
+    let config = Config {
+        bracket: Some((1.0, 3.0)),
+        ..Config::default()
+    };
+    assert_eq!(newton(f, 2.0, &config), Err(RootError::InvalidBracket { a: 1.0, b: 3.0 }));
+}
+
+#[test]
+fn damped_newton_test() {
+    let root = damped_newton(|x: Dual| x.atan().into(), 2.0, &Config::default()).unwrap();
+    assert!(root.x.abs() < 1e-12);
+    assert!(root.evaluations > root.iterations + 1);
+
+    let config = Config {
+        min_damping: 0.5,
+        ..Config::default()
+    };
+    match damped_newton(|x: Dual| (x * x + 1.0).into(), 0.5, &config) {
+        Err(RootError::LineSearch { residual, .. }) => assert!(residual >= 1.0),
+        res => panic!("unexpected result {:?}", res),
+    }
+}
+
+#[test]
+fn halley_test() {
+    let config = Config::default();
+    let root = halley(|x: HyperDual| (x * x * x - 2.0).into(), 1.0, &config).unwrap();
+    assert!((root.x - 2f64.cbrt()).abs() < 1e-15);
+
+    let newton_root = newton(|x: Dual| (x * x * x - 2.0).into(), 1.0, &config).unwrap();
+    assert!(root.iterations < newton_root.iterations);
+
+    let root = halley(|x: HyperDual| (x.exp() - 3.0).into(), 0.0, &config).unwrap();
+    assert!((root.x - 3f64.ln()).abs() < 1e-12);
+}
+
+#[test]
+fn limits_test() {
+    let config = Config {
+        max_iter: 2,
+        ..Config::default()
+    };
+    match newton(|x: Dual| (x * x - 2.0).into(), 100.0, &config) {
+        Err(RootError::MaxIterations { x, residual }) => {
+            assert!(x < 100.0 && residual > 1.0);
+        }
+        res => panic!("unexpected result {:?}", res),
+    }
+
+    let config = Config {
+        x_tol: 1e-3,
+        f_tol: 0.0,
+        ..Config::default()
+    };
+    let root = newton(|x: Dual| (x * x - 2.0).into(), 1.0, &config).unwrap();
+    assert_eq!(root.convergence, Convergence::Step);
+    assert!((root.x - 2f64.sqrt()).abs() < 1e-6);
+
+    assert_eq!(
+        RootError::<f64>::InvalidBracket { a: 1.0, b: 3.0 }.to_string(),
+        "function has the same sign at 1 and 3"
+    );
+}
+
+#[test]
+fn newton_system_test() {
+    // circle of radius 2 crossed by line y = x
+    let f = |x: &[Dual]| -> Vec<Dual> {
+        vec![(x[0] * x[0] + x[1] * x[1] - 4.0).into(), (x[0] - x[1]).into()]
+    };
+    let root = newton_system(f, &[1.0, 0.5], &Config::default()).unwrap();
+    assert!((root.x[0] - 2f64.sqrt()).abs() < 1e-12);
+    assert!((root.x[1] - 2f64.sqrt()).abs() < 1e-12);
+    assert!(root.residual <= 1e-12);
+
+    let singular = |x: &[Dual]| -> Vec<Dual> { vec![(x[0] + x[1] - 1.0).into(), (x[0] * 2.0 + x[1] * 2.0).into()] };
+    assert_eq!(
+        newton_system(singular, &[0.0, 0.0], &Config::default()),
+        Err(RootError::ZeroDerivative { x: vec![0.0, 0.0] })
+    );
+}
+
+#[test]
+fn damped_newton_system_test() {
+    // Rosenbrock gradient system, full steps from far away overshoot
+    let f = |x: &[Dual]| -> Vec<Dual> {
+        vec![(10.0 * (x[1] - x[0] * x[0])).into(), (1.0 - x[0]).into()]
+    };
+    let root = newton_system(f, &[-1.2, 1.0], &Config::default()).unwrap();
+    assert!((root.x[0] - 1.0).abs() < 1e-12 && (root.x[1] - 1.0).abs() < 1e-12);
+}
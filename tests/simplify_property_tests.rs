@@ -0,0 +1,136 @@
+// Simplified expressions are compared with the same expressions built from
+// Dual constants, which are evaluated node by node without simplification.
+
+#[cfg(test)]
+extern crate quickcheck;
+#[cfg(test)]
+#[macro_use(quickcheck)]
+extern crate quickcheck_macros;
+
+#[cfg(test)]
+use float_cmp::{ApproxEq, F64Margin};
+
+use rad::{Dual, DynXpr, HyperDual};
+
+const EPSILON: f64 = f64::EPSILON * 1000000.0;
+const ULP: i64 = 5;
+
+fn margin() -> F64Margin {
+    F64Margin {
+        epsilon: EPSILON,
+        ulps: ULP,
+    }
+}
+
+#[cfg(test)]
+mod tests_identity {
+    use super::*;
+
+    #[quickcheck]
+    fn mul_one_property(x: f64) -> bool {
+        let x = Dual::from(x).seeded();
+        let res = Dual::from(x * 1.0);
+        let swapped = Dual::from(1.0 * x.sin());
+        res.val() == x.val() && res.der() == x.der() && swapped.approx_eq(x.sin().into(), margin())
+    }
+
+    #[quickcheck]
+    fn mul_minus_one_property(x: f64, y: f64) -> bool {
+        let x = Dual::from(x).seeded();
+        let y = Dual::from(y);
+        Dual::from((x * y) * -1.0).approx_eq((x * y * Dual::from(-1.0)).into(), margin())
+    }
+
+    #[quickcheck]
+    fn add_zero_property(x: f64) -> bool {
+        let x = Dual::from(x).seeded();
+        Dual::from(x.cos() + 0.0).approx_eq(x.cos().into(), margin())
+            && Dual::from(0.0 + x.cos()).approx_eq(x.cos().into(), margin())
+            && Dual::from(x.cos() - 0.0).approx_eq(x.cos().into(), margin())
+    }
+
+    #[quickcheck]
+    fn div_one_property(x: f64) -> bool {
+        let x = Dual::from(x).seeded();
+        Dual::from(x.exp() / 1.0).approx_eq(x.exp().into(), margin())
+    }
+
+    #[quickcheck]
+    fn div_const_property(x: f64, c: f64) -> bool {
+        let x = Dual::from(x).seeded();
+        c == 0.0 || Dual::from((x * x) / c).approx_eq((x * x / Dual::from(c)).into(), margin())
+    }
+
+    #[quickcheck]
+    fn pow_one_property(x: f64) -> bool {
+        let x = Dual::from(x.abs() + 1.0).seeded();
+        Dual::from(x.powf(1.0)).approx_eq(x, margin())
+    }
+}
+
+#[cfg(test)]
+mod tests_negation {
+    use super::*;
+
+    #[quickcheck]
+    fn double_negation_property(x: f64, y: f64) -> bool {
+        let x = Dual::from(x).seeded();
+        let y = Dual::from(y);
+        let res = Dual::from(-(-x));
+        res.val() == x.val()
+            && res.der() == x.der()
+            && Dual::from(-(-(x * y))).approx_eq((x * y).into(), margin())
+            && Dual::from(-(-(-x))).approx_eq((-x).into(), margin())
+    }
+
+    #[quickcheck]
+    fn negated_operand_property(x: f64, y: f64) -> bool {
+        let x = Dual::from(x).seeded();
+        let y = Dual::from(y).seeded();
+        Dual::from(y - -x).approx_eq((y + x).into(), margin())
+            && Dual::from(y * -(-x)).approx_eq((y * x).into(), margin())
+    }
+}
+
+#[cfg(test)]
+mod tests_constant {
+    use super::*;
+
+    #[quickcheck]
+    fn fold_property(a: f64, b: f64, x: f64) -> bool {
+        let x = Dual::from(x).seeded();
+        let (ca, cb) = (Dual::from(a), Dual::from(b));
+        let a = DynXpr::new(a);
+        Dual::from(a.clone() * b + x).approx_eq((ca * cb + x).into(), margin())
+            && Dual::from(x * (a.clone() - b)).approx_eq((x * (ca - cb)).into(), margin())
+            && Dual::from(x - -(a + b)).approx_eq((x + (ca + cb)).into(), margin())
+    }
+
+    #[quickcheck]
+    fn constant_left_property(c: f64, x: f64) -> bool {
+        let x = Dual::from(x).seeded();
+        let cd = Dual::from(c);
+        let y = x.sin();
+        let y_val = Dual::from(y).val();
+        Dual::from(c + y).approx_eq((cd + y).into(), margin())
+            && Dual::from(c - y).approx_eq((cd - y).into(), margin())
+            && Dual::from(c * y).approx_eq((cd * y).into(), margin())
+            && (y_val == 0.0 || Dual::from(c / y).approx_eq((cd / y).into(), margin()))
+    }
+
+    #[quickcheck]
+    fn constant_left_hyper_dual_property(c: f64, x: f64) -> bool {
+        let x = HyperDual::seeded(x.abs() + 1.0, 1.0, 1.0);
+        let cd = HyperDual::new(c);
+        HyperDual::from(c / x.exp()).approx_eq((cd / x.exp()).into(), margin())
+            && HyperDual::from(c - x * x).approx_eq((cd - x * x).into(), margin())
+    }
+
+    #[quickcheck]
+    fn dyn_fold_property(a: f64, b: f64, x: f64) -> bool {
+        let x = Dual::from(x).seeded();
+        let c = DynXpr::new(DynXpr::new(a) * b);
+        Dual::from(c.clone() * x).approx_eq((Dual::from(a) * Dual::from(b) * x).into(), margin())
+            && DynXpr::new(-c).eval().approx_eq(Dual::from(-(a * b)), margin())
+    }
+}
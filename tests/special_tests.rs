@@ -0,0 +1,132 @@
+#[cfg(test)]
+extern crate quickcheck;
+#[cfg(test)]
+#[macro_use(quickcheck)]
+extern crate quickcheck_macros;
+
+use rad::special::{bessel_j0, bessel_j1, digamma, erf, erfc, gamma, ln_gamma, tetragamma, trigamma};
+use rad::{derivative, Dual, HyperDual};
+use std::f64::consts::PI;
+
+fn assert_close(actual: f64, expected: f64, tol: f64) {
+    assert!(
+        (actual - expected).abs() <= tol * expected.abs().max(1.0),
+        "{} != {}",
+        actual,
+        expected
+    );
+}
+
+/// Central finite difference of 'f' at 'x'.
+fn fd<F: Fn(f64) -> f64>(f: F, x: f64) -> f64 {
+    let h = 1e-6 * x.abs().max(1.0);
+    (f(x + h) - f(x - h)) / (2.0 * h)
+}
+
+#[test]
+fn erf_values_test() {
+    assert_close(erf(0.0), 0.0, 1e-15);
+    assert_close(erf(0.5), 0.520_499_877_813_046_5, 1e-14);
+    assert_close(erf(2.5), 0.999_593_047_982_555, 1e-14);
+    assert_close(erf(-2.5), -0.999_593_047_982_555, 1e-14);
+    assert_close(erfc(1.5), 0.033_894_853_524_689_27, 1e-13);
+    assert_close(erfc(3.0), 2.209_049_699_858_544e-5, 1e-13);
+    assert_close(erfc(-1.0), 1.0 + 0.842_700_792_949_714_9, 1e-14);
+}
+
+#[test]
+fn gamma_values_test() {
+    assert_close(gamma(5.0), 24.0, 1e-13);
+    assert_close(gamma(0.5), PI.sqrt(), 1e-13);
+    assert_close(gamma(-0.5), -2.0 * PI.sqrt(), 1e-13);
+    assert_close(ln_gamma(10.0), 362_880.0f64.ln(), 1e-13);
+    assert_close(digamma(1.0), -0.577_215_664_901_532_9, 1e-13);
+    assert_close(digamma(-0.5), 0.036_489_973_978_576_52, 1e-12);
+    assert_close(trigamma(1.0), PI * PI / 6.0, 1e-13);
+    assert_close(tetragamma(1.0), -2.404_113_806_319_188_5, 1e-13);
+}
+
+#[test]
+fn polygamma_negative_test() {
+    for x in [f64::NEG_INFINITY, f64::INFINITY, f64::NAN].iter() {
+        assert!(digamma(*x).is_nan() && trigamma(*x).is_nan() && tetragamma(*x).is_nan());
+    }
+    let x = Dual::from(f64::NEG_INFINITY).seeded();
+    assert!(Dual::from(x.gamma()).der().is_nan() && Dual::from(x.ln_gamma()).der().is_nan());
+
+    // large negative arguments, cot(pi x) vanishes at half integers
+    assert_close(digamma(-1e15 - 0.5), 1e15f64.ln(), 1e-14);
+    assert_close(trigamma(-1e15 - 0.5), PI * PI, 1e-14);
+    assert!(tetragamma(-1e15 - 0.5).abs() < 1e-12);
+    assert!(digamma(-1e17).is_infinite() && trigamma(-1e17).is_infinite());
+    let x = Dual::from(-1e17 - 0.5).seeded();
+    assert!(Dual::from(x.ln_gamma()).der().is_infinite());
+
+    // compare with shift to positive argument, psi(x) = psi(x + n) - sum 1/(x + k)
+    for x in [-3.0 + 1e-6, -3.0 - 1e-6, -7.25, -1e-3f64].iter() {
+        let n = 1.0 - x.floor();
+        let shift = |p: i32| (0..n as i32).map(|k| 1.0 / (x + k as f64).powi(p)).sum::<f64>();
+        assert_close(digamma(*x), digamma(x + n) - shift(1), 1e-10);
+        assert_close(trigamma(*x), trigamma(x + n) + shift(2), 1e-10);
+        assert_close(tetragamma(*x), tetragamma(x + n) - 2.0 * shift(3), 1e-10);
+    }
+}
+
+#[test]
+fn bessel_values_test() {
+    assert_close(bessel_j0(0.0), 1.0, 1e-15);
+    assert_close(bessel_j0(1.0), 0.765_197_686_557_966_6, 1e-14);
+    assert_close(bessel_j1(1.0), 0.440_050_585_744_933_5, 1e-14);
+    assert_close(bessel_j0(24.0), -0.056_230_274_166_859_27, 1e-13);
+    assert_close(bessel_j1(-24.5), 0.158_978_411_819_328_1, 1e-13);
+    assert_close(bessel_j0(30.0), -0.086_367_983_581_040_21, 1e-13);
+    assert_close(bessel_j1(30.0), -0.118_751_062_616_622_94, 1e-13);
+}
+
+#[quickcheck]
+fn erf_derivative_test(x: f64) -> bool {
+    let x = Dual::from(x.sin() * 3.0);
+    let f = |x: Dual| -> Dual { (x * 2.0).erf().into() };
+    let g = |x: Dual| -> Dual { x.erfc().into() };
+    let df = derivative!(f(x), x);
+    let dg = derivative!(g(x), x);
+    (df - 4.0 / PI.sqrt() * (-4.0 * x.val() * x.val()).exp()).abs() < 1e-14
+        && (dg - fd(erfc, x.val())).abs() < 1e-8
+}
+
+#[quickcheck]
+fn gamma_derivative_test(x: f64) -> bool {
+    let x = Dual::from(x.sin() * 2.0 + 3.0);
+    let f = |x: Dual| -> Dual { x.gamma().into() };
+    let g = |x: Dual| -> Dual { x.ln_gamma().into() };
+    let h = |x: Dual| -> Dual { x.digamma().into() };
+    let df = derivative!(f(x), x);
+    (df - fd(gamma, x.val())).abs() < 1e-7 * df.abs().max(1.0)
+        && derivative!(g(x), x) == digamma(x.val())
+        && (derivative!(h(x), x) - fd(digamma, x.val())).abs() < 1e-8
+}
+
+#[quickcheck]
+fn bessel_derivative_test(x: f64) -> bool {
+    let x = Dual::from(x);
+    let f = |x: Dual| -> Dual { x.bessel_j0().into() };
+    let g = |x: Dual| -> Dual { (x + 0.0).bessel_j1().into() };
+    (derivative!(f(x), x) + bessel_j1(x.val())).abs() < 1e-14
+        && (derivative!(g(x), x) - fd(bessel_j1, x.val())).abs() < 1e-8
+}
+
+#[test]
+fn second_derivatives_test() {
+    let second = |f: &dyn Fn(HyperDual) -> HyperDual, x: f64| f(HyperDual::seeded(x, 1.0, 1.0)).der12();
+    let erf_der = |x: f64| 2.0 / PI.sqrt() * (-x * x).exp();
+    let gamma_der = |x: f64| gamma(x) * digamma(x);
+    let j1_der = |x: f64| bessel_j0(x) - bessel_j1(x) / x;
+    for x in [0.3, 1.7, 4.2].iter() {
+        assert_close(second(&|x| x.erf().into(), *x), fd(erf_der, *x), 1e-7);
+        assert_close(second(&|x| x.gamma().into(), *x), fd(gamma_der, *x), 1e-7);
+        assert_close(second(&|x| x.ln_gamma().into(), *x), trigamma(*x), 1e-15);
+        assert_close(second(&|x| x.digamma().into(), *x), tetragamma(*x), 1e-15);
+        assert_close(second(&|x| x.bessel_j0().into(), *x), -j1_der(*x), 1e-15);
+        assert_close(second(&|x| x.bessel_j1().into(), *x), fd(j1_der, *x), 1e-7);
+    }
+}
@@ -0,0 +1,106 @@
+#[cfg(test)]
+extern crate quickcheck;
+#[cfg(test)]
+#[macro_use(quickcheck)]
+extern crate quickcheck_macros;
+
+#[cfg(test)]
+use float_cmp::{ApproxEq, F64Margin};
+
+use rad::{derivative, grad, gradient, Dual, Tape, Var};
+
+const EPSILON: f64 = f64::EPSILON * 1000000.0;
+const ULP: i64 = 5;
+
+#[cfg(test)]
+mod tests_tape {
+    use super::*;
+
+    #[quickcheck]
+    fn keeps_value(val: f64) -> bool {
+        let tape = Tape::new();
+        tape.var(val).val().approx_eq(val, F64Margin::default())
+    }
+
+    #[quickcheck]
+    fn self_derivative_property(val: f64) -> bool {
+        let tape = Tape::new();
+        let x = tape.var(val);
+        x.grad().wrt(&x).approx_eq(1.0, F64Margin::default())
+    }
+
+    #[quickcheck]
+    fn independent_variable_property(x: f64, y: f64) -> bool {
+        let tape = Tape::new();
+        let x = tape.var(x);
+        let y = tape.var(y);
+        let z = x * 2.0;
+        z.grad().wrt(&y).approx_eq(0.0, F64Margin::default())
+    }
+
+    #[quickcheck]
+    fn arithmetic_is_same_as_dual(x: f64, y: f64, z: f64) -> bool {
+        let f = |x: &Vec<Dual>| -> Dual {
+            (x[0] * x[1] - x[2] / (x[0] * x[0] + 1.0) + 2.0 * x[1] - (-x[2]) / 3.0).into()
+        };
+        fn g<'t>(x: &[Var<'t>]) -> Var<'t> {
+            x[0] * x[1] - x[2] / (x[0] * x[0] + 1.0) + 2.0 * x[1] - (-x[2]) / 3.0
+        }
+        let xs = vec![Dual::from(x), Dual::from(y), Dual::from(z)];
+        let expected = gradient!(f(&xs), xs);
+        let (val, actual) = grad(g, &[x, y, z]);
+        f(&xs).val().approx_eq(val, (EPSILON, ULP))
+            && expected
+                .iter()
+                .zip(actual.iter())
+                .all(|(e, a)| e.approx_eq(*a, (EPSILON, ULP)))
+    }
+
+    #[quickcheck]
+    fn math_functions_are_same_as_dual(x: f64, y: f64) -> bool {
+        let f = |x: &Vec<Dual>| -> Dual {
+            (x[0].sin() * x[1].cos() + (x[0] * x[0] + 1.0).sqrt() - x[1].exp()
+                + (x[1] * x[1] + 1.0).ln()
+                + (x[0] * x[0] + 1.0).powf(1.5))
+            .into()
+        };
+        fn g<'t>(x: &[Var<'t>]) -> Var<'t> {
+            x[0].sin() * x[1].cos() + (x[0] * x[0] + 1.0).sqrt() - x[1].exp()
+                + (x[1] * x[1] + 1.0).ln()
+                + (x[0] * x[0] + 1.0).powf(1.5)
+        }
+        let xs = vec![Dual::from(x), Dual::from(y)];
+        let expected = gradient!(f(&xs), xs);
+        let (_, actual) = grad(g, &[x, y]);
+        expected
+            .iter()
+            .zip(actual.iter())
+            .all(|(e, a)| e.approx_eq(*a, (EPSILON, ULP)))
+    }
+
+    #[quickcheck]
+    fn powf_var_test(x: f64, deg: f64) -> bool {
+        let x = x * x + 1.0;
+        let powf = |x: Dual, deg: Dual| -> Dual { x.powf(deg).into() };
+        let (xd, degd) = (Dual::from(x), Dual::from(deg));
+        let (_, actual) = grad(|v| v[0].powf(v[1]), &[x, deg]);
+        actual[0].approx_eq(derivative!(powf(xd, degd), xd), (EPSILON, ULP))
+            && actual[1].approx_eq(derivative!(powf(xd, degd), degd), (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn assign_ops_property(x: f64, y: f64) -> bool {
+        let (_, expected) = grad(|v| (v[0] + v[1]) * v[0] - v[1], &[x, y]);
+        let (_, actual) = grad(
+            |v| {
+                let mut z = v[0];
+                z += v[1];
+                z *= v[0];
+                z -= v[1];
+                z
+            },
+            &[x, y],
+        );
+        expected == actual
+    }
+}
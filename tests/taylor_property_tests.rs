@@ -0,0 +1,126 @@
+#[cfg(test)]
+extern crate quickcheck;
+#[cfg(test)]
+#[macro_use(quickcheck)]
+extern crate quickcheck_macros;
+
+#[cfg(test)]
+use float_cmp::{ApproxEq, F64Margin};
+
+use rad::{derivative, derivatives, Dual, HyperDual, Taylor};
+
+const EPSILON: f64 = f64::EPSILON * 1000000.0;
+const ULP: i64 = 5;
+
+/// Compare derivatives with relative tolerance, higher
+/// order derivatives of series grow fast.
+fn all_close(actual: &[f64], expected: &[f64]) -> bool {
+    actual.len() == expected.len()
+        && actual
+            .iter()
+            .zip(expected.iter())
+            .all(|(a, e)| (a - e).abs() <= 1e-9 * (1.0 + e.abs()))
+}
+
+#[cfg(test)]
+mod tests_taylor {
+    use super::*;
+
+    #[quickcheck]
+    fn keeps_value(val: f64) -> bool {
+        Taylor::<3>::from(val).val().approx_eq(val, F64Margin::default())
+    }
+
+    #[quickcheck]
+    fn polynomial_test(x: f64) -> bool {
+        let x = x % 10.0;
+        let f = |x: Taylor<4>| -> Taylor<4> { x * x * x - 2.0 * x + 1.0 };
+        let ders = f(Taylor::variable(x)).derivatives();
+        all_close(&ders, &[x * x * x - 2.0 * x + 1.0, 3.0 * x * x - 2.0, 6.0 * x, 6.0, 0.0])
+    }
+
+    #[quickcheck]
+    fn sin_cos_test(x: f64) -> bool {
+        let x = x % 10.0;
+        let (s, c) = (x.sin(), x.cos());
+        all_close(&Taylor::<4>::variable(x).sin().derivatives(), &[s, c, -s, -c, s])
+            && all_close(&Taylor::<4>::variable(x).cos().derivatives(), &[c, -s, -c, s, c])
+    }
+
+    #[quickcheck]
+    fn exp_ln_test(x: f64) -> bool {
+        let x = x.abs() % 10.0 + 1.0;
+        let e = x.exp();
+        all_close(&Taylor::<3>::variable(x).exp().derivatives(), &[e, e, e, e])
+            && all_close(
+                &Taylor::<3>::variable(x).ln().derivatives(),
+                &[x.ln(), 1.0 / x, -1.0 / (x * x), 2.0 / (x * x * x)],
+            )
+    }
+
+    #[quickcheck]
+    fn sqrt_div_test(x: f64) -> bool {
+        let x = x.abs() % 10.0 + 1.0;
+        let s = x.sqrt();
+        all_close(
+            &Taylor::<2>::variable(x).sqrt().derivatives(),
+            &[s, 0.5 / s, -0.25 / (x * s)],
+        ) && all_close(
+            &(1.0 / Taylor::<2>::variable(x)).derivatives(),
+            &[1.0 / x, -1.0 / (x * x), 2.0 / (x * x * x)],
+        )
+    }
+
+    #[quickcheck]
+    fn powf_test(x: f64, deg: f64) -> bool {
+        let x = x.abs() % 10.0 + 1.0;
+        let deg = deg % 5.0;
+        let expected = [
+            x.powf(deg),
+            deg * x.powf(deg - 1.0),
+            deg * (deg - 1.0) * x.powf(deg - 2.0),
+            deg * (deg - 1.0) * (deg - 2.0) * x.powf(deg - 3.0),
+        ];
+        all_close(&Taylor::<3>::variable(x).powf(deg).derivatives(), &expected)
+    }
+
+    #[test]
+    fn powf_at_zero_test() {
+        let x = Taylor::<3>::variable(0.0);
+        assert_eq!(x.powf(0.0).derivatives(), vec![1.0, 0.0, 0.0, 0.0]);
+        assert_eq!(x.powf(2.0).derivatives(), vec![0.0, 0.0, 2.0, 0.0]);
+        assert_eq!(x.powf(2.0).coeff(2), 1.0);
+        assert_eq!(x.powf(3.0).derivatives(), vec![0.0, 0.0, 0.0, 6.0]);
+        assert_eq!(x.powf(7.0).derivatives(), vec![0.0; 4]);
+        assert_eq!((x * 2.0 + x * x).powf(2.0).derivatives(), vec![0.0, 0.0, 8.0, 24.0]);
+    }
+
+    #[quickcheck]
+    fn powf_series_test(x: f64) -> bool {
+        // x^x = exp(x ln x)
+        let x = x.abs() % 5.0 + 1.0;
+        let v = Taylor::<2>::variable(x);
+        let p = x.powf(x);
+        let l = x.ln() + 1.0;
+        all_close(&v.powf(v).derivatives(), &[p, p * l, p * (l * l + 1.0 / x)])
+    }
+
+    #[quickcheck]
+    fn first_and_second_derivatives_are_same_as_dual(x: f64) -> bool {
+        let x = x % 10.0;
+        let f = |x: Dual| -> Dual { (x.sin() * x.exp() / (x * x + 1.0)).into() };
+        let g = |x: HyperDual| -> HyperDual { (x.sin() * x.exp() / (x * x + 1.0)).into() };
+        let h = |x: Taylor<2>| -> Taylor<2> { x.sin() * x.exp() / (x * x + 1.0) };
+        let ders = h(Taylor::variable(x)).derivatives();
+        let xd = Dual::from(x);
+        derivative!(f(xd), xd).approx_eq(ders[1], (EPSILON, ULP))
+            && g(HyperDual::seeded(x, 1.0, 1.0)).der12().approx_eq(ders[2], (EPSILON, ULP))
+    }
+
+    #[quickcheck]
+    fn derivatives_macro_test(x: Dual) -> bool {
+        let f = |x: Taylor<3>| -> Taylor<3> { x * x * x };
+        let ders = derivatives!(f(x), x, 3);
+        all_close(&ders, &[x.val().powi(3), 3.0 * x.val().powi(2), 6.0 * x.val(), 6.0])
+    }
+}
@@ -1,30 +1,35 @@
 use crate::{
     dual::Dual,
+    dual_n::DualN,
+    hyper_dual::HyperDual,
+    real::Real,
     xpr::expression::{Xpr, XprWrapper},
 };
 use std::cmp::{Ordering, PartialEq};
 
-impl PartialEq for Dual {
-    fn eq(&self, other: &Dual) -> bool {
+impl<T: Real> PartialEq for Dual<T> {
+    fn eq(&self, other: &Dual<T>) -> bool {
         self.val.eq(&other.val)
     }
 }
 
-impl<T> PartialEq<XprWrapper<T>> for Dual
+impl<T, E> PartialEq<XprWrapper<E>> for Dual<T>
 where
-    T: Xpr,
+    T: Real,
+    E: Xpr<Dual<T>>,
 {
-    fn eq(&self, other: &XprWrapper<T>) -> bool {
-        self.eq(&Dual::from(*other))
+    fn eq(&self, other: &XprWrapper<E>) -> bool {
+        self.eq(&Dual::from(other.clone()))
     }
 }
 
-impl<T> PartialEq<Dual> for XprWrapper<T>
+impl<T, E> PartialEq<Dual<T>> for XprWrapper<E>
 where
-    T: Xpr,
+    T: Real,
+    E: Xpr<Dual<T>>,
 {
-    fn eq(&self, other: &Dual) -> bool {
-        Dual::from(*self).eq(other)
+    fn eq(&self, other: &Dual<T>) -> bool {
+        Dual::from(self.clone()).eq(other)
     }
 }
 
@@ -34,31 +39,33 @@ where
     R: Xpr,
 {
     fn eq(&self, other: &XprWrapper<R>) -> bool {
-        self.eq(&Dual::from(*other))
+        self.eq(&Dual::from(other.clone()))
     }
 }
 
-impl PartialOrd for Dual {
-    fn partial_cmp(&self, other: &Dual) -> Option<Ordering> {
+impl<T: Real> PartialOrd for Dual<T> {
+    fn partial_cmp(&self, other: &Dual<T>) -> Option<Ordering> {
         self.val.partial_cmp(&other.val)
     }
 }
 
-impl<T> PartialOrd<XprWrapper<T>> for Dual
+impl<T, E> PartialOrd<XprWrapper<E>> for Dual<T>
 where
-    T: Xpr,
+    T: Real,
+    E: Xpr<Dual<T>>,
 {
-    fn partial_cmp(&self, other: &XprWrapper<T>) -> Option<Ordering> {
-        self.partial_cmp(&Dual::from(*other))
+    fn partial_cmp(&self, other: &XprWrapper<E>) -> Option<Ordering> {
+        self.partial_cmp(&Dual::from(other.clone()))
     }
 }
 
-impl<T> PartialOrd<Dual> for XprWrapper<T>
+impl<T, E> PartialOrd<Dual<T>> for XprWrapper<E>
 where
-    T: Xpr,
+    T: Real,
+    E: Xpr<Dual<T>>,
 {
-    fn partial_cmp(&self, other: &Dual) -> Option<Ordering> {
-        Dual::from(*self).partial_cmp(other)
+    fn partial_cmp(&self, other: &Dual<T>) -> Option<Ordering> {
+        Dual::from(self.clone()).partial_cmp(other)
     }
 }
 
@@ -68,6 +75,66 @@ where
     R: Xpr,
 {
     fn partial_cmp(&self, other: &XprWrapper<R>) -> Option<Ordering> {
-        self.partial_cmp(&Dual::from(*other))
+        self.partial_cmp(&Dual::from(other.clone()))
+    }
+}
+
+impl<const N: usize> PartialEq for DualN<N> {
+    fn eq(&self, other: &DualN<N>) -> bool {
+        self.val.eq(&other.val)
+    }
+}
+
+impl<T, const N: usize> PartialEq<XprWrapper<T>> for DualN<N>
+where
+    T: Xpr<DualN<N>>,
+{
+    fn eq(&self, other: &XprWrapper<T>) -> bool {
+        self.eq(&DualN::from(other.clone()))
+    }
+}
+
+impl<const N: usize> PartialOrd for DualN<N> {
+    fn partial_cmp(&self, other: &DualN<N>) -> Option<Ordering> {
+        self.val.partial_cmp(&other.val)
+    }
+}
+
+impl<T, const N: usize> PartialOrd<XprWrapper<T>> for DualN<N>
+where
+    T: Xpr<DualN<N>>,
+{
+    fn partial_cmp(&self, other: &XprWrapper<T>) -> Option<Ordering> {
+        self.partial_cmp(&DualN::from(other.clone()))
+    }
+}
+
+impl PartialEq for HyperDual {
+    fn eq(&self, other: &HyperDual) -> bool {
+        self.val.eq(&other.val)
+    }
+}
+
+impl<T> PartialEq<XprWrapper<T>> for HyperDual
+where
+    T: Xpr<HyperDual>,
+{
+    fn eq(&self, other: &XprWrapper<T>) -> bool {
+        self.eq(&HyperDual::from(other.clone()))
+    }
+}
+
+impl PartialOrd for HyperDual {
+    fn partial_cmp(&self, other: &HyperDual) -> Option<Ordering> {
+        self.val.partial_cmp(&other.val)
+    }
+}
+
+impl<T> PartialOrd<XprWrapper<T>> for HyperDual
+where
+    T: Xpr<HyperDual>,
+{
+    fn partial_cmp(&self, other: &XprWrapper<T>) -> Option<Ordering> {
+        self.partial_cmp(&HyperDual::from(other.clone()))
     }
 }
@@ -0,0 +1,546 @@
+use crate::{dual::Dual, real::Real, xpr::Operation};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::atomic::{self, AtomicUsize};
+
+/// Recorded instruction, index of instruction is index of its result.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Instr {
+    /// Input with given index.
+    Input(usize),
+    /// Constant, kept as bits so instructions can be hashed.
+    Const(u64),
+    /// Operation with one operand.
+    Unary(Operation, usize),
+    /// Operation with two operands.
+    Binary(Operation, usize, usize),
+    /// Weight of left part of min or max function, operands
+    /// are left and right parts and chosen one.
+    Select(usize, usize, usize),
+}
+
+/// Computation recorded by tracing. Equal instructions are
+/// recorded once, so common subexpressions share their result.
+#[derive(Debug, Default)]
+struct Graph {
+    /// 'id' - identifier of trace call which owns graph.
+    id: usize,
+    /// 'instrs' - recorded instructions in order of evaluation.
+    instrs: Vec<Instr>,
+    /// 'index' - position of each recorded instruction.
+    index: HashMap<Instr, usize>,
+}
+
+impl Graph {
+    fn push(&mut self, instr: Instr) -> usize {
+        let instrs = &mut self.instrs;
+        *self.index.entry(instr).or_insert_with(|| {
+            instrs.push(instr);
+            instrs.len() - 1
+        })
+    }
+
+    fn constant(&self, idx: usize) -> Option<f64> {
+        match self.instrs[idx] {
+            Instr::Const(bits) => Some(f64::from_bits(bits)),
+            _ => None,
+        }
+    }
+}
+
+thread_local! {
+    /// Graphs of running traces, the innermost trace is the last one.
+    static TRACES: RefCell<Vec<Graph>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Identifier of next trace call.
+static NEXT_TRACE: AtomicUsize = AtomicUsize::new(1);
+
+/// Run 'f' on graph of the innermost running trace.
+///
+/// # Arguments
+///
+/// 'operands' - scalars which must belong to this graph.
+/// 'f' - function to run.
+fn with_graph<R>(operands: &[Sym], f: impl FnOnce(&mut Graph) -> R) -> R {
+    TRACES.with(|traces| {
+        let mut traces = traces.borrow_mut();
+        let graph = traces.last_mut().expect("Sym is used outside of trace");
+        assert!(
+            operands.iter().all(|s| s.graph == graph.id),
+            "Sym of another trace is used"
+        );
+        f(graph)
+    })
+}
+
+/// Graph of one trace call, it records operations until guard
+/// is dropped, then graph of enclosing trace is restored.
+struct TraceGuard;
+
+impl TraceGuard {
+    fn new() -> Self {
+        let id = NEXT_TRACE.fetch_add(1, atomic::Ordering::Relaxed);
+        TRACES.with(|traces| traces.borrow_mut().push(Graph { id, ..Graph::default() }));
+        TraceGuard
+    }
+
+    fn finish(self) -> Graph {
+        with_graph(&[], std::mem::take)
+    }
+}
+
+impl Drop for TraceGuard {
+    fn drop(&mut self) {
+        TRACES.with(|traces| traces.borrow_mut().pop());
+    }
+}
+
+/// Value of operation with one operand.
+fn apply_unary(op: Operation, a: f64) -> f64 {
+    match op {
+        Operation::Neg => -a,
+        Operation::Powi(n) => a.powi(n),
+        Operation::Sin => a.sin(),
+        Operation::Cos => a.cos(),
+        Operation::Sqrt => a.sqrt(),
+        Operation::Ln => a.ln(),
+        Operation::Exp => a.exp(),
+        Operation::Tan => a.tan(),
+        Operation::Asin => a.asin(),
+        Operation::Acos => a.acos(),
+        Operation::Atan => a.atan(),
+        Operation::Sinh => a.sinh(),
+        Operation::Cosh => a.cosh(),
+        Operation::Tanh => a.tanh(),
+        Operation::Asinh => a.asinh(),
+        Operation::Acosh => a.acosh(),
+        Operation::Atanh => a.atanh(),
+        Operation::Exp2 => a.exp2(),
+        Operation::ExpM1 => a.exp_m1(),
+        Operation::Ln1p => a.ln_1p(),
+        Operation::Log2 => a.log2(),
+        Operation::Log10 => a.log10(),
+        Operation::Cbrt => a.cbrt(),
+        Operation::Abs => a.abs(),
+        Operation::Signum => a.signum(),
+        Operation::Recip => a.recip(),
+        op => unreachable!("{} is not traced", op.name()),
+    }
+}
+
+/// Weight of left part of min or max function, same as 'Real::select'.
+fn select_weight(l: f64, r: f64, val: f64) -> f64 {
+    if l == r {
+        0.5
+    } else if val == l {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// Value of operation with two operands.
+fn apply_binary(op: Operation, a: f64, b: f64) -> f64 {
+    match op {
+        Operation::Add => a + b,
+        Operation::Sub => a - b,
+        Operation::Mul => a * b,
+        Operation::Div => a / b,
+        Operation::Powf => a.powf(b),
+        Operation::Atan2 => a.atan2(b),
+        Operation::Hypot => a.hypot(b),
+        Operation::Min => a.min(b),
+        Operation::Max => a.max(b),
+        Operation::Log => a.log(b),
+        Operation::Copysign => a.copysign(b),
+        op => unreachable!("{} is not traced", op.name()),
+    }
+}
+
+/// Scalar which records operations made on it. 'Dual<Sym>' is evaluated
+/// by expression templates as usual, while computation of its value and
+/// derivative is recorded. Comparisons use values at the traced point,
+/// so branches of traced function are taken as they are at this point,
+/// while min and max functions are recorded with their branches. Scalar
+/// belongs to trace call which created it, it panics in other traces.
+#[derive(Clone, Copy, Debug)]
+pub struct Sym {
+    /// 'idx' - index of instruction which computes the scalar.
+    idx: usize,
+    /// 'val' - value at the traced point.
+    val: f64,
+    /// 'graph' - identifier of trace call which recorded the scalar.
+    graph: usize,
+}
+
+impl Sym {
+    fn record(instr: Instr, val: f64) -> Self {
+        with_graph(&[], |graph| Self {
+            idx: graph.push(instr),
+            val,
+            graph: graph.id,
+        })
+    }
+
+    fn constant(val: f64) -> Self {
+        Self::record(Instr::Const(val.to_bits()), val)
+    }
+
+    fn as_constant(self) -> Option<f64> {
+        with_graph(&[self], |graph| graph.constant(self.idx))
+    }
+
+    /// Value at the traced point.
+    pub fn val(&self) -> f64 {
+        self.val
+    }
+
+    fn unary(self, op: Operation) -> Self {
+        let val = apply_unary(op, self.val);
+        match self.as_constant() {
+            Some(_) => Self::constant(val),
+            None => Self::record(Instr::Unary(op, self.idx), val),
+        }
+    }
+
+    /// Record binary operation, constant operands are folded and
+    /// additive and multiplicative identities are skipped. Zero
+    /// constant absorbs products and quotients whatever other
+    /// operand is, so '0 * inf' and '0 / 0' give 0 here.
+    fn binary(self, op: Operation, other: Self) -> Self {
+        let val = apply_binary(op, self.val, other.val);
+        let (l, r) = (self.as_constant(), other.as_constant());
+        match (op, l, r) {
+            (_, Some(_), Some(_)) => Self::constant(val),
+            (Operation::Add, Some(0.0), _) => other,
+            (Operation::Add, _, Some(0.0)) | (Operation::Sub, _, Some(0.0)) => self,
+            (Operation::Sub, Some(0.0), _) => other.unary(Operation::Neg),
+            (Operation::Mul, Some(0.0), _) | (Operation::Mul, _, Some(0.0)) => Self::constant(0.0),
+            (Operation::Mul, Some(1.0), _) => other,
+            (Operation::Mul, _, Some(1.0)) | (Operation::Div, _, Some(1.0)) => self,
+            (Operation::Div, Some(0.0), _) => Self::constant(0.0),
+            _ => Self::record(Instr::Binary(op, self.idx, other.idx), val),
+        }
+    }
+}
+
+impl PartialEq for Sym {
+    fn eq(&self, other: &Self) -> bool {
+        self.val == other.val
+    }
+}
+
+impl PartialOrd for Sym {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.val.partial_cmp(&other.val)
+    }
+}
+
+/// Implement Real methods of Sym by recording operation.
+macro_rules! impl_sym_fn(
+    (unary $($f: ident => $Op: ident),*; binary $($g: ident => $BinOp: ident),*) => {
+        $(fn $f(self) -> Self { self.unary(Operation::$Op) })*
+        $(fn $g(self, other: Self) -> Self { self.binary(Operation::$BinOp, other) })*
+    }
+);
+
+impl Real for Sym {
+    fn zero() -> Self {
+        Self::constant(0.0)
+    }
+
+    fn one() -> Self {
+        Self::constant(1.0)
+    }
+
+    fn from_f64(val: f64) -> Self {
+        Self::constant(val)
+    }
+
+    fn select(l: Self, r: Self, val: Self) -> (Self, Self) {
+        let weight = select_weight(l.val, r.val, val.val);
+        let left = match with_graph(&[l, r, val], |graph| (graph.constant(l.idx), graph.constant(r.idx))) {
+            (Some(_), Some(_)) => Self::constant(weight),
+            _ => Self::record(Instr::Select(l.idx, r.idx, val.idx), weight),
+        };
+        (left, Self::one().sub(left))
+    }
+
+    fn powi(self, n: i32) -> Self {
+        match n {
+            0 => Self::one(),
+            1 => self,
+            n => self.unary(Operation::Powi(n)),
+        }
+    }
+
+    impl_sym_fn!(
+        unary
+        neg => Neg, sin => Sin, cos => Cos, sqrt => Sqrt, ln => Ln, exp => Exp, tan => Tan,
+        asin => Asin, acos => Acos, atan => Atan, sinh => Sinh, cosh => Cosh, tanh => Tanh,
+        asinh => Asinh, acosh => Acosh, atanh => Atanh, exp2 => Exp2, exp_m1 => ExpM1,
+        ln_1p => Ln1p, log2 => Log2, log10 => Log10, cbrt => Cbrt, abs => Abs,
+        signum => Signum, recip => Recip;
+        binary
+        add => Add, sub => Sub, mul => Mul, div => Div, powf => Powf, atan2 => Atan2,
+        hypot => Hypot, min => Min, max => Max, log => Log, copysign => Copysign
+    );
+}
+
+/// Straight line program which computes value and gradient
+/// of traced function. Common subexpressions are computed
+/// once and results which don't affect outputs are dropped.
+#[derive(Clone, Debug)]
+pub struct Program {
+    /// 'inputs' - count of function arguments.
+    inputs: usize,
+    /// 'instrs' - recorded instructions.
+    instrs: Vec<Instr>,
+    /// 'value' - instruction which computes value.
+    value: usize,
+    /// 'gradient' - instructions which compute partial derivatives.
+    gradient: Vec<usize>,
+}
+
+/// Target language of emitted code.
+#[derive(Clone, Copy, PartialEq)]
+enum Language {
+    Rust,
+    C,
+}
+
+impl Language {
+    fn literal(self, val: f64) -> String {
+        let text = match (self, val) {
+            (Self::Rust, val) if val.is_nan() => "f64::NAN".to_string(),
+            (Self::Rust, val) if val.is_infinite() => "f64::INFINITY".to_string(),
+            (Self::C, val) if val.is_nan() => "NAN".to_string(),
+            (Self::C, val) if val.is_infinite() => "INFINITY".to_string(),
+            (Self::Rust, val) => format!("{:?}_f64", val.abs()),
+            (Self::C, val) => format!("{:?}", val.abs()),
+        };
+        if val.is_sign_negative() && !val.is_nan() {
+            format!("(-{})", text)
+        } else {
+            text
+        }
+    }
+
+    fn unary(self, op: Operation, a: &str) -> String {
+        match (self, op) {
+            (_, Operation::Neg) => format!("-{}", a),
+            (Self::Rust, Operation::Powi(n)) => format!("{}.powi({})", a, n),
+            (Self::Rust, op) => format!("{}.{}()", a, op.name()),
+            (Self::C, Operation::Powi(n)) => format!("pow({}, {})", a, n),
+            (Self::C, Operation::Recip) => format!("1.0 / {}", a),
+            (Self::C, Operation::Signum) => format!("copysign(1.0, {})", a),
+            (Self::C, Operation::Ln) => format!("log({})", a),
+            (Self::C, Operation::ExpM1) => format!("expm1({})", a),
+            (Self::C, Operation::Ln1p) => format!("log1p({})", a),
+            (Self::C, Operation::Abs) => format!("fabs({})", a),
+            (Self::C, op) => format!("{}({})", op.name(), a),
+        }
+    }
+
+    fn binary(self, op: Operation, a: &str, b: &str) -> String {
+        match (self, op) {
+            (_, Operation::Add) => format!("{} + {}", a, b),
+            (_, Operation::Sub) => format!("{} - {}", a, b),
+            (_, Operation::Mul) => format!("{} * {}", a, b),
+            (_, Operation::Div) => format!("{} / {}", a, b),
+            (Self::Rust, op) => format!("{}.{}({})", a, op.name(), b),
+            (Self::C, Operation::Powf) => format!("pow({}, {})", a, b),
+            (Self::C, Operation::Min) => format!("fmin({}, {})", a, b),
+            (Self::C, Operation::Max) => format!("fmax({}, {})", a, b),
+            (Self::C, Operation::Log) => format!("log({}) / log({})", a, b),
+            (Self::C, op) => format!("{}({}, {})", op.name(), a, b),
+        }
+    }
+
+    fn select(self, l: &str, r: &str, val: &str) -> String {
+        let (half, one, zero) = (self.literal(0.5), self.literal(1.0), self.literal(0.0));
+        match self {
+            Self::Rust => format!(
+                "if {} == {} {{ {} }} else if {} == {} {{ {} }} else {{ {} }}",
+                l, r, half, val, l, one, zero
+            ),
+            Self::C => format!("{} == {} ? {} : {} == {} ? {} : {}", l, r, half, val, l, one, zero),
+        }
+    }
+}
+
+impl Program {
+    /// Count of function arguments.
+    pub fn inputs(&self) -> usize {
+        self.inputs
+    }
+
+    /// Count of operations left after common subexpression
+    /// elimination and removal of unused results.
+    pub fn operations(&self) -> usize {
+        self.live()
+            .iter()
+            .zip(self.instrs.iter())
+            .filter(|(live, instr)| **live && matches!(instr, Instr::Unary(..) | Instr::Binary(..) | Instr::Select(..)))
+            .count()
+    }
+
+    /// Evaluate program, result is value and gradient of traced function.
+    ///
+    /// # Arguments
+    ///
+    /// 'x' - point of evaluation.
+    pub fn eval(&self, x: &[f64]) -> (f64, Vec<f64>) {
+        assert_eq!(x.len(), self.inputs, "point and program have different count of inputs");
+        let mut vals = Vec::with_capacity(self.instrs.len());
+        for instr in self.instrs.iter() {
+            let val = match *instr {
+                Instr::Input(i) => x[i],
+                Instr::Const(bits) => f64::from_bits(bits),
+                Instr::Unary(op, a) => apply_unary(op, vals[a]),
+                Instr::Binary(op, a, b) => apply_binary(op, vals[a], vals[b]),
+                Instr::Select(l, r, val) => select_weight(vals[l], vals[r], vals[val]),
+            };
+            vals.push(val);
+        }
+        (vals[self.value], self.gradient.iter().map(|&i| vals[i]).collect())
+    }
+
+    /// Emit Rust function 'fn name(x: &[f64; N]) -> (f64, [f64; N])'
+    /// which returns value and gradient.
+    ///
+    /// # Arguments
+    ///
+    /// 'name' - name of emitted function.
+    pub fn to_rust(&self, name: &str) -> String {
+        let (body, operands) = self.statements(Language::Rust, "let");
+        format!(
+            "pub fn {}(x: &[f64; {}]) -> (f64, [f64; {}]) {{\n{}    ({}, [{}])\n}}\n",
+            name,
+            self.inputs,
+            self.inputs,
+            body,
+            operands[self.value],
+            self.gradient.iter().map(|&i| operands[i].as_str()).collect::<Vec<_>>().join(", ")
+        )
+    }
+
+    /// Emit C function 'double name(const double *x, double *grad)'
+    /// which returns value and writes gradient into 'grad'.
+    ///
+    /// # Arguments
+    ///
+    /// 'name' - name of emitted function.
+    pub fn to_c(&self, name: &str) -> String {
+        let (mut body, operands) = self.statements(Language::C, "const double");
+        for (i, &g) in self.gradient.iter().enumerate() {
+            body += &format!("    grad[{}] = {};\n", i, operands[g]);
+        }
+        format!(
+            "#include <math.h>\n\ndouble {}(const double *x, double *grad) {{\n{}    return {};\n}}\n",
+            name, body, operands[self.value]
+        )
+    }
+
+    /// Instructions which outputs depend on.
+    fn live(&self) -> Vec<bool> {
+        let mut live = vec![false; self.instrs.len()];
+        live[self.value] = true;
+        for &i in self.gradient.iter() {
+            live[i] = true;
+        }
+        for i in (0..self.instrs.len()).rev() {
+            if !live[i] {
+                continue;
+            }
+            match self.instrs[i] {
+                Instr::Unary(_, a) => live[a] = true,
+                Instr::Binary(_, a, b) => {
+                    live[a] = true;
+                    live[b] = true;
+                }
+                Instr::Select(l, r, val) => {
+                    live[l] = true;
+                    live[r] = true;
+                    live[val] = true;
+                }
+                _ => {}
+            }
+        }
+        live
+    }
+
+    /// Declarations of temporaries and operand text of each instruction.
+    fn statements(&self, lang: Language, decl: &str) -> (String, Vec<String>) {
+        let live = self.live();
+        let mut body = String::new();
+        let mut operands = Vec::with_capacity(self.instrs.len());
+        for (instr, live) in self.instrs.iter().zip(live) {
+            let code = match *instr {
+                Instr::Input(i) => {
+                    operands.push(format!("x[{}]", i));
+                    continue;
+                }
+                Instr::Const(bits) => {
+                    operands.push(lang.literal(f64::from_bits(bits)));
+                    continue;
+                }
+                _ if !live => {
+                    operands.push(String::new());
+                    continue;
+                }
+                Instr::Unary(op, a) => lang.unary(op, &operands[a]),
+                Instr::Binary(op, a, b) => lang.binary(op, &operands[a], &operands[b]),
+                Instr::Select(l, r, val) => lang.select(&operands[l], &operands[r], &operands[val]),
+            };
+            let temp = format!("t{}", body.lines().count());
+            body += &format!("    {} {} = {};\n", decl, temp, code);
+            operands.push(temp);
+        }
+        (body, operands)
+    }
+}
+
+/// Trace function at point and build program computing
+/// its value and gradient. Function is evaluated once per
+/// argument, each time with derivative seeded for one of them.
+///
+/// # Arguments
+///
+/// 'f' - function to trace.
+/// 'x' - point of tracing, branches of function are taken as at this point.
+///
+/// Zero constants absorb products and quotients, so where function gives
+/// NaN from '0 * inf' or '0 / 0' the program may give 0. Traces may be
+/// nested, but scalars of one trace can't be used in another.
+pub fn trace<F, Y>(f: F, x: &[f64]) -> Program
+where
+    F: Fn(&[Dual<Sym>]) -> Y,
+    Y: Into<Dual<Sym>>,
+{
+    let guard = TraceGuard::new();
+    let inputs: Vec<Sym> = x
+        .iter()
+        .enumerate()
+        .map(|(i, &val)| Sym::record(Instr::Input(i), val))
+        .collect();
+    let value = f(&inputs.iter().map(|&s| Dual::new(s)).collect::<Vec<_>>()).into().val;
+    let mut gradient = Vec::with_capacity(x.len());
+    for i in 0..x.len() {
+        let args: Vec<Dual<Sym>> = inputs
+            .iter()
+            .enumerate()
+            .map(|(j, &s)| if i == j { Dual::new(s).seeded() } else { Dual::new(s) })
+            .collect();
+        gradient.push(f(&args).into().der.idx);
+    }
+    let graph = guard.finish();
+    Program {
+        inputs: x.len(),
+        instrs: graph.instrs,
+        value: value.idx,
+        gradient,
+    }
+}
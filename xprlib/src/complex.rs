@@ -0,0 +1,258 @@
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Step of complex-step differentiation. There is no subtractive
+/// cancellation, so step may be far below square root of epsilon.
+pub const COMPLEX_STEP: f64 = 1e-20;
+
+/// Complex number used for complex-step differentiation. For
+/// real analytic function 'f' derivative is 'Im f(x + ih) / h'.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Complex {
+    /// Real part.
+    pub re: f64,
+    /// Imaginary part.
+    pub im: f64,
+}
+
+impl Complex {
+    /// Create new complex number.
+    ///
+    /// # Arguments
+    ///
+    /// 're' - real part.
+    /// 'im' - imaginary part.
+    pub fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    /// Real number 'x' perturbed by complex step.
+    ///
+    /// # Arguments
+    ///
+    /// 'x' - real part of variable.
+    pub fn variable(x: f64) -> Self {
+        Self::new(x, COMPLEX_STEP)
+    }
+
+    /// value of function, real part
+    pub fn val(&self) -> f64 {
+        self.re
+    }
+
+    /// derivative of function w.r.t. perturbed variable
+    pub fn der(&self) -> f64 {
+        self.im / COMPLEX_STEP
+    }
+
+    /// Absolute value.
+    pub fn norm(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// Sine of complex number.
+    pub fn sin(self) -> Self {
+        Self::new(self.re.sin() * self.im.cosh(), self.re.cos() * self.im.sinh())
+    }
+
+    /// Cosine of complex number.
+    pub fn cos(self) -> Self {
+        Self::new(self.re.cos() * self.im.cosh(), -self.re.sin() * self.im.sinh())
+    }
+
+    /// Principal square root of complex number.
+    pub fn sqrt(self) -> Self {
+        if self.re == 0.0 && self.im == 0.0 {
+            return Self::default();
+        }
+        // avoid cancellation of |z| and re for tiny imaginary part
+        let t = ((self.norm() + self.re.abs()) / 2.0).sqrt();
+        if self.re >= 0.0 {
+            Self::new(t, self.im / (2.0 * t))
+        } else {
+            Self::new(self.im.abs() / (2.0 * t), t.copysign(self.im))
+        }
+    }
+
+    /// Principal natural logarithm of complex number.
+    pub fn ln(self) -> Self {
+        Self::new(self.norm().ln(), self.im.atan2(self.re))
+    }
+
+    /// Exponent of complex number.
+    pub fn exp(self) -> Self {
+        let norm = self.re.exp();
+        Self::new(norm * self.im.cos(), norm * self.im.sin())
+    }
+
+    /// Complex number raised to real or complex power. Negative real
+    /// base perturbed by complex step is raised to real power by first
+    /// order expansion, other bases by principal value.
+    ///
+    /// # Arguments
+    ///
+    /// 'deg' - power.
+    pub fn powf<Deg: Into<Complex>>(self, deg: Deg) -> Self {
+        let deg = deg.into();
+        if deg.im == 0.0 {
+            let d = deg.re;
+            if d.fract() == 0.0 && d.abs() <= i32::MAX as f64 {
+                return self.powi(d as i32);
+            }
+            if self.re > 0.0 {
+                let norm = self.norm().powf(d);
+                let arg = self.im.atan2(self.re) * d;
+                return Self::new(norm * arg.cos(), norm * arg.sin());
+            }
+            // Polar form loses imaginary part of order of step for negative base,
+            // where argument is close to pi, so first order term is used while
+            // imaginary part is a step, i.e. negligible next to real part.
+            if self.im.abs() <= (self.re.abs() * f64::EPSILON).max(COMPLEX_STEP) {
+                return Self::new(self.re.powf(d), d * self.re.powf(d - 1.0) * self.im);
+            }
+        }
+        (deg * self.ln()).exp()
+    }
+
+    /// Complex number raised to integer power.
+    ///
+    /// # Arguments
+    ///
+    /// 'n' - power.
+    pub fn powi(self, n: i32) -> Self {
+        let (mut res, mut base, mut n_abs) = (Self::from(1.0), self, n.unsigned_abs());
+        while n_abs > 0 {
+            if n_abs & 1 == 1 {
+                res *= base;
+            }
+            base = base * base;
+            n_abs >>= 1;
+        }
+        if n < 0 {
+            Self::from(1.0) / res
+        } else {
+            res
+        }
+    }
+}
+
+impl From<f64> for Complex {
+    fn from(re: f64) -> Self {
+        Self::new(re, 0.0)
+    }
+}
+
+impl Neg for Complex {
+    type Output = Complex;
+    fn neg(self) -> Self::Output {
+        Self::new(-self.re, -self.im)
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+    fn add(self, other: Complex) -> Self::Output {
+        Self::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+    fn sub(self, other: Complex) -> Self::Output {
+        Self::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+    fn mul(self, other: Complex) -> Self::Output {
+        Self::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Complex;
+    fn div(self, other: Complex) -> Self::Output {
+        let den = other.re * other.re + other.im * other.im;
+        Self::new(
+            (self.re * other.re + self.im * other.im) / den,
+            (self.im * other.re - self.re * other.im) / den,
+        )
+    }
+}
+
+/// Operations with real numbers, real number is
+/// converted to complex one with zero imaginary part.
+macro_rules! impl_real_op(
+    ($Op: ident, $op: ident, $OpAssign: ident, $op_assign: ident) => {
+        impl $Op<f64> for Complex {
+            type Output = Complex;
+            fn $op(self, other: f64) -> Self::Output {
+                self.$op(Complex::from(other))
+            }
+        }
+
+        impl $Op<Complex> for f64 {
+            type Output = Complex;
+            fn $op(self, other: Complex) -> Self::Output {
+                Complex::from(self).$op(other)
+            }
+        }
+
+        impl $OpAssign for Complex {
+            fn $op_assign(&mut self, other: Complex) {
+                *self = self.$op(other);
+            }
+        }
+
+        impl $OpAssign<f64> for Complex {
+            fn $op_assign(&mut self, other: f64) {
+                *self = self.$op(other);
+            }
+        }
+    }
+);
+
+impl_real_op!(Add, add, AddAssign, add_assign);
+impl_real_op!(Sub, sub, SubAssign, sub_assign);
+impl_real_op!(Mul, mul, MulAssign, mul_assign);
+impl_real_op!(Div, div, DivAssign, div_assign);
+
+/// Value and derivative of scalar function evaluated with complex
+/// step, independent check of results of Dual numbers.
+///
+/// # Arguments
+///
+/// 'f' - function which derivative we want to eval.
+/// 'x' - point at which derivative is evaluated.
+pub fn complex_step_derivative<F>(f: F, x: f64) -> (f64, f64)
+where
+    F: Fn(Complex) -> Complex,
+{
+    let res = f(Complex::variable(x));
+    (res.val(), res.der())
+}
+
+/// Value and gradient of scalar function evaluated with complex
+/// step, one input is perturbed at a time.
+///
+/// # Arguments
+///
+/// 'f' - function which gradient we want to eval.
+/// 'x' - point at which gradient is evaluated.
+pub fn complex_step_gradient<F>(f: F, x: &[f64]) -> (f64, Vec<f64>)
+where
+    F: Fn(&[Complex]) -> Complex,
+{
+    let x: Vec<Complex> = x.iter().map(|x| Complex::from(*x)).collect();
+    let grad = (0..x.len())
+        .map(|i| {
+            let mut perturbed = x.clone();
+            perturbed[i] = Complex::variable(perturbed[i].re);
+            f(&perturbed).der()
+        })
+        .collect();
+    (f(&x).val(), grad)
+}
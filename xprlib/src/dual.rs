@@ -1,65 +1,192 @@
-/// Use this CopyCell to allow eval gradient for immutable duals.
-use toolshed::CopyCell;
+use crate::{real::Real, xpr::expression::Xpr};
 /// To be able apply property tests
 #[cfg(feature = "test-utils")]
 extern crate quickcheck;
 
 /// Dual number representation.
+/// 'T' - type of value and derivative, f64 by default.
 #[derive(Clone, Copy, Debug)]
-pub struct Dual {
+pub struct Dual<T = f64> {
     /// Value of Dual number.
-    pub(crate) val: f64,
-    /// Derivative of Dual number. This field is "mutable",
-    /// since user don't have access to this field from
-    /// his program.
-    pub(crate) der: CopyCell<f64>,
+    pub(crate) val: T,
+    /// Derivative of Dual number.
+    pub(crate) der: T,
 }
 
-impl Dual {
+impl<T: Real> Dual<T> {
     /// Create new Dual numbed form float number.
     ///
     /// # Arguments
     ///
     /// 'val' - value of Dual number.
-    pub fn new(val: f64) -> Self {
+    pub fn new(val: T) -> Self {
         Self {
             val,
-            der: CopyCell::new(0.0),
+            der: T::zero(),
         }
     }
 
+    /// Create new Dual number with known value and derivative.
+    ///
+    /// # Arguments
+    ///
+    /// 'val' - value of Dual number.
+    /// 'der' - derivative of Dual number.
+    pub fn with_derivative(val: T, der: T) -> Self {
+        Self { val, der }
+    }
+
     /// Set derivative to 1.0 value
-    pub fn seed(&self) {
-        self.der.set(1.0)
+    pub fn seed(&mut self) {
+        self.der = T::one()
+    }
+
+    /// Set derivative to given value, e.g. component of direction
+    /// for directional derivative.
+    ///
+    /// # Arguments
+    ///
+    /// 'der' - new derivative.
+    pub fn seed_with(&mut self, der: T) {
+        self.der = der
     }
 
     /// Set derivative to 0.0 value
-    pub fn unseed(&self) {
-        self.der.set(0.0)
+    pub fn unseed(&mut self) {
+        self.der = T::zero()
+    }
+
+    /// Copy of dual variable with derivative set to 1.0 value
+    pub fn seeded(mut self) -> Self {
+        self.seed();
+        self
+    }
+
+    /// Copy of dual variable with derivative set to given value
+    ///
+    /// # Arguments
+    ///
+    /// 'der' - new derivative.
+    pub fn seeded_with(mut self, der: T) -> Self {
+        self.seed_with(der);
+        self
     }
 
     /// derivative of dual variable
-    pub fn der(&self) -> f64 {
-        self.der.get()
+    pub fn der(&self) -> T {
+        self.der
     }
 
     /// value of dual variable
-    pub fn val(&self) -> f64 {
+    pub fn val(&self) -> T {
+        self.val
+    }
+}
+
+/// Number with value and tangent part(s) which can be a target
+/// of expression assignment. Expression nodes are written in terms
+/// of this trait, so they work with any kind of dual number.
+pub trait DualNumber: Xpr<Self> + Copy {
+    /// Type of value and tangent components.
+    type Scalar: Real;
+
+    /// Dual number with zero value and tangent part.
+    fn zero() -> Self;
+
+    /// Value of dual number.
+    fn value(&self) -> Self::Scalar;
+
+    /// Set value of dual number, tangent part is left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// 'val' - new value of dual number.
+    fn set_value(&mut self, val: Self::Scalar);
+
+    /// Apply function to each tangent component.
+    ///
+    /// # Arguments
+    ///
+    /// 'f' - function which maps old tangent component to new one.
+    fn map_der<F: Fn(Self::Scalar) -> Self::Scalar>(&mut self, f: F);
+
+    /// Combine tangent components of this and other number.
+    ///
+    /// # Arguments
+    ///
+    /// 'other' - number which tangent components are second argument of 'f'.
+    /// 'f' - function which maps pair of old tangent components to new one.
+    fn zip_der<F: Fn(Self::Scalar, Self::Scalar) -> Self::Scalar>(&mut self, other: &Self, f: F);
+
+    /// Apply chain rule for scalar function 'g' to this number.
+    ///
+    /// # Arguments
+    ///
+    /// 'val' - value of g at current value.
+    /// 'd1' - first derivative of g at current value.
+    /// 'd2' - second derivative of g at current value, used by
+    /// numbers which carry second order part.
+    fn chain(&mut self, val: Self::Scalar, d1: Self::Scalar, _d2: Self::Scalar) {
+        self.map_der(|der| der.mul(d1));
+        self.set_value(val);
+    }
+
+    /// Apply chain rule for binary scalar function 'g(l, r)', this number
+    /// holds 'l' and 'other' holds 'r'.
+    ///
+    /// # Arguments
+    ///
+    /// 'other' - second argument of g.
+    /// 'val' - value of g at current values.
+    /// 'd1' - partial derivatives of g w.r.t. 'l' and 'r'.
+    /// 'd2' - second partial derivatives of g w.r.t. 'l' twice, 'l' and 'r', 'r' twice.
+    fn chain_bin(
+        &mut self,
+        other: &Self,
+        val: Self::Scalar,
+        d1: (Self::Scalar, Self::Scalar),
+        _d2: (Self::Scalar, Self::Scalar, Self::Scalar),
+    ) {
+        self.zip_der(other, |l, r| l.mul(d1.0).add(r.mul(d1.1)));
+        self.set_value(val);
+    }
+
+    /// Negate value and tangent part.
+    fn negate(&mut self) {
+        self.set_value(self.value().neg());
+        self.map_der(|d| d.neg());
+    }
+}
+
+impl<T: Real> DualNumber for Dual<T> {
+    type Scalar = T;
+
+    fn zero() -> Self {
+        Dual::new(T::zero())
+    }
+
+    fn value(&self) -> T {
         self.val
     }
-    
-    /// negate dual value
-    pub(crate) fn neagate(&mut self) {
-        self.val = -self.val;
-        self.der.set(-self.der.get());
+
+    fn set_value(&mut self, val: T) {
+        self.val = val;
+    }
+
+    fn map_der<F: Fn(T) -> T>(&mut self, f: F) {
+        self.der = f(self.der);
+    }
+
+    fn zip_der<F: Fn(T, T) -> T>(&mut self, other: &Self, f: F) {
+        self.der = f(self.der, other.der);
     }
 }
 
-impl From<f64> for Dual {
-    fn from(val: f64) -> Self {
+impl<T: Real> From<T> for Dual<T> {
+    fn from(val: T) -> Self {
         Self {
             val,
-            der: CopyCell::new(0.0),
+            der: T::zero(),
         }
     }
 }
@@ -81,6 +208,6 @@ impl float_cmp::ApproxEq for Dual {
     /// Check Dual numbers for approximate equal. Compare value and derivative.
     fn approx_eq<M: Into<Self::Margin>>(self, other: Self, margin: M) -> bool {
         let margin = margin.into();
-        self.val.approx_eq(other.val, margin) && self.der.get().approx_eq(other.der.get(), margin)
+        self.val.approx_eq(other.val, margin) && self.der.approx_eq(other.der, margin)
     }
 }
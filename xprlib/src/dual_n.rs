@@ -0,0 +1,130 @@
+use crate::dual::DualNumber;
+/// To be able apply property tests
+#[cfg(feature = "test-utils")]
+extern crate quickcheck;
+
+/// Dual number with 'N' tangent components. When each of 'N'
+/// inputs is seeded in its own direction, the full gradient of
+/// a function comes out of a single evaluation.
+#[derive(Clone, Copy, Debug)]
+pub struct DualN<const N: usize> {
+    /// Value of Dual number.
+    pub(crate) val: f64,
+    /// Tangent components of Dual number.
+    pub(crate) der: [f64; N],
+}
+
+impl<const N: usize> DualN<N> {
+    /// Create new Dual number with zero tangent.
+    ///
+    /// # Arguments
+    ///
+    /// 'val' - value of Dual number.
+    pub fn new(val: f64) -> Self {
+        Self { val, der: [0.0; N] }
+    }
+
+    /// Create independent variable seeded in direction 'i'.
+    ///
+    /// # Arguments
+    ///
+    /// 'val' - value of variable.
+    /// 'i' - index of tangent component set to 1.0.
+    ///
+    /// # Panics
+    ///
+    /// If 'i' is not less than 'N'.
+    pub fn variable(val: f64, i: usize) -> Self {
+        let mut var = Self::new(val);
+        var.der[i] = 1.0;
+        var
+    }
+
+    /// Create 'N' independent variables, i-th variable
+    /// is seeded in i-th direction.
+    ///
+    /// # Arguments
+    ///
+    /// 'vals' - values of variables.
+    pub fn variables(vals: [f64; N]) -> [Self; N] {
+        let mut vars = [Self::new(0.0); N];
+        for (i, (var, val)) in vars.iter_mut().zip(vals.iter()).enumerate() {
+            *var = Self::variable(*val, i);
+        }
+        vars
+    }
+
+    /// value of dual variable
+    pub fn val(&self) -> f64 {
+        self.val
+    }
+
+    /// derivative of dual variable in direction 'i'
+    pub fn der(&self, i: usize) -> f64 {
+        self.der[i]
+    }
+
+    /// all tangent components, gradient w.r.t. seeded variables
+    pub fn grad(&self) -> [f64; N] {
+        self.der
+    }
+}
+
+impl<const N: usize> DualNumber for DualN<N> {
+    type Scalar = f64;
+
+    fn zero() -> Self {
+        Self::new(0.0)
+    }
+
+    fn value(&self) -> f64 {
+        self.val
+    }
+
+    fn set_value(&mut self, val: f64) {
+        self.val = val;
+    }
+
+    fn map_der<F: Fn(f64) -> f64>(&mut self, f: F) {
+        self.der.iter_mut().for_each(|d| *d = f(*d));
+    }
+
+    fn zip_der<F: Fn(f64, f64) -> f64>(&mut self, other: &Self, f: F) {
+        self.der
+            .iter_mut()
+            .zip(other.der.iter())
+            .for_each(|(l, r)| *l = f(*l, *r));
+    }
+}
+
+impl<const N: usize> From<f64> for DualN<N> {
+    fn from(val: f64) -> Self {
+        Self::new(val)
+    }
+}
+
+/// Implement Arbitrary trait for DualN to use it in property tests.
+#[cfg(feature = "test-utils")]
+impl<const N: usize> quickcheck::Arbitrary for DualN<N> {
+    /// Just create DualN num from f64
+    fn arbitrary<G: quickcheck::Gen>(g: &mut G) -> DualN<N> {
+        f64::arbitrary(g).into()
+    }
+}
+
+/// Implement ApproxEq trait for DualN to use it in property tests
+#[cfg(feature = "test-utils")]
+impl<const N: usize> float_cmp::ApproxEq for DualN<N> {
+    /// Use 'Margin' from f64
+    type Margin = float_cmp::F64Margin;
+    /// Check DualN numbers for approximate equal. Compare value and all tangent components.
+    fn approx_eq<M: Into<Self::Margin>>(self, other: Self, margin: M) -> bool {
+        let margin = margin.into();
+        self.val.approx_eq(other.val, margin)
+            && self
+                .der
+                .iter()
+                .zip(other.der.iter())
+                .all(|(l, r)| l.approx_eq(*r, margin))
+    }
+}
@@ -0,0 +1,419 @@
+use crate::dual::Dual;
+use num_traits::{Float, Num, NumCast, One, ToPrimitive, Zero};
+use std::cmp::Ordering;
+use std::num::FpCategory;
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+
+/// Dual number which implements 'num_traits::Float', so it can be
+/// passed to generic numerical code. 'num_traits' requires operators
+/// which return 'Self', so unlike 'Dual' operations are evaluated
+/// eagerly instead of building expressions.
+#[derive(Clone, Copy, Debug)]
+pub struct FloatDual<T = f64>(pub Dual<T>);
+
+impl<T: Float> FloatDual<T> {
+    /// Create new FloatDual number from value and derivative.
+    ///
+    /// # Arguments
+    ///
+    /// 'val' - value of number.
+    /// 'der' - derivative of number.
+    pub fn new(val: T, der: T) -> Self {
+        FloatDual(Dual { val, der })
+    }
+
+    /// Create constant, derivative is zero.
+    fn constant(val: T) -> Self {
+        Self::new(val, T::zero())
+    }
+
+    /// value of dual variable
+    pub fn val(&self) -> T {
+        self.0.val
+    }
+
+    /// derivative of dual variable
+    pub fn der(&self) -> T {
+        self.0.der
+    }
+
+    /// Apply chain rule, 'val' is value of function and
+    /// 'd1' is its derivative at current value.
+    fn chain(self, val: T, d1: T) -> Self {
+        Self::new(val, self.der() * d1)
+    }
+}
+
+/// Convert f64 constant to scalar type.
+fn scalar<T: Float>(val: f64) -> T {
+    <T as NumCast>::from(val).unwrap()
+}
+
+impl<T> From<Dual<T>> for FloatDual<T> {
+    fn from(dual: Dual<T>) -> Self {
+        FloatDual(dual)
+    }
+}
+
+impl<T> From<FloatDual<T>> for Dual<T> {
+    fn from(dual: FloatDual<T>) -> Self {
+        dual.0
+    }
+}
+
+impl<T: Float> PartialEq for FloatDual<T> {
+    fn eq(&self, other: &FloatDual<T>) -> bool {
+        self.val().eq(&other.val())
+    }
+}
+
+impl<T: Float> PartialOrd for FloatDual<T> {
+    fn partial_cmp(&self, other: &FloatDual<T>) -> Option<Ordering> {
+        self.val().partial_cmp(&other.val())
+    }
+}
+
+impl<T: Float> Neg for FloatDual<T> {
+    type Output = FloatDual<T>;
+    fn neg(self) -> Self::Output {
+        Self::new(-self.val(), -self.der())
+    }
+}
+
+impl<T: Float> Add for FloatDual<T> {
+    type Output = FloatDual<T>;
+    fn add(self, other: FloatDual<T>) -> Self::Output {
+        Self::new(self.val() + other.val(), self.der() + other.der())
+    }
+}
+
+impl<T: Float> Sub for FloatDual<T> {
+    type Output = FloatDual<T>;
+    fn sub(self, other: FloatDual<T>) -> Self::Output {
+        Self::new(self.val() - other.val(), self.der() - other.der())
+    }
+}
+
+impl<T: Float> Mul for FloatDual<T> {
+    type Output = FloatDual<T>;
+    fn mul(self, other: FloatDual<T>) -> Self::Output {
+        Self::new(
+            self.val() * other.val(),
+            self.der() * other.val() + self.val() * other.der(),
+        )
+    }
+}
+
+impl<T: Float> Div for FloatDual<T> {
+    type Output = FloatDual<T>;
+    fn div(self, other: FloatDual<T>) -> Self::Output {
+        Self::new(
+            self.val() / other.val(),
+            (self.der() * other.val() - self.val() * other.der()) / (other.val() * other.val()),
+        )
+    }
+}
+
+impl<T: Float> Rem for FloatDual<T> {
+    type Output = FloatDual<T>;
+    fn rem(self, other: FloatDual<T>) -> Self::Output {
+        let quot = (self.val() / other.val()).trunc();
+        Self::new(self.val() % other.val(), self.der() - other.der() * quot)
+    }
+}
+
+macro_rules! impl_assign_op(
+    ($Op: ident, $op: ident, $fun: ident) => {
+        /// $Op operation FloatDual to FloatDual.
+        impl<T: Float> $Op for FloatDual<T> {
+            fn $op(&mut self, other: FloatDual<T>) { *self = (*self).$fun(other); }
+        }
+    }
+);
+
+impl_assign_op!(AddAssign, add_assign, add);
+impl_assign_op!(SubAssign, sub_assign, sub);
+impl_assign_op!(MulAssign, mul_assign, mul);
+impl_assign_op!(DivAssign, div_assign, div);
+impl_assign_op!(RemAssign, rem_assign, rem);
+
+impl<T: Float> Zero for FloatDual<T> {
+    fn zero() -> Self {
+        Self::constant(T::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.val().is_zero()
+    }
+}
+
+impl<T: Float> One for FloatDual<T> {
+    fn one() -> Self {
+        Self::constant(T::one())
+    }
+}
+
+impl<T: Float> Num for FloatDual<T> {
+    type FromStrRadixErr = T::FromStrRadixErr;
+
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        T::from_str_radix(str, radix).map(Self::constant)
+    }
+}
+
+impl<T: Float> ToPrimitive for FloatDual<T> {
+    fn to_i64(&self) -> Option<i64> {
+        self.val().to_i64()
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        self.val().to_u64()
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        self.val().to_f64()
+    }
+}
+
+impl<T: Float> NumCast for FloatDual<T> {
+    fn from<N: ToPrimitive>(n: N) -> Option<Self> {
+        <T as NumCast>::from(n).map(Self::constant)
+    }
+}
+
+/// Forward constructors of constants to scalar type.
+macro_rules! forward_const(
+    ($($name: ident),*) => {
+        $(fn $name() -> Self { Self::constant(T::$name()) })*
+    }
+);
+
+/// Forward predicates to value of dual number.
+macro_rules! forward_val(
+    ($($name: ident -> $Res: ty),*) => {
+        $(fn $name(self) -> $Res { self.val().$name() })*
+    }
+);
+
+/// Piecewise constant functions have zero derivative.
+macro_rules! forward_step(
+    ($($name: ident),*) => {
+        $(fn $name(self) -> Self { Self::constant(self.val().$name()) })*
+    }
+);
+
+impl<T: Float> Float for FloatDual<T> {
+    forward_const!(
+        nan,
+        infinity,
+        neg_infinity,
+        neg_zero,
+        min_value,
+        min_positive_value,
+        epsilon,
+        max_value
+    );
+
+    forward_val!(
+        is_nan -> bool,
+        is_infinite -> bool,
+        is_finite -> bool,
+        is_normal -> bool,
+        classify -> FpCategory,
+        is_sign_positive -> bool,
+        is_sign_negative -> bool,
+        integer_decode -> (u64, i16, i8)
+    );
+
+    forward_step!(floor, ceil, round, trunc, signum);
+
+    fn fract(self) -> Self {
+        self.chain(self.val().fract(), T::one())
+    }
+
+    fn abs(self) -> Self {
+        self.chain(self.val().abs(), self.val().signum())
+    }
+
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        self * a + b
+    }
+
+    fn recip(self) -> Self {
+        let val = self.val().recip();
+        self.chain(val, -val * val)
+    }
+
+    fn powi(self, n: i32) -> Self {
+        let d1 = if n == 0 {
+            T::zero()
+        } else {
+            scalar::<T>(n as f64) * self.val().powi(n - 1)
+        };
+        self.chain(self.val().powi(n), d1)
+    }
+
+    fn powf(self, n: Self) -> Self {
+        let val = self.val().powf(n.val());
+        let d1 = n.val() * self.val().powf(n.val() - T::one());
+        if n.der().is_zero() {
+            return self.chain(val, d1);
+        }
+        Self::new(val, self.der() * d1 + n.der() * val * self.val().ln())
+    }
+
+    fn sqrt(self) -> Self {
+        let val = self.val().sqrt();
+        self.chain(val, (val + val).recip())
+    }
+
+    fn exp(self) -> Self {
+        let val = self.val().exp();
+        self.chain(val, val)
+    }
+
+    fn exp2(self) -> Self {
+        let val = self.val().exp2();
+        self.chain(val, val * scalar(std::f64::consts::LN_2))
+    }
+
+    fn ln(self) -> Self {
+        self.chain(self.val().ln(), self.val().recip())
+    }
+
+    fn log(self, base: Self) -> Self {
+        self.ln() / base.ln()
+    }
+
+    fn log2(self) -> Self {
+        self.chain(self.val().log2(), (self.val() * scalar(std::f64::consts::LN_2)).recip())
+    }
+
+    fn log10(self) -> Self {
+        self.chain(self.val().log10(), (self.val() * scalar(std::f64::consts::LN_10)).recip())
+    }
+
+    fn max(self, other: Self) -> Self {
+        if other.val() > self.val() || self.val().is_nan() {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn min(self, other: Self) -> Self {
+        if other.val() < self.val() || self.val().is_nan() {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn abs_sub(self, other: Self) -> Self {
+        if self.val() <= other.val() {
+            Self::zero()
+        } else {
+            self - other
+        }
+    }
+
+    fn cbrt(self) -> Self {
+        let val = self.val().cbrt();
+        self.chain(val, (scalar::<T>(3.0) * val * val).recip())
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        let val = self.val().hypot(other.val());
+        Self::new(
+            val,
+            (self.val() * self.der() + other.val() * other.der()) / val,
+        )
+    }
+
+    fn sin(self) -> Self {
+        self.chain(self.val().sin(), self.val().cos())
+    }
+
+    fn cos(self) -> Self {
+        self.chain(self.val().cos(), -self.val().sin())
+    }
+
+    fn tan(self) -> Self {
+        let val = self.val().tan();
+        self.chain(val, T::one() + val * val)
+    }
+
+    fn asin(self) -> Self {
+        let d1 = (T::one() - self.val() * self.val()).sqrt().recip();
+        self.chain(self.val().asin(), d1)
+    }
+
+    fn acos(self) -> Self {
+        let d1 = -(T::one() - self.val() * self.val()).sqrt().recip();
+        self.chain(self.val().acos(), d1)
+    }
+
+    fn atan(self) -> Self {
+        let d1 = (T::one() + self.val() * self.val()).recip();
+        self.chain(self.val().atan(), d1)
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        let (y, x) = (self.val(), other.val());
+        Self::new(
+            y.atan2(x),
+            (x * self.der() - y * other.der()) / (x * x + y * y),
+        )
+    }
+
+    fn sin_cos(self) -> (Self, Self) {
+        let (sin, cos) = self.val().sin_cos();
+        (self.chain(sin, cos), self.chain(cos, -sin))
+    }
+
+    fn exp_m1(self) -> Self {
+        self.chain(self.val().exp_m1(), self.val().exp())
+    }
+
+    fn ln_1p(self) -> Self {
+        self.chain(self.val().ln_1p(), (T::one() + self.val()).recip())
+    }
+
+    fn sinh(self) -> Self {
+        self.chain(self.val().sinh(), self.val().cosh())
+    }
+
+    fn cosh(self) -> Self {
+        self.chain(self.val().cosh(), self.val().sinh())
+    }
+
+    fn tanh(self) -> Self {
+        let val = self.val().tanh();
+        self.chain(val, T::one() - val * val)
+    }
+
+    fn asinh(self) -> Self {
+        let d1 = (self.val() * self.val() + T::one()).sqrt().recip();
+        self.chain(self.val().asinh(), d1)
+    }
+
+    fn acosh(self) -> Self {
+        let d1 = (self.val() * self.val() - T::one()).sqrt().recip();
+        self.chain(self.val().acosh(), d1)
+    }
+
+    fn atanh(self) -> Self {
+        let d1 = (T::one() - self.val() * self.val()).recip();
+        self.chain(self.val().atanh(), d1)
+    }
+
+    fn to_degrees(self) -> Self {
+        self.chain(self.val().to_degrees(), scalar::<T>(180.0) / scalar(std::f64::consts::PI))
+    }
+
+    fn to_radians(self) -> Self {
+        self.chain(self.val().to_radians(), scalar::<T>(std::f64::consts::PI) / scalar(180.0))
+    }
+}
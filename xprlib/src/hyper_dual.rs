@@ -0,0 +1,172 @@
+use crate::dual::DualNumber;
+/// To be able apply property tests
+#[cfg(feature = "test-utils")]
+extern crate quickcheck;
+
+/// Hyper-dual number f + f1 e1 + f2 e2 + f12 e1 e2, where
+/// e1^2 = e2^2 = 0. Seeding e1 with direction of i-th input
+/// and e2 with direction of j-th input gives second order
+/// derivative w.r.t. i-th and j-th inputs in mixed part.
+#[derive(Clone, Copy, Debug)]
+pub struct HyperDual {
+    /// Value of HyperDual number.
+    pub(crate) val: f64,
+    /// First tangent component.
+    pub(crate) e1: f64,
+    /// Second tangent component.
+    pub(crate) e2: f64,
+    /// Mixed second order component.
+    pub(crate) e12: f64,
+}
+
+impl HyperDual {
+    /// Create new HyperDual number with zero tangent parts.
+    ///
+    /// # Arguments
+    ///
+    /// 'val' - value of HyperDual number.
+    pub fn new(val: f64) -> Self {
+        Self::seeded(val, 0.0, 0.0)
+    }
+
+    /// Create HyperDual number with given first order tangents.
+    ///
+    /// # Arguments
+    ///
+    /// 'val' - value of HyperDual number.
+    /// 'e1' - first tangent component.
+    /// 'e2' - second tangent component.
+    pub fn seeded(val: f64, e1: f64, e2: f64) -> Self {
+        Self {
+            val,
+            e1,
+            e2,
+            e12: 0.0,
+        }
+    }
+
+    /// Create 'N' variables, i-th variable is seeded in e1
+    /// and j-th variable is seeded in e2 direction.
+    ///
+    /// # Arguments
+    ///
+    /// 'vals' - values of variables.
+    /// 'i' - index of variable seeded in e1 direction.
+    /// 'j' - index of variable seeded in e2 direction.
+    pub fn variables<const N: usize>(vals: [f64; N], i: usize, j: usize) -> [Self; N] {
+        let mut vars = [Self::new(0.0); N];
+        for (k, (var, val)) in vars.iter_mut().zip(vals.iter()).enumerate() {
+            *var = Self::seeded(*val, (k == i) as u8 as f64, (k == j) as u8 as f64);
+        }
+        vars
+    }
+
+    /// value of hyper-dual variable
+    pub fn val(&self) -> f64 {
+        self.val
+    }
+
+    /// derivative in e1 direction
+    pub fn der1(&self) -> f64 {
+        self.e1
+    }
+
+    /// derivative in e2 direction
+    pub fn der2(&self) -> f64 {
+        self.e2
+    }
+
+    /// mixed second order derivative in e1 and e2 directions
+    pub fn der12(&self) -> f64 {
+        self.e12
+    }
+
+    /// Multiply target by other hyper-dual number.
+    pub(crate) fn mul_assign(&mut self, other: &HyperDual) {
+        self.e12 = self.e12 * other.val
+            + self.e1 * other.e2
+            + self.e2 * other.e1
+            + self.val * other.e12;
+        self.e1 = self.e1 * other.val + self.val * other.e1;
+        self.e2 = self.e2 * other.val + self.val * other.e2;
+        self.val *= other.val;
+    }
+}
+
+impl DualNumber for HyperDual {
+    type Scalar = f64;
+
+    fn zero() -> Self {
+        Self::new(0.0)
+    }
+
+    fn value(&self) -> f64 {
+        self.val
+    }
+
+    fn set_value(&mut self, val: f64) {
+        self.val = val;
+    }
+
+    fn map_der<F: Fn(f64) -> f64>(&mut self, f: F) {
+        self.e1 = f(self.e1);
+        self.e2 = f(self.e2);
+        self.e12 = f(self.e12);
+    }
+
+    fn zip_der<F: Fn(f64, f64) -> f64>(&mut self, other: &Self, f: F) {
+        self.e1 = f(self.e1, other.e1);
+        self.e2 = f(self.e2, other.e2);
+        self.e12 = f(self.e12, other.e12);
+    }
+
+    fn chain(&mut self, val: f64, d1: f64, d2: f64) {
+        self.e12 = d1 * self.e12 + d2 * self.e1 * self.e2;
+        self.e1 *= d1;
+        self.e2 *= d1;
+        self.val = val;
+    }
+
+    fn chain_bin(&mut self, other: &Self, val: f64, d1: (f64, f64), d2: (f64, f64, f64)) {
+        let (dl, dr) = d1;
+        let (dll, dlr, drr) = d2;
+        self.e12 = dl * self.e12
+            + dr * other.e12
+            + dll * self.e1 * self.e2
+            + dlr * (self.e1 * other.e2 + other.e1 * self.e2)
+            + drr * other.e1 * other.e2;
+        self.e1 = dl * self.e1 + dr * other.e1;
+        self.e2 = dl * self.e2 + dr * other.e2;
+        self.val = val;
+    }
+}
+
+impl From<f64> for HyperDual {
+    fn from(val: f64) -> Self {
+        Self::new(val)
+    }
+}
+
+/// Implement Arbitrary trait for HyperDual to use it in property tests.
+#[cfg(feature = "test-utils")]
+impl quickcheck::Arbitrary for HyperDual {
+    /// Just create HyperDual num from f64
+    fn arbitrary<G: quickcheck::Gen>(g: &mut G) -> HyperDual {
+        f64::arbitrary(g).into()
+    }
+}
+
+/// Implement ApproxEq trait for HyperDual to use it in property tests
+#[cfg(feature = "test-utils")]
+impl float_cmp::ApproxEq for HyperDual {
+    /// Use 'Margin' from f64
+    type Margin = float_cmp::F64Margin;
+    /// Check HyperDual numbers for approximate equal. Compare value and all tangent parts.
+    fn approx_eq<M: Into<Self::Margin>>(self, other: Self, margin: M) -> bool {
+        let margin = margin.into();
+        self.val.approx_eq(other.val, margin)
+            && self.e1.approx_eq(other.e1, margin)
+            && self.e2.approx_eq(other.e2, margin)
+            && self.e12.approx_eq(other.e12, margin)
+    }
+}
@@ -1,6 +1,28 @@
 pub mod cmp;
+pub mod codegen;
+pub mod complex;
 pub mod dual;
+pub mod dual_n;
+#[cfg(feature = "num-traits")]
+pub mod float;
+pub mod hyper_dual;
+pub mod parser;
+pub mod products;
+pub mod real;
+#[cfg(feature = "special")]
+pub mod special;
+pub mod tape;
+pub mod taylor;
 pub mod xpr;
 
-pub use cmp::*;
+pub use complex::*;
 pub use dual::*;
+pub use dual_n::*;
+#[cfg(feature = "num-traits")]
+pub use float::*;
+pub use hyper_dual::*;
+pub use products::*;
+pub use real::*;
+pub use tape::*;
+pub use taylor::*;
+pub use xpr::{DynNode, DynXpr, Operation, Visit, Visitor};
@@ -0,0 +1,419 @@
+use crate::{dual::Dual, xpr::DynXpr};
+use std::fmt;
+
+/// Error of formula parsing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    /// 'position' - index of character where error is found.
+    pub position: usize,
+    /// 'message' - description of error.
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Error of formula evaluation, variable has no value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnboundVariable {
+    /// 'name' - name of variable.
+    pub name: String,
+}
+
+impl fmt::Display for UnboundVariable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "variable '{}' has no value", self.name)
+    }
+}
+
+impl std::error::Error for UnboundVariable {}
+
+/// Functions of one argument.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum UnaryFn {
+    Sin,
+    Cos,
+    Tan,
+    Sqrt,
+    Ln,
+    Exp,
+    Asin,
+    Acos,
+    Atan,
+    Sinh,
+    Cosh,
+    Tanh,
+    Abs,
+}
+
+impl UnaryFn {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "sin" => Self::Sin,
+            "cos" => Self::Cos,
+            "tan" => Self::Tan,
+            "sqrt" => Self::Sqrt,
+            "ln" => Self::Ln,
+            "exp" => Self::Exp,
+            "asin" => Self::Asin,
+            "acos" => Self::Acos,
+            "atan" => Self::Atan,
+            "sinh" => Self::Sinh,
+            "cosh" => Self::Cosh,
+            "tanh" => Self::Tanh,
+            "abs" => Self::Abs,
+            _ => return None,
+        })
+    }
+
+    fn apply(self, x: DynXpr) -> DynXpr {
+        match self {
+            Self::Sin => x.sin().into_dyn(),
+            Self::Cos => x.cos().into_dyn(),
+            Self::Tan => x.tan().into_dyn(),
+            Self::Sqrt => x.sqrt().into_dyn(),
+            Self::Ln => x.ln().into_dyn(),
+            Self::Exp => x.exp().into_dyn(),
+            Self::Asin => x.asin().into_dyn(),
+            Self::Acos => x.acos().into_dyn(),
+            Self::Atan => x.atan().into_dyn(),
+            Self::Sinh => x.sinh().into_dyn(),
+            Self::Cosh => x.cosh().into_dyn(),
+            Self::Tanh => x.tanh().into_dyn(),
+            Self::Abs => x.abs().into_dyn(),
+        }
+    }
+}
+
+/// Functions of two arguments, 'Powf' is also written as '^'.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BinaryFn {
+    Powf,
+    Atan2,
+    Hypot,
+    Min,
+    Max,
+}
+
+impl BinaryFn {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "powf" => Self::Powf,
+            "atan2" => Self::Atan2,
+            "hypot" => Self::Hypot,
+            "min" => Self::Min,
+            "max" => Self::Max,
+            _ => return None,
+        })
+    }
+
+    fn apply(self, l: DynXpr, r: DynXpr) -> DynXpr {
+        match self {
+            Self::Powf => l.powf(r).into_dyn(),
+            Self::Atan2 => l.atan2(r).into_dyn(),
+            Self::Hypot => l.hypot(r).into_dyn(),
+            Self::Min => l.min(r).into_dyn(),
+            Self::Max => l.max(r).into_dyn(),
+        }
+    }
+}
+
+/// Node of parsed formula.
+#[derive(Clone, Debug, PartialEq)]
+enum Node {
+    Const(f64),
+    /// index of variable in 'Formula::variables'
+    Var(usize),
+    Neg(Box<Node>),
+    Add(Box<Node>, Box<Node>),
+    Sub(Box<Node>, Box<Node>),
+    Mul(Box<Node>, Box<Node>),
+    Div(Box<Node>, Box<Node>),
+    Unary(UnaryFn, Box<Node>),
+    Binary(BinaryFn, Box<Node>, Box<Node>),
+}
+
+/// Formula parsed from infix string, e.g. "x*sin(y) + exp(-z^2)".
+/// Formula is evaluated as runtime expression over Dual numbers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Formula {
+    /// 'variables' - names of variables in order of first appearance.
+    variables: Vec<String>,
+    /// 'root' - root node of formula.
+    root: Node,
+}
+
+impl Formula {
+    /// Parse formula. Numbers, variables, '+', '-', '*', '/', '^',
+    /// parentheses and functions sin, cos, tan, sqrt, ln, exp, asin,
+    /// acos, atan, sinh, cosh, tanh, abs, powf, atan2, hypot, min
+    /// and max are supported.
+    ///
+    /// # Arguments
+    ///
+    /// 'src' - formula text.
+    pub fn parse(src: &str) -> Result<Self, ParseError> {
+        let mut parser = Parser {
+            chars: src.chars().collect(),
+            pos: 0,
+            variables: Vec::new(),
+        };
+        let root = parser.expr()?;
+        parser.skip_whitespace();
+        if let Some(c) = parser.peek() {
+            return Err(parser.error(format!("unexpected '{}' after expression", c)));
+        }
+        Ok(Self {
+            variables: parser.variables,
+            root,
+        })
+    }
+
+    /// Names of variables in order of first appearance.
+    pub fn variables(&self) -> &[String] {
+        &self.variables
+    }
+
+    /// Value of formula.
+    ///
+    /// # Arguments
+    ///
+    /// 'vars' - names and values of variables.
+    pub fn eval(&self, vars: &[(&str, f64)]) -> Result<f64, UnboundVariable> {
+        Ok(self.eval_seeded(vars, None)?.val())
+    }
+
+    /// Value and gradient of formula w.r.t. given variables, formula is
+    /// evaluated once per variable with its Dual number seeded.
+    ///
+    /// # Arguments
+    ///
+    /// 'vars' - names and values of variables, gradient is in the same order.
+    pub fn gradient(&self, vars: &[(&str, f64)]) -> Result<(f64, Vec<f64>), UnboundVariable> {
+        let val = self.eval(vars)?;
+        let grad = vars
+            .iter()
+            .map(|(name, _)| Ok(self.eval_seeded(vars, Some(name))?.der()))
+            .collect::<Result<_, _>>()?;
+        Ok((val, grad))
+    }
+
+    /// Evaluate formula with variable 'seed' seeded.
+    fn eval_seeded(&self, vars: &[(&str, f64)], seed: Option<&str>) -> Result<Dual, UnboundVariable> {
+        let duals = self
+            .variables
+            .iter()
+            .map(|name| match vars.iter().find(|(var, _)| var == name) {
+                Some((_, val)) if seed == Some(name) => Ok(Dual::from(*val).seeded()),
+                Some((_, val)) => Ok(Dual::from(*val)),
+                None => Err(UnboundVariable { name: name.clone() }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(build(&self.root, &duals).eval())
+    }
+}
+
+/// Build runtime expression of formula node.
+fn build(node: &Node, vars: &[Dual]) -> DynXpr {
+    match node {
+        Node::Const(c) => DynXpr::new(*c),
+        Node::Var(i) => DynXpr::new(vars[*i]),
+        Node::Neg(x) => (-build(x, vars)).into_dyn(),
+        Node::Add(l, r) => (build(l, vars) + build(r, vars)).into_dyn(),
+        Node::Sub(l, r) => (build(l, vars) - build(r, vars)).into_dyn(),
+        Node::Mul(l, r) => (build(l, vars) * build(r, vars)).into_dyn(),
+        Node::Div(l, r) => (build(l, vars) / build(r, vars)).into_dyn(),
+        Node::Unary(f, x) => f.apply(build(x, vars)),
+        Node::Binary(f, l, r) => f.apply(build(l, vars), build(r, vars)),
+    }
+}
+
+/// Recursive descent parser, grammar is
+/// expr = term (('+' | '-') term)*
+/// term = unary (('*' | '/') unary)*
+/// unary = '-' unary | power
+/// power = primary ('^' unary)?
+/// primary = number | name | name '(' expr (',' expr)* ')' | '(' expr ')'
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    variables: Vec<String>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    /// Skip whitespaces and consume 'c' if it is next character.
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn error(&self, message: String) -> ParseError {
+        ParseError {
+            position: self.pos,
+            message,
+        }
+    }
+
+    fn expr(&mut self) -> Result<Node, ParseError> {
+        let mut node = self.term()?;
+        loop {
+            if self.eat('+') {
+                node = Node::Add(Box::new(node), Box::new(self.term()?));
+            } else if self.eat('-') {
+                node = Node::Sub(Box::new(node), Box::new(self.term()?));
+            } else {
+                return Ok(node);
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<Node, ParseError> {
+        let mut node = self.unary()?;
+        loop {
+            if self.eat('*') {
+                node = Node::Mul(Box::new(node), Box::new(self.unary()?));
+            } else if self.eat('/') {
+                node = Node::Div(Box::new(node), Box::new(self.unary()?));
+            } else {
+                return Ok(node);
+            }
+        }
+    }
+
+    fn unary(&mut self) -> Result<Node, ParseError> {
+        if self.eat('-') {
+            return Ok(Node::Neg(Box::new(self.unary()?)));
+        }
+        self.power()
+    }
+
+    fn power(&mut self) -> Result<Node, ParseError> {
+        let base = self.primary()?;
+        if self.eat('^') {
+            // right associative, 'x^-y' is allowed
+            let deg = self.unary()?;
+            return Ok(Node::Binary(BinaryFn::Powf, Box::new(base), Box::new(deg)));
+        }
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> Result<Node, ParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(c) if c.is_ascii_digit() || c == '.' => self.number(),
+            Some(c) if c.is_alphabetic() || c == '_' => self.name(),
+            Some('(') => {
+                self.pos += 1;
+                let node = self.expr()?;
+                self.expect_close()?;
+                Ok(node)
+            }
+            Some(c) => Err(self.error(format!("unexpected '{}', expected expression", c))),
+            None => Err(self.error("unexpected end of formula, expected expression".to_string())),
+        }
+    }
+
+    fn expect_close(&mut self) -> Result<(), ParseError> {
+        if self.eat(')') {
+            Ok(())
+        } else {
+            Err(self.error("expected ')'".to_string()))
+        }
+    }
+
+    fn number(&mut self) -> Result<Node, ParseError> {
+        let start = self.pos;
+        let digits = |p: &mut Self| {
+            while p.peek().is_some_and(|c| c.is_ascii_digit()) {
+                p.pos += 1;
+            }
+        };
+        digits(self);
+        if self.peek() == Some('.') {
+            self.pos += 1;
+            digits(self);
+        }
+        if let Some('e') | Some('E') = self.peek() {
+            self.pos += 1;
+            if let Some('+') | Some('-') = self.peek() {
+                self.pos += 1;
+            }
+            digits(self);
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        text.parse().map(Node::Const).map_err(|_| ParseError {
+            position: start,
+            message: format!("invalid number '{}'", text),
+        })
+    }
+
+    fn name(&mut self) -> Result<Node, ParseError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            self.pos += 1;
+        }
+        let name: String = self.chars[start..self.pos].iter().collect();
+        if !self.eat('(') {
+            let idx = match self.variables.iter().position(|var| *var == name) {
+                Some(idx) => idx,
+                None => {
+                    self.variables.push(name);
+                    self.variables.len() - 1
+                }
+            };
+            return Ok(Node::Var(idx));
+        }
+        let mut args = vec![self.expr()?];
+        while self.eat(',') {
+            args.push(self.expr()?);
+        }
+        self.expect_close()?;
+        let arity = |expected: usize| ParseError {
+            position: start,
+            message: format!(
+                "function '{}' takes {} argument(s), {} given",
+                name,
+                expected,
+                args.len()
+            ),
+        };
+        if let Some(f) = UnaryFn::from_name(&name) {
+            if args.len() != 1 {
+                return Err(arity(1));
+            }
+            return Ok(Node::Unary(f, Box::new(args.remove(0))));
+        }
+        if let Some(f) = BinaryFn::from_name(&name) {
+            if args.len() != 2 {
+                return Err(arity(2));
+            }
+            let r = args.pop().unwrap();
+            let l = args.pop().unwrap();
+            return Ok(Node::Binary(f, Box::new(l), Box::new(r)));
+        }
+        Err(ParseError {
+            position: start,
+            message: format!("unknown function '{}'", name),
+        })
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{dual::Dual, tape::{Tape, Var}};
+
+/// Jacobian-vector product of vector valued function. Each input is
+/// seeded with corresponding component of direction, so 'J v' is
+/// evaluated in one forward pass without forming Jacobian.
+/// Values of function and product are returned.
+///
+/// # Arguments
+///
+/// 'f' - function which Jacobian is multiplied.
+/// 'x' - point at which Jacobian is evaluated.
+/// 'v' - direction, vector of the same length as 'x'.
+///
+/// # Panics
+///
+/// If 'x' and 'v' have different length.
+pub fn jvp<F, Y>(f: F, x: &[f64], v: &[f64]) -> (Vec<f64>, Vec<f64>)
+where
+    F: Fn(&[Dual]) -> Y,
+    Y: AsRef<[Dual]>,
+{
+    assert_eq!(x.len(), v.len(), "direction and point have different length");
+    let x: Vec<Dual> = x
+        .iter()
+        .zip(v)
+        .map(|(val, der)| Dual::with_derivative(*val, *der))
+        .collect();
+    let y = f(&x);
+    y.as_ref().iter().map(|y| (y.val(), y.der())).unzip()
+}
+
+/// Vector-Jacobian product of vector valued function. Tape is recorded
+/// once and outputs are weighted by components of 'v' in one backward
+/// sweep, so 'v^T J' is evaluated without forming Jacobian.
+/// Values of function and product are returned.
+///
+/// # Arguments
+///
+/// 'f' - function which Jacobian is multiplied.
+/// 'x' - point at which Jacobian is evaluated.
+/// 'v' - cotangent, vector of the same length as function output.
+///
+/// # Panics
+///
+/// If function output and 'v' have different length.
+pub fn vjp<F>(f: F, x: &[f64], v: &[f64]) -> (Vec<f64>, Vec<f64>)
+where
+    F: for<'t> Fn(&[Var<'t>]) -> Vec<Var<'t>>,
+{
+    let tape = Tape::new();
+    let vars: Vec<Var> = x.iter().map(|x| tape.var(*x)).collect();
+    let y = f(&vars);
+    assert_eq!(y.len(), v.len(), "cotangent and function output have different length");
+    let seeds: Vec<_> = y.iter().zip(v).map(|(y, v)| (y.idx, *v)).collect();
+    let adjoints = tape.backward(&seeds);
+    (
+        y.iter().map(Var::val).collect(),
+        vars.iter().map(|v| adjoints.wrt(v)).collect(),
+    )
+}
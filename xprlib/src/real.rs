@@ -0,0 +1,354 @@
+use crate::dual::Dual;
+use std::fmt::Debug;
+
+/// Real number which can be used as a scalar of Dual number.
+/// Arithmetic is done through named methods, so nested Dual
+/// numbers, which operators produce expressions, can be scalars too.
+pub trait Real: Copy + Clone + Debug + PartialOrd {
+    /// Additive identity.
+    fn zero() -> Self;
+
+    /// Multiplicative identity.
+    fn one() -> Self;
+
+    /// Convert f64 constant to this type.
+    fn from_f64(val: f64) -> Self;
+
+    /// add operation
+    fn add(self, other: Self) -> Self;
+
+    /// sub operation
+    fn sub(self, other: Self) -> Self;
+
+    /// mul operation
+    fn mul(self, other: Self) -> Self;
+
+    /// div operation
+    fn div(self, other: Self) -> Self;
+
+    /// neg operation
+    fn neg(self) -> Self;
+
+    /// sin operation
+    fn sin(self) -> Self;
+
+    /// cos operation
+    fn cos(self) -> Self;
+
+    /// sqrt operation
+    fn sqrt(self) -> Self;
+
+    /// ln operation
+    fn ln(self) -> Self;
+
+    /// exp operation
+    fn exp(self) -> Self;
+
+    /// powf operation
+    fn powf(self, deg: Self) -> Self;
+
+    /// tan operation
+    fn tan(self) -> Self;
+
+    /// asin operation
+    fn asin(self) -> Self;
+
+    /// acos operation
+    fn acos(self) -> Self;
+
+    /// atan operation
+    fn atan(self) -> Self;
+
+    /// sinh operation
+    fn sinh(self) -> Self;
+
+    /// cosh operation
+    fn cosh(self) -> Self;
+
+    /// tanh operation
+    fn tanh(self) -> Self;
+
+    /// asinh operation
+    fn asinh(self) -> Self;
+
+    /// acosh operation
+    fn acosh(self) -> Self;
+
+    /// atanh operation
+    fn atanh(self) -> Self;
+
+    /// exp2 operation
+    fn exp2(self) -> Self;
+
+    /// exp_m1 operation
+    fn exp_m1(self) -> Self;
+
+    /// ln_1p operation
+    fn ln_1p(self) -> Self;
+
+    /// log2 operation
+    fn log2(self) -> Self;
+
+    /// log10 operation
+    fn log10(self) -> Self;
+
+    /// cbrt operation
+    fn cbrt(self) -> Self;
+
+    /// abs operation
+    fn abs(self) -> Self;
+
+    /// signum operation
+    fn signum(self) -> Self;
+
+    /// recip operation
+    fn recip(self) -> Self;
+
+    /// powi operation
+    fn powi(self, n: i32) -> Self;
+
+    /// atan2 operation
+    fn atan2(self, other: Self) -> Self;
+
+    /// hypot operation
+    fn hypot(self, other: Self) -> Self;
+
+    /// min operation
+    fn min(self, other: Self) -> Self;
+
+    /// max operation
+    fn max(self, other: Self) -> Self;
+
+    /// log operation
+    fn log(self, base: Self) -> Self;
+
+    /// copysign operation
+    fn copysign(self, sign: Self) -> Self;
+
+    /// Weights of parts of min and max functions, 'val' is chosen part.
+    /// If parts are equal derivative is average of parts derivatives.
+    fn select(l: Self, r: Self, val: Self) -> (Self, Self) {
+        if l == r {
+            let half = Self::from_f64(0.5);
+            (half, half)
+        } else if val == l {
+            (Self::one(), Self::zero())
+        } else {
+            (Self::zero(), Self::one())
+        }
+    }
+}
+
+macro_rules! impl_real(
+    ($T: ty) => {
+        impl Real for $T {
+            fn zero() -> Self { 0.0 }
+            fn one() -> Self { 1.0 }
+            fn from_f64(val: f64) -> Self { val as $T }
+            fn add(self, other: Self) -> Self { self + other }
+            fn sub(self, other: Self) -> Self { self - other }
+            fn mul(self, other: Self) -> Self { self * other }
+            fn div(self, other: Self) -> Self { self / other }
+            fn neg(self) -> Self { -self }
+            fn sin(self) -> Self { <$T>::sin(self) }
+            fn cos(self) -> Self { <$T>::cos(self) }
+            fn sqrt(self) -> Self { <$T>::sqrt(self) }
+            fn ln(self) -> Self { <$T>::ln(self) }
+            fn exp(self) -> Self { <$T>::exp(self) }
+            fn powf(self, deg: Self) -> Self { <$T>::powf(self, deg) }
+            fn tan(self) -> Self { <$T>::tan(self) }
+            fn asin(self) -> Self { <$T>::asin(self) }
+            fn acos(self) -> Self { <$T>::acos(self) }
+            fn atan(self) -> Self { <$T>::atan(self) }
+            fn sinh(self) -> Self { <$T>::sinh(self) }
+            fn cosh(self) -> Self { <$T>::cosh(self) }
+            fn tanh(self) -> Self { <$T>::tanh(self) }
+            fn asinh(self) -> Self { <$T>::asinh(self) }
+            fn acosh(self) -> Self { <$T>::acosh(self) }
+            fn atanh(self) -> Self { <$T>::atanh(self) }
+            fn exp2(self) -> Self { <$T>::exp2(self) }
+            fn exp_m1(self) -> Self { <$T>::exp_m1(self) }
+            fn ln_1p(self) -> Self { <$T>::ln_1p(self) }
+            fn log2(self) -> Self { <$T>::log2(self) }
+            fn log10(self) -> Self { <$T>::log10(self) }
+            fn cbrt(self) -> Self { <$T>::cbrt(self) }
+            fn abs(self) -> Self { <$T>::abs(self) }
+            fn signum(self) -> Self { <$T>::signum(self) }
+            fn recip(self) -> Self { <$T>::recip(self) }
+            fn powi(self, n: i32) -> Self { <$T>::powi(self, n) }
+            fn atan2(self, other: Self) -> Self { <$T>::atan2(self, other) }
+            fn hypot(self, other: Self) -> Self { <$T>::hypot(self, other) }
+            fn min(self, other: Self) -> Self { <$T>::min(self, other) }
+            fn max(self, other: Self) -> Self { <$T>::max(self, other) }
+            fn log(self, base: Self) -> Self { <$T>::log(self, base) }
+            fn copysign(self, sign: Self) -> Self { <$T>::copysign(self, sign) }
+        }
+    }
+);
+
+impl_real!(f32);
+impl_real!(f64);
+
+/// Dual number is a real number too, this allows
+/// nested Dual numbers for higher order derivatives.
+impl<T: Real> Real for Dual<T> {
+    fn zero() -> Self {
+        Dual::new(T::zero())
+    }
+
+    fn one() -> Self {
+        Dual::new(T::one())
+    }
+
+    fn from_f64(val: f64) -> Self {
+        Dual::new(T::from_f64(val))
+    }
+
+    fn add(self, other: Self) -> Self {
+        (self + other).into()
+    }
+
+    fn sub(self, other: Self) -> Self {
+        (self - other).into()
+    }
+
+    fn mul(self, other: Self) -> Self {
+        (self * other).into()
+    }
+
+    fn div(self, other: Self) -> Self {
+        (self / other).into()
+    }
+
+    fn neg(self) -> Self {
+        (-self).into()
+    }
+
+    fn sin(self) -> Self {
+        Dual::sin(self).into()
+    }
+
+    fn cos(self) -> Self {
+        Dual::cos(self).into()
+    }
+
+    fn sqrt(self) -> Self {
+        Dual::sqrt(self).into()
+    }
+
+    fn ln(self) -> Self {
+        Dual::ln(self).into()
+    }
+
+    fn exp(self) -> Self {
+        Dual::exp(self).into()
+    }
+
+    fn powf(self, deg: Self) -> Self {
+        Dual::powf(self, deg).into()
+    }
+
+    fn tan(self) -> Self {
+        Dual::tan(self).into()
+    }
+
+    fn asin(self) -> Self {
+        Dual::asin(self).into()
+    }
+
+    fn acos(self) -> Self {
+        Dual::acos(self).into()
+    }
+
+    fn atan(self) -> Self {
+        Dual::atan(self).into()
+    }
+
+    fn sinh(self) -> Self {
+        Dual::sinh(self).into()
+    }
+
+    fn cosh(self) -> Self {
+        Dual::cosh(self).into()
+    }
+
+    fn tanh(self) -> Self {
+        Dual::tanh(self).into()
+    }
+
+    fn asinh(self) -> Self {
+        Dual::asinh(self).into()
+    }
+
+    fn acosh(self) -> Self {
+        Dual::acosh(self).into()
+    }
+
+    fn atanh(self) -> Self {
+        Dual::atanh(self).into()
+    }
+
+    fn exp2(self) -> Self {
+        Dual::exp2(self).into()
+    }
+
+    fn exp_m1(self) -> Self {
+        Dual::exp_m1(self).into()
+    }
+
+    fn ln_1p(self) -> Self {
+        Dual::ln_1p(self).into()
+    }
+
+    fn log2(self) -> Self {
+        Dual::log2(self).into()
+    }
+
+    fn log10(self) -> Self {
+        Dual::log10(self).into()
+    }
+
+    fn cbrt(self) -> Self {
+        Dual::cbrt(self).into()
+    }
+
+    fn abs(self) -> Self {
+        Dual::abs(self).into()
+    }
+
+    fn signum(self) -> Self {
+        Dual::signum(self).into()
+    }
+
+    fn recip(self) -> Self {
+        Dual::recip(self).into()
+    }
+
+    fn powi(self, n: i32) -> Self {
+        Dual::powi(self, n).into()
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        Dual::atan2(self, other).into()
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        Dual::hypot(self, other).into()
+    }
+
+    fn min(self, other: Self) -> Self {
+        Dual::min(self, other).into()
+    }
+
+    fn max(self, other: Self) -> Self {
+        Dual::max(self, other).into()
+    }
+
+    fn log(self, base: Self) -> Self {
+        Dual::log(self, base).into()
+    }
+
+    fn copysign(self, sign: Self) -> Self {
+        Dual::copysign(self, sign).into()
+    }
+}
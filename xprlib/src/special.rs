@@ -0,0 +1,432 @@
+use crate::{
+    dual::{Dual, DualNumber},
+    dual_n::DualN,
+    hyper_dual::HyperDual,
+    xpr::expression::{UnXpr, Xpr, XprWrapper},
+};
+use std::f64::consts::{FRAC_2_SQRT_PI, PI};
+
+/// Coefficients of Lanczos approximation with g = 7.
+const LANCZOS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+/// Argument above which asymptotic expansions of polygamma functions are used.
+const POLYGAMMA_ASYMPTOTIC: f64 = 10.0;
+
+/// Argument above which asymptotic expansion of Bessel functions is used.
+const BESSEL_ASYMPTOTIC: f64 = 25.0;
+
+/// Error function.
+pub fn erf(x: f64) -> f64 {
+    if x.abs() < 2.0 {
+        // erf(x) = 2/sqrt(pi) exp(-x^2) sum 2^n x^(2n+1) / (1 3 5 ... (2n+1)),
+        // all terms are positive, so there is no cancellation.
+        let (mut term, mut sum, mut n) = (x, x, 0.0);
+        while term.abs() > sum.abs() * f64::EPSILON {
+            n += 1.0;
+            term *= 2.0 * x * x / (2.0 * n + 1.0);
+            sum += term;
+        }
+        FRAC_2_SQRT_PI * (-x * x).exp() * sum
+    } else {
+        1.0_f64.copysign(x) - erfc_cf(x.abs()).copysign(x)
+    }
+}
+
+/// Complementary error function, 1 - erf(x).
+pub fn erfc(x: f64) -> f64 {
+    if x < 0.5 {
+        1.0 - erf(x)
+    } else {
+        erfc_cf(x)
+    }
+}
+
+/// Complementary error function for x >= 0.5 by continued fraction
+/// evaluated with modified Lentz method.
+fn erfc_cf(x: f64) -> f64 {
+    let tiny = 1e-300;
+    let (mut f, mut c, mut d) = (x, x, 0.0);
+    for n in 1..500 {
+        let a = n as f64 / 2.0;
+        d = x + a * d;
+        d = if d == 0.0 { tiny } else { d };
+        c = x + a / c;
+        c = if c == 0.0 { tiny } else { c };
+        d = 1.0 / d;
+        let delta = c * d;
+        f *= delta;
+        if (delta - 1.0).abs() < f64::EPSILON {
+            break;
+        }
+    }
+    (-x * x).exp() / (PI.sqrt() * f)
+}
+
+/// Natural logarithm of absolute value of gamma function.
+pub fn ln_gamma(x: f64) -> f64 {
+    if x < 0.5 {
+        // Reflection formula: gamma(x) gamma(1 - x) = pi / sin(pi x).
+        (PI / (PI * x).sin()).abs().ln() - ln_gamma(1.0 - x)
+    } else {
+        let x = x - 1.0;
+        let t = x + 7.5;
+        let sum = LANCZOS[1..]
+            .iter()
+            .enumerate()
+            .fold(LANCZOS[0], |sum, (i, c)| sum + c / (x + i as f64 + 1.0));
+        0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+    }
+}
+
+/// Gamma function.
+pub fn gamma(x: f64) -> f64 {
+    if x < 0.5 {
+        PI / ((PI * x).sin() * gamma(1.0 - x))
+    } else {
+        ln_gamma(x).exp()
+    }
+}
+
+/// Digamma function, logarithmic derivative of gamma function.
+pub fn digamma(x: f64) -> f64 {
+    if !x.is_finite() {
+        return f64::NAN;
+    }
+    if x < 0.0 {
+        // Reflection formula: psi(1 - x) - psi(x) = pi cot(pi x).
+        return digamma(1.0 - x) - PI * cot_pi(x);
+    }
+    // Shift argument with psi(x) = psi(x + 1) - 1/x.
+    let (mut x, mut res) = (x, 0.0);
+    while x < POLYGAMMA_ASYMPTOTIC {
+        res -= 1.0 / x;
+        x += 1.0;
+    }
+    let x2 = 1.0 / (x * x);
+    let series = x2
+        * (1.0 / 12.0
+            - x2 * (1.0 / 120.0 - x2 * (1.0 / 252.0 - x2 * (1.0 / 240.0 - x2 / 132.0))));
+    res + x.ln() - 0.5 / x - series
+}
+
+/// Trigamma function, derivative of digamma function.
+pub fn trigamma(x: f64) -> f64 {
+    if !x.is_finite() {
+        return f64::NAN;
+    }
+    if x < 0.0 {
+        // Derivative of reflection formula: psi1(x) + psi1(1 - x) = pi^2 / sin^2(pi x).
+        let cot = cot_pi(x);
+        return PI * PI * (1.0 + cot * cot) - trigamma(1.0 - x);
+    }
+    // Shift argument with psi1(x) = psi1(x + 1) + 1/x^2.
+    let (mut x, mut res) = (x, 0.0);
+    while x < POLYGAMMA_ASYMPTOTIC {
+        res += 1.0 / (x * x);
+        x += 1.0;
+    }
+    let x2 = 1.0 / (x * x);
+    let series = 1.0 / 6.0 - x2 * (1.0 / 30.0 - x2 * (1.0 / 42.0 - x2 * (1.0 / 30.0 - x2 * 5.0 / 66.0)));
+    res + 1.0 / x + 0.5 * x2 + series * x2 / x
+}
+
+/// Tetragamma function, second derivative of digamma function.
+pub fn tetragamma(x: f64) -> f64 {
+    if !x.is_finite() {
+        return f64::NAN;
+    }
+    if x < 0.0 {
+        // Second derivative of reflection formula:
+        // psi2(x) - psi2(1 - x) = -2 pi^3 cos(pi x) / sin^3(pi x).
+        let cot = cot_pi(x);
+        return tetragamma(1.0 - x) - 2.0 * PI * PI * PI * cot * (1.0 + cot * cot);
+    }
+    // Shift argument with psi2(x) = psi2(x + 1) - 2/x^3.
+    let (mut x, mut res) = (x, 0.0);
+    while x < POLYGAMMA_ASYMPTOTIC {
+        res -= 2.0 / (x * x * x);
+        x += 1.0;
+    }
+    let x2 = 1.0 / (x * x);
+    let series = 0.5 - x2 * (1.0 / 6.0 - x2 * (1.0 / 6.0 - x2 * (3.0 / 10.0 - x2 * 5.0 / 6.0)));
+    res - x2 - x2 / x - series * x2 * x2
+}
+
+/// Cotangent of pi x. It has period 1, so argument is reduced to [-0.5, 0.5]
+/// to stay accurate for large x. It is infinite at integers.
+fn cot_pi(x: f64) -> f64 {
+    let (sin, cos) = (PI * (x - x.round())).sin_cos();
+    cos / sin
+}
+
+/// Bessel function of the first kind of order zero.
+pub fn bessel_j0(x: f64) -> f64 {
+    bessel_j01(x).0
+}
+
+/// Bessel function of the first kind of order one.
+pub fn bessel_j1(x: f64) -> f64 {
+    bessel_j01(x).1
+}
+
+/// Bessel functions of the first kind of order zero and one.
+fn bessel_j01(x: f64) -> (f64, f64) {
+    let ax = x.abs();
+    let (j0, j1) = if ax == 0.0 {
+        (1.0, 0.0)
+    } else if ax < BESSEL_ASYMPTOTIC {
+        bessel_j01_miller(ax)
+    } else {
+        (bessel_hankel(0.0, ax), bessel_hankel(1.0, ax))
+    };
+    (j0, if x < 0.0 { -j1 } else { j1 })
+}
+
+/// Miller backward recurrence J(k-1) = 2k/x J(k) - J(k+1) normalized
+/// with identity J0 + 2 (J2 + J4 + ...) = 1.
+fn bessel_j01_miller(x: f64) -> (f64, f64) {
+    let big = 1e250;
+    let start = 2 * ((x as usize + 30 + (10.0 * x.sqrt()) as usize) / 2);
+    let (mut next, mut cur, mut even_sum, mut j1) = (0.0, 1.0, 0.0, 0.0);
+    for k in (1..=start).rev() {
+        let prev = 2.0 * k as f64 / x * cur - next;
+        next = cur;
+        cur = prev;
+        if cur.abs() > big {
+            cur /= big;
+            next /= big;
+            even_sum /= big;
+            j1 /= big;
+        }
+        if (k - 1) % 2 == 0 {
+            even_sum += cur;
+        }
+        if k - 1 == 1 {
+            j1 = cur;
+        }
+    }
+    let norm = 2.0 * even_sum - cur;
+    (cur / norm, j1 / norm)
+}
+
+/// Hankel asymptotic expansion of Bessel function of order 'n' for large x.
+fn bessel_hankel(n: f64, x: f64) -> f64 {
+    let mu = 4.0 * n * n;
+    let (mut p, mut q, mut term) = (1.0, 0.0, 1.0);
+    for k in 1..30 {
+        let odd = (2 * k - 1) as f64;
+        let next = term * (mu - odd * odd) / (k as f64 * 8.0 * x);
+        if next.abs() > term.abs() {
+            break;
+        }
+        term = next;
+        match k % 4 {
+            1 => q += term,
+            2 => p -= term,
+            3 => q -= term,
+            _ => p += term,
+        }
+        if term.abs() < f64::EPSILON {
+            break;
+        }
+    }
+    let chi = x - (0.5 * n + 0.25) * PI;
+    (2.0 / (PI * x)).sqrt() * (p * chi.cos() - q * chi.sin())
+}
+
+/// Error function expression
+#[derive(Copy, Clone, Debug)]
+pub struct ErfXpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for ErfXpr<E>
+where
+    D: DualNumber<Scalar = f64>,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        let d1 = FRAC_2_SQRT_PI * (-val * val).exp();
+        other.chain(erf(val), d1, -2.0 * val * d1);
+    }
+}
+
+/// Complementary error function expression
+#[derive(Copy, Clone, Debug)]
+pub struct ErfcXpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for ErfcXpr<E>
+where
+    D: DualNumber<Scalar = f64>,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        let d1 = -FRAC_2_SQRT_PI * (-val * val).exp();
+        other.chain(erfc(val), d1, -2.0 * val * d1);
+    }
+}
+
+/// Gamma function expression
+#[derive(Copy, Clone, Debug)]
+pub struct GammaXpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for GammaXpr<E>
+where
+    D: DualNumber<Scalar = f64>,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        let (gamma, psi) = (gamma(val), digamma(val));
+        other.chain(gamma, gamma * psi, gamma * (psi * psi + trigamma(val)));
+    }
+}
+
+/// Logarithm of gamma function expression
+#[derive(Copy, Clone, Debug)]
+pub struct LnGammaXpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for LnGammaXpr<E>
+where
+    D: DualNumber<Scalar = f64>,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        other.chain(ln_gamma(val), digamma(val), trigamma(val));
+    }
+}
+
+/// Digamma function expression
+#[derive(Copy, Clone, Debug)]
+pub struct DigammaXpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for DigammaXpr<E>
+where
+    D: DualNumber<Scalar = f64>,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        other.chain(digamma(val), trigamma(val), tetragamma(val));
+    }
+}
+
+/// Bessel function of order zero expression
+#[derive(Copy, Clone, Debug)]
+pub struct BesselJ0Xpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for BesselJ0Xpr<E>
+where
+    D: DualNumber<Scalar = f64>,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        let (j0, j1) = bessel_j01(val);
+        other.chain(j0, -j1, -bessel_j1_der(val, j0, j1));
+    }
+}
+
+/// Bessel function of order one expression
+#[derive(Copy, Clone, Debug)]
+pub struct BesselJ1Xpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for BesselJ1Xpr<E>
+where
+    D: DualNumber<Scalar = f64>,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        let (j0, j1) = bessel_j01(val);
+        let d1 = bessel_j1_der(val, j0, j1);
+        let d2 = if val == 0.0 {
+            0.0
+        } else {
+            -j1 - d1 / val + j1 / (val * val)
+        };
+        other.chain(j1, d1, d2);
+    }
+}
+
+/// Derivative of Bessel function of order one, J1' = J0 - J1/x.
+fn bessel_j1_der(x: f64, j0: f64, j1: f64) -> f64 {
+    if x == 0.0 {
+        0.5
+    } else {
+        j0 - j1 / x
+    }
+}
+
+macro_rules! special_fn_dual(
+    ($op: ident, $Res: ident) => {
+        /// $op special function
+        pub fn $op(self) -> XprWrapper<$Res<Self>> { XprWrapper{xpr: $Res(UnXpr{ op: self })}}
+    };
+);
+
+macro_rules! special_fn_xpr(
+    ($op: ident, $Res: ident, $E: ident) => {
+        /// $op special function
+        pub fn $op(self) -> XprWrapper<$Res<$E>> { XprWrapper{xpr: $Res(UnXpr{ op: self.xpr })}}
+    };
+);
+
+/// Special functions are implemented for f64 scalars only.
+impl Dual<f64> {
+    special_fn_dual!(erf, ErfXpr);
+    special_fn_dual!(erfc, ErfcXpr);
+    special_fn_dual!(gamma, GammaXpr);
+    special_fn_dual!(ln_gamma, LnGammaXpr);
+    special_fn_dual!(digamma, DigammaXpr);
+    special_fn_dual!(bessel_j0, BesselJ0Xpr);
+    special_fn_dual!(bessel_j1, BesselJ1Xpr);
+}
+
+impl<const N: usize> DualN<N> {
+    special_fn_dual!(erf, ErfXpr);
+    special_fn_dual!(erfc, ErfcXpr);
+    special_fn_dual!(gamma, GammaXpr);
+    special_fn_dual!(ln_gamma, LnGammaXpr);
+    special_fn_dual!(digamma, DigammaXpr);
+    special_fn_dual!(bessel_j0, BesselJ0Xpr);
+    special_fn_dual!(bessel_j1, BesselJ1Xpr);
+}
+
+impl HyperDual {
+    special_fn_dual!(erf, ErfXpr);
+    special_fn_dual!(erfc, ErfcXpr);
+    special_fn_dual!(gamma, GammaXpr);
+    special_fn_dual!(ln_gamma, LnGammaXpr);
+    special_fn_dual!(digamma, DigammaXpr);
+    special_fn_dual!(bessel_j0, BesselJ0Xpr);
+    special_fn_dual!(bessel_j1, BesselJ1Xpr);
+}
+
+impl<E> XprWrapper<E> {
+    special_fn_xpr!(erf, ErfXpr, E);
+    special_fn_xpr!(erfc, ErfcXpr, E);
+    special_fn_xpr!(gamma, GammaXpr, E);
+    special_fn_xpr!(ln_gamma, LnGammaXpr, E);
+    special_fn_xpr!(digamma, DigammaXpr, E);
+    special_fn_xpr!(bessel_j0, BesselJ0Xpr, E);
+    special_fn_xpr!(bessel_j1, BesselJ1Xpr, E);
+}
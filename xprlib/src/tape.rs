@@ -0,0 +1,380 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Node of the tape. Keeps partial derivatives of
+/// operation result w.r.t. its (at most two) operands.
+#[derive(Clone, Copy, Debug)]
+struct Node {
+    /// Tape indices of operands.
+    deps: [usize; 2],
+    /// Partial derivatives w.r.t. operands.
+    weights: [f64; 2],
+}
+
+/// Wengert list which records operations made on Var numbers.
+/// Forward sweep is done while expression is evaluated, backward
+/// sweep is done by 'Var::grad'.
+#[derive(Debug, Default)]
+pub struct Tape {
+    /// 'nodes' - recorded operations, index of node is index of its result.
+    nodes: RefCell<Vec<Node>>,
+}
+
+impl Tape {
+    /// Create new empty tape.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create new independent variable on the tape.
+    ///
+    /// # Arguments
+    ///
+    /// 'val' - value of variable.
+    pub fn var(&self, val: f64) -> Var<'_> {
+        let idx = self.len();
+        Var {
+            tape: self,
+            idx: self.push([idx, idx], [0.0, 0.0]),
+            val,
+        }
+    }
+
+    /// Count of recorded nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.borrow().len()
+    }
+
+    /// Check if nothing is recorded.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.borrow().is_empty()
+    }
+
+    /// Backward sweep from weighted outputs. Adjoints of all
+    /// variables recorded before the last output are returned.
+    ///
+    /// # Arguments
+    ///
+    /// 'seeds' - tape indices of outputs and their initial adjoints.
+    pub(crate) fn backward(&self, seeds: &[(usize, f64)]) -> Adjoints {
+        let nodes = self.nodes.borrow();
+        let last = seeds.iter().map(|(idx, _)| *idx).max();
+        let mut adjoints = vec![0.0; last.map_or(0, |idx| idx + 1)];
+        for (idx, seed) in seeds {
+            adjoints[*idx] += seed;
+        }
+        for (i, node) in nodes[..adjoints.len()].iter().enumerate().rev() {
+            let adjoint = adjoints[i];
+            if adjoint == 0.0 {
+                continue;
+            }
+            // zero weight means no dependency (leaves, unary operations)
+            for (dep, weight) in node.deps.iter().zip(node.weights.iter()) {
+                if *weight != 0.0 {
+                    adjoints[*dep] += weight * adjoint;
+                }
+            }
+        }
+        Adjoints { adjoints }
+    }
+
+    /// Push node to the tape and return its index.
+    fn push(&self, deps: [usize; 2], weights: [f64; 2]) -> usize {
+        let mut nodes = self.nodes.borrow_mut();
+        nodes.push(Node { deps, weights });
+        nodes.len() - 1
+    }
+}
+
+/// Adjoints of all tape variables, result of backward sweep.
+#[derive(Clone, Debug)]
+pub struct Adjoints {
+    /// 'adjoints' - derivatives of output w.r.t. each tape node.
+    adjoints: Vec<f64>,
+}
+
+impl Adjoints {
+    /// Derivative of output w.r.t. variable.
+    ///
+    /// # Arguments
+    ///
+    /// 'var' - variable from the same tape.
+    pub fn wrt(&self, var: &Var) -> f64 {
+        // output doesn't depend on variables recorded after it
+        self.adjoints.get(var.idx).copied().unwrap_or(0.0)
+    }
+}
+
+/// Reverse mode variable. Each operation on variables
+/// is recorded on the tape they were created from.
+#[derive(Clone, Copy, Debug)]
+pub struct Var<'t> {
+    /// Tape variable belongs to.
+    tape: &'t Tape,
+    /// Index of variable on the tape.
+    pub(crate) idx: usize,
+    /// Value of variable.
+    val: f64,
+}
+
+impl<'t> Var<'t> {
+    /// value of variable
+    pub fn val(&self) -> f64 {
+        self.val
+    }
+
+    /// Backward sweep from this variable. Adjoints of
+    /// all variables recorded before it are returned.
+    pub fn grad(&self) -> Adjoints {
+        self.tape.backward(&[(self.idx, 1.0)])
+    }
+
+    /// Record unary operation result.
+    fn unary(self, val: f64, der: f64) -> Self {
+        Self {
+            tape: self.tape,
+            idx: self.tape.push([self.idx, self.idx], [der, 0.0]),
+            val,
+        }
+    }
+
+    /// Record binary operation result.
+    fn binary(self, other: Self, val: f64, ders: [f64; 2]) -> Self {
+        debug_assert!(
+            std::ptr::eq(self.tape, other.tape),
+            "variables belong to different tapes"
+        );
+        Self {
+            tape: self.tape,
+            idx: self.tape.push([self.idx, other.idx], ders),
+            val,
+        }
+    }
+
+    /// sin operation
+    pub fn sin(self) -> Self {
+        self.unary(self.val.sin(), self.val.cos())
+    }
+
+    /// cos operation
+    pub fn cos(self) -> Self {
+        self.unary(self.val.cos(), -self.val.sin())
+    }
+
+    /// sqrt operation
+    pub fn sqrt(self) -> Self {
+        let val = self.val.sqrt();
+        self.unary(val, 1.0 / (2.0 * val))
+    }
+
+    /// ln operation
+    pub fn ln(self) -> Self {
+        self.unary(self.val.ln(), 1.0 / self.val)
+    }
+
+    /// exp operation
+    pub fn exp(self) -> Self {
+        let val = self.val.exp();
+        self.unary(val, val)
+    }
+
+    /// powf operation
+    pub fn powf<Deg: Exponent<'t>>(self, deg: Deg) -> Self {
+        deg.powf_of(self)
+    }
+}
+
+/// Exponent of powf operation on Var, either constant or variable.
+pub trait Exponent<'t> {
+    /// Raise 'base' to the power of self.
+    fn powf_of(self, base: Var<'t>) -> Var<'t>;
+}
+
+impl<'t> Exponent<'t> for f64 {
+    fn powf_of(self, base: Var<'t>) -> Var<'t> {
+        let pow = base.val.powf(self - 1.0);
+        base.unary(pow * base.val, self * pow)
+    }
+}
+
+impl<'t> Exponent<'t> for Var<'t> {
+    fn powf_of(self, base: Var<'t>) -> Var<'t> {
+        let pow = base.val.powf(self.val - 1.0);
+        let val = pow * base.val;
+        base.binary(self, val, [self.val * pow, val * base.val.ln()])
+    }
+}
+
+impl<'t> Neg for Var<'t> {
+    type Output = Var<'t>;
+    fn neg(self) -> Self::Output {
+        self.unary(-self.val, -1.0)
+    }
+}
+
+impl<'t> Add for Var<'t> {
+    type Output = Var<'t>;
+    fn add(self, other: Var<'t>) -> Self::Output {
+        self.binary(other, self.val + other.val, [1.0, 1.0])
+    }
+}
+
+impl<'t> Sub for Var<'t> {
+    type Output = Var<'t>;
+    fn sub(self, other: Var<'t>) -> Self::Output {
+        self.binary(other, self.val - other.val, [1.0, -1.0])
+    }
+}
+
+impl<'t> Mul for Var<'t> {
+    type Output = Var<'t>;
+    fn mul(self, other: Var<'t>) -> Self::Output {
+        self.binary(other, self.val * other.val, [other.val, self.val])
+    }
+}
+
+impl<'t> Div for Var<'t> {
+    type Output = Var<'t>;
+    fn div(self, other: Var<'t>) -> Self::Output {
+        let val = self.val / other.val;
+        self.binary(other, val, [1.0 / other.val, -val / other.val])
+    }
+}
+
+impl<'t> Add<f64> for Var<'t> {
+    type Output = Var<'t>;
+    fn add(self, other: f64) -> Self::Output {
+        self.unary(self.val + other, 1.0)
+    }
+}
+
+impl<'t> Sub<f64> for Var<'t> {
+    type Output = Var<'t>;
+    fn sub(self, other: f64) -> Self::Output {
+        self.unary(self.val - other, 1.0)
+    }
+}
+
+impl<'t> Mul<f64> for Var<'t> {
+    type Output = Var<'t>;
+    fn mul(self, other: f64) -> Self::Output {
+        self.unary(self.val * other, other)
+    }
+}
+
+impl<'t> Div<f64> for Var<'t> {
+    type Output = Var<'t>;
+    fn div(self, other: f64) -> Self::Output {
+        self.unary(self.val / other, 1.0 / other)
+    }
+}
+
+impl<'t> Add<Var<'t>> for f64 {
+    type Output = Var<'t>;
+    fn add(self, other: Var<'t>) -> Self::Output {
+        other.unary(self + other.val, 1.0)
+    }
+}
+
+impl<'t> Sub<Var<'t>> for f64 {
+    type Output = Var<'t>;
+    fn sub(self, other: Var<'t>) -> Self::Output {
+        other.unary(self - other.val, -1.0)
+    }
+}
+
+impl<'t> Mul<Var<'t>> for f64 {
+    type Output = Var<'t>;
+    fn mul(self, other: Var<'t>) -> Self::Output {
+        other.unary(self * other.val, self)
+    }
+}
+
+impl<'t> Div<Var<'t>> for f64 {
+    type Output = Var<'t>;
+    fn div(self, other: Var<'t>) -> Self::Output {
+        let val = self / other.val;
+        other.unary(val, -val / other.val)
+    }
+}
+
+/// Implement operations on references through operations on values.
+macro_rules! impl_ref_op(
+    ($Op: ident, $op: ident, $L: ty, $R: ty) => {
+        impl<'l, 't> $Op<$R> for &'l $L {
+            type Output = Var<'t>;
+            fn $op(self, other: $R) -> Self::Output { (*self).$op(other) }
+        }
+        impl<'r, 't> $Op<&'r $R> for $L {
+            type Output = Var<'t>;
+            fn $op(self, other: &$R) -> Self::Output { self.$op(*other) }
+        }
+        impl<'l, 'r, 't> $Op<&'r $R> for &'l $L {
+            type Output = Var<'t>;
+            fn $op(self, other: &$R) -> Self::Output { (*self).$op(*other) }
+        }
+    }
+);
+
+impl_ref_op!(Add, add, Var<'t>, Var<'t>);
+impl_ref_op!(Sub, sub, Var<'t>, Var<'t>);
+impl_ref_op!(Mul, mul, Var<'t>, Var<'t>);
+impl_ref_op!(Div, div, Var<'t>, Var<'t>);
+impl_ref_op!(Add, add, Var<'t>, f64);
+impl_ref_op!(Sub, sub, Var<'t>, f64);
+impl_ref_op!(Mul, mul, Var<'t>, f64);
+impl_ref_op!(Div, div, Var<'t>, f64);
+impl_ref_op!(Add, add, f64, Var<'t>);
+impl_ref_op!(Sub, sub, f64, Var<'t>);
+impl_ref_op!(Mul, mul, f64, Var<'t>);
+impl_ref_op!(Div, div, f64, Var<'t>);
+
+macro_rules! impl_assign_op(
+    ($Op: ident, $op: ident, $fun: ident) => {
+        /// $Op operation Var to Var.
+        impl<'t> $Op for Var<'t> {
+            fn $op(&mut self, other: Var<'t>) { *self = self.$fun(other); }
+        }
+        /// $Op operation f64 to Var.
+        impl<'t> $Op<f64> for Var<'t> {
+            fn $op(&mut self, other: f64) { *self = self.$fun(other); }
+        }
+    }
+);
+
+impl_assign_op!(AddAssign, add_assign, add);
+impl_assign_op!(SubAssign, sub_assign, sub);
+impl_assign_op!(MulAssign, mul_assign, mul);
+impl_assign_op!(DivAssign, div_assign, div);
+
+impl<'t> PartialEq for Var<'t> {
+    fn eq(&self, other: &Var<'t>) -> bool {
+        self.val.eq(&other.val)
+    }
+}
+
+impl<'t> PartialOrd for Var<'t> {
+    fn partial_cmp(&self, other: &Var<'t>) -> Option<Ordering> {
+        self.val.partial_cmp(&other.val)
+    }
+}
+
+/// Value and gradient of scalar function evaluated in reverse mode,
+/// one forward sweep records the tape and one backward sweep
+/// returns all partial derivatives.
+///
+/// # Arguments
+///
+/// 'f' - function which gradient we want to eval.
+/// 'x' - point at which gradient is evaluated.
+pub fn grad<F>(f: F, x: &[f64]) -> (f64, Vec<f64>)
+where
+    F: for<'t> Fn(&[Var<'t>]) -> Var<'t>,
+{
+    let tape = Tape::new();
+    let vars: Vec<Var> = x.iter().map(|x| tape.var(*x)).collect();
+    let res = f(&vars);
+    let adjoints = res.grad();
+    (res.val(), vars.iter().map(|v| adjoints.wrt(v)).collect())
+}
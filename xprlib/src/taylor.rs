@@ -0,0 +1,273 @@
+use std::cmp::Ordering;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Truncated power series of order 'K'. Keeps K+1 normalized
+/// coefficients f^(k)/k!, so all derivatives up to order 'K'
+/// of univariate function come out of a single evaluation.
+#[derive(Clone, Copy, Debug)]
+pub struct Taylor<const K: usize> {
+    /// Value of series, zero order coefficient.
+    val: f64,
+    /// Normalized coefficients of order 1..=K.
+    der: [f64; K],
+}
+
+impl<const K: usize> Taylor<K> {
+    /// Create constant series.
+    ///
+    /// # Arguments
+    ///
+    /// 'val' - value of series.
+    pub fn new(val: f64) -> Self {
+        Self { val, der: [0.0; K] }
+    }
+
+    /// Create independent variable, first order coefficient is 1.0.
+    ///
+    /// # Arguments
+    ///
+    /// 'val' - value of variable.
+    pub fn variable(val: f64) -> Self {
+        let mut var = Self::new(val);
+        if K > 0 {
+            var.der[0] = 1.0;
+        }
+        var
+    }
+
+    /// value of series
+    pub fn val(&self) -> f64 {
+        self.val
+    }
+
+    /// Normalized coefficient of order 'k', f^(k)/k!.
+    pub fn coeff(&self, k: usize) -> f64 {
+        if k == 0 {
+            self.val
+        } else {
+            self.der[k - 1]
+        }
+    }
+
+    /// Derivative of order 'k'.
+    pub fn derivative(&self, k: usize) -> f64 {
+        (1..=k).fold(self.coeff(k), |d, i| d * i as f64)
+    }
+
+    /// All derivatives f, f', ..., f^(K).
+    pub fn derivatives(&self) -> Vec<f64> {
+        (0..=K).map(|k| self.derivative(k)).collect()
+    }
+
+    /// Set normalized coefficient of order 'k'.
+    fn set(&mut self, k: usize, val: f64) {
+        if k == 0 {
+            self.val = val;
+        } else {
+            self.der[k - 1] = val;
+        }
+    }
+
+    /// Normalized coefficient of order 'k' of product of series.
+    ///
+    /// # Arguments
+    ///
+    /// 'other' - second factor.
+    /// 'k' - order of coefficient.
+    fn convolve(&self, other: &Self, k: usize) -> f64 {
+        (0..=k).map(|j| self.coeff(j) * other.coeff(k - j)).sum()
+    }
+
+    /// Check if series is constant.
+    fn is_constant(&self) -> bool {
+        self.der.iter().all(|d| *d == 0.0)
+    }
+
+    /// sin operation
+    pub fn sin(self) -> Self {
+        self.sin_cos().0
+    }
+
+    /// cos operation
+    pub fn cos(self) -> Self {
+        self.sin_cos().1
+    }
+
+    /// sin and cos series are computed together.
+    fn sin_cos(self) -> (Self, Self) {
+        let mut sin = Self::new(self.val.sin());
+        let mut cos = Self::new(self.val.cos());
+        for k in 1..=K {
+            let (mut s, mut c) = (0.0, 0.0);
+            for j in 1..=k {
+                s += j as f64 * self.coeff(j) * cos.coeff(k - j);
+                c -= j as f64 * self.coeff(j) * sin.coeff(k - j);
+            }
+            sin.set(k, s / k as f64);
+            cos.set(k, c / k as f64);
+        }
+        (sin, cos)
+    }
+
+    /// sqrt operation
+    pub fn sqrt(self) -> Self {
+        let mut res = Self::new(self.val.sqrt());
+        for k in 1..=K {
+            let sum: f64 = (1..k).map(|j| res.coeff(j) * res.coeff(k - j)).sum();
+            res.set(k, (self.coeff(k) - sum) / (2.0 * res.val));
+        }
+        res
+    }
+
+    /// ln operation
+    pub fn ln(self) -> Self {
+        let mut res = Self::new(self.val.ln());
+        for k in 1..=K {
+            let sum: f64 = (1..k)
+                .map(|j| j as f64 * res.coeff(j) * self.coeff(k - j))
+                .sum();
+            res.set(k, (self.coeff(k) - sum / k as f64) / self.val);
+        }
+        res
+    }
+
+    /// exp operation
+    pub fn exp(self) -> Self {
+        let mut res = Self::new(self.val.exp());
+        for k in 1..=K {
+            let sum: f64 = (1..=k)
+                .map(|j| j as f64 * self.coeff(j) * res.coeff(k - j))
+                .sum();
+            res.set(k, sum / k as f64);
+        }
+        res
+    }
+
+    /// powf operation, exponent is f64 or Taylor series.
+    pub fn powf<Deg: Into<Self>>(self, deg: Deg) -> Self {
+        let deg = deg.into();
+        if !deg.is_constant() {
+            return (deg * self.ln()).exp();
+        }
+        let deg = deg.val;
+        if self.val == 0.0 && deg >= 0.0 && deg.fract() == 0.0 {
+            // recurrence below divides by value, series without constant term
+            // raised to power above K has no terms up to order K
+            if deg > K as f64 {
+                return Self::new(0.0);
+            }
+            return (0..deg as usize).fold(Self::new(1.0), |res, _| res * self);
+        }
+        let mut res = Self::new(self.val.powf(deg));
+        for k in 1..=K {
+            let sum: f64 = (1..=k)
+                .map(|j| ((deg + 1.0) * j as f64 - k as f64) * self.coeff(j) * res.coeff(k - j))
+                .sum();
+            res.set(k, sum / (k as f64 * self.val));
+        }
+        res
+    }
+}
+
+impl<const K: usize> From<f64> for Taylor<K> {
+    fn from(val: f64) -> Self {
+        Self::new(val)
+    }
+}
+
+impl<const K: usize> Neg for Taylor<K> {
+    type Output = Taylor<K>;
+    fn neg(mut self) -> Self::Output {
+        self.val = -self.val;
+        self.der.iter_mut().for_each(|d| *d = -*d);
+        self
+    }
+}
+
+impl<const K: usize> Add for Taylor<K> {
+    type Output = Taylor<K>;
+    fn add(mut self, other: Taylor<K>) -> Self::Output {
+        self.val += other.val;
+        self.der.iter_mut().zip(other.der.iter()).for_each(|(l, r)| *l += r);
+        self
+    }
+}
+
+impl<const K: usize> Sub for Taylor<K> {
+    type Output = Taylor<K>;
+    fn sub(self, other: Taylor<K>) -> Self::Output {
+        self + (-other)
+    }
+}
+
+impl<const K: usize> Mul for Taylor<K> {
+    type Output = Taylor<K>;
+    fn mul(self, other: Taylor<K>) -> Self::Output {
+        let mut res = Self::new(self.val * other.val);
+        for k in 1..=K {
+            res.set(k, self.convolve(&other, k));
+        }
+        res
+    }
+}
+
+impl<const K: usize> Div for Taylor<K> {
+    type Output = Taylor<K>;
+    fn div(self, other: Taylor<K>) -> Self::Output {
+        let mut res = Self::new(self.val / other.val);
+        for k in 1..=K {
+            let sum: f64 = (1..=k).map(|j| other.coeff(j) * res.coeff(k - j)).sum();
+            res.set(k, (self.coeff(k) - sum) / other.val);
+        }
+        res
+    }
+}
+
+/// Implement operations with f64 through constant series.
+macro_rules! impl_f64_op(
+    ($Op: ident, $op: ident) => {
+        impl<const K: usize> $Op<f64> for Taylor<K> {
+            type Output = Taylor<K>;
+            fn $op(self, other: f64) -> Self::Output { self.$op(Taylor::from(other)) }
+        }
+        impl<const K: usize> $Op<Taylor<K>> for f64 {
+            type Output = Taylor<K>;
+            fn $op(self, other: Taylor<K>) -> Self::Output { Taylor::from(self).$op(other) }
+        }
+    }
+);
+
+impl_f64_op!(Add, add);
+impl_f64_op!(Sub, sub);
+impl_f64_op!(Mul, mul);
+impl_f64_op!(Div, div);
+
+macro_rules! impl_assign_op(
+    ($Op: ident, $op: ident, $fun: ident) => {
+        /// $Op operation Taylor to Taylor.
+        impl<const K: usize> $Op for Taylor<K> {
+            fn $op(&mut self, other: Taylor<K>) { *self = self.$fun(other); }
+        }
+        /// $Op operation f64 to Taylor.
+        impl<const K: usize> $Op<f64> for Taylor<K> {
+            fn $op(&mut self, other: f64) { *self = self.$fun(other); }
+        }
+    }
+);
+
+impl_assign_op!(AddAssign, add_assign, add);
+impl_assign_op!(SubAssign, sub_assign, sub);
+impl_assign_op!(MulAssign, mul_assign, mul);
+impl_assign_op!(DivAssign, div_assign, div);
+
+impl<const K: usize> PartialEq for Taylor<K> {
+    fn eq(&self, other: &Taylor<K>) -> bool {
+        self.val.eq(&other.val)
+    }
+}
+
+impl<const K: usize> PartialOrd for Taylor<K> {
+    fn partial_cmp(&self, other: &Taylor<K>) -> Option<Ordering> {
+        self.val.partial_cmp(&other.val)
+    }
+}
@@ -2,7 +2,13 @@ mod dual_expression;
 pub(crate) mod expression;
 mod assign_op;
 mod constant;
+mod dyn_xpr;
 mod binary_fn;
 mod binary_op;
+mod print;
 mod unary_fn;
 mod unary_op;
+mod visit;
+
+pub use dyn_xpr::{DynNode, DynXpr};
+pub use visit::{Operation, Visit, Visitor};
@@ -1,21 +1,31 @@
 use std::ops::{AddAssign, SubAssign, MulAssign, DivAssign};
 use super::expression::{Xpr, XprWrapper};
-use crate::dual::Dual;
+use crate::{dual::Dual, dual_n::DualN, hyper_dual::HyperDual, real::Real};
 
 macro_rules! impl_assign_op(
-    ($Op: ident, $op: ident, $fun: ident) => {
+    ($Op: ident, $op: ident, $fun: ident, [$($gen: tt)*] $D: ty) => {
         /// $Op operation Dual to Dual number.
-        impl $Op for Dual {
-            fn $op(&mut self, other: Dual) { other.$fun(self); }
+        impl<$($gen)*> $Op for $D {
+            fn $op(&mut self, other: $D) { other.$fun(self); }
         }
         /// $Op operation XprWrapper to Dual number.
-        impl<E: Xpr> $Op<XprWrapper<E>> for Dual {
+        impl<E: Xpr<$D>, $($gen)*> $Op<XprWrapper<E>> for $D {
             fn $op(&mut self, other: XprWrapper<E>) { other.xpr.$fun(self); }
         }
     }
 );
 
-impl_assign_op!(AddAssign, add_assign, assign_add);
-impl_assign_op!(SubAssign, sub_assign, assign_sub);
-impl_assign_op!(MulAssign, mul_assign, assign_mul);
-impl_assign_op!(DivAssign, div_assign, assign_div);
+impl_assign_op!(AddAssign, add_assign, assign_add, [T: Real] Dual<T>);
+impl_assign_op!(SubAssign, sub_assign, assign_sub, [T: Real] Dual<T>);
+impl_assign_op!(MulAssign, mul_assign, assign_mul, [T: Real] Dual<T>);
+impl_assign_op!(DivAssign, div_assign, assign_div, [T: Real] Dual<T>);
+
+impl_assign_op!(AddAssign, add_assign, assign_add, [const N: usize] DualN<N>);
+impl_assign_op!(SubAssign, sub_assign, assign_sub, [const N: usize] DualN<N>);
+impl_assign_op!(MulAssign, mul_assign, assign_mul, [const N: usize] DualN<N>);
+impl_assign_op!(DivAssign, div_assign, assign_div, [const N: usize] DualN<N>);
+
+impl_assign_op!(AddAssign, add_assign, assign_add, [] HyperDual);
+impl_assign_op!(SubAssign, sub_assign, assign_sub, [] HyperDual);
+impl_assign_op!(MulAssign, mul_assign, assign_mul, [] HyperDual);
+impl_assign_op!(DivAssign, div_assign, assign_div, [] HyperDual);
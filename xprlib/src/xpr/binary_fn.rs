@@ -1,47 +1,223 @@
 use super::expression::{BinXpr, Xpr, XprWrapper};
-use crate::dual::Dual;
+use crate::{
+    dual::{Dual, DualNumber},
+    dual_n::DualN,
+    hyper_dual::HyperDual,
+    real::Real,
+};
 
 /// Powf expression structure which holds binary expression.
 #[derive(Copy, Clone, Debug)]
-pub struct PowfXpr<L, R>(BinXpr<L, R>)
-where
-    L: Xpr,
-    R: Xpr;
+pub struct PowfXpr<L, R>(pub(crate) BinXpr<L, R>);
 
 /// Implement Xpr trait for PowfXpr.
-impl<L: Xpr, R: Xpr> Xpr for PowfXpr<L, R> {
-    fn assign(&self, target: &mut Dual) {
+impl<D: DualNumber, L: Xpr<D>, R: Xpr<D>> Xpr<D> for PowfXpr<L, R> {
+    fn assign(&self, target: &mut D) {
         self.0.l.assign(target);
         self.0.r.assign_pow(target);
     }
 }
 
-impl Dual {
-    pub fn powf<R, Deg>(self, deg: Deg) -> XprWrapper<PowfXpr<Dual, R>>
-    where
-        R: Xpr,
-        Deg: Into<XprWrapper<R>>,
-    {
-        XprWrapper {
-            xpr: PowfXpr(BinXpr {
-                l: self,
-                r: deg.into().xpr,
-            }),
-        }
+/// Atan2 expression, left part is ordinate and right part is abscissa.
+#[derive(Copy, Clone, Debug)]
+pub struct Atan2Xpr<L, R>(pub(crate) BinXpr<L, R>);
+
+/// Implement Xpr trait for Atan2Xpr.
+impl<D: DualNumber, L: Xpr<D>, R: Xpr<D>> Xpr<D> for Atan2Xpr<L, R> {
+    fn assign(&self, target: &mut D) {
+        let mut other = D::zero();
+        self.0.r.assign(&mut other);
+        self.0.l.assign(target);
+        let (y, x) = (target.value(), other.value());
+        let sq = x.mul(x).add(y.mul(y));
+        let sq2 = sq.mul(sq);
+        let two = D::Scalar::from_f64(2.0);
+        target.chain_bin(
+            &other,
+            y.atan2(x),
+            (x.div(sq), y.neg().div(sq)),
+            (
+                two.mul(x).mul(y).neg().div(sq2),
+                y.mul(y).sub(x.mul(x)).div(sq2),
+                two.mul(x).mul(y).div(sq2),
+            ),
+        );
     }
 }
 
-impl<L: Xpr> XprWrapper<L> {
-    pub fn powf<R, Base>(self, deg: Base) -> XprWrapper<PowfXpr<L, R>>
-    where
-        R: Xpr,
-        Base: Into<XprWrapper<R>>,
-    {
-        XprWrapper {
-            xpr: PowfXpr(BinXpr {
-                l: self.xpr,
-                r: deg.into().xpr,
-            }),
-        }
+/// Hypot expression, length of hypotenuse of right triangle.
+#[derive(Copy, Clone, Debug)]
+pub struct HypotXpr<L, R>(pub(crate) BinXpr<L, R>);
+
+/// Implement Xpr trait for HypotXpr.
+impl<D: DualNumber, L: Xpr<D>, R: Xpr<D>> Xpr<D> for HypotXpr<L, R> {
+    fn assign(&self, target: &mut D) {
+        let mut other = D::zero();
+        self.0.r.assign(&mut other);
+        self.0.l.assign(target);
+        let (l, r) = (target.value(), other.value());
+        let hypot = l.hypot(r);
+        let cube = hypot.mul(hypot).mul(hypot);
+        target.chain_bin(
+            &other,
+            hypot,
+            (l.div(hypot), r.div(hypot)),
+            (r.mul(r).div(cube), l.mul(r).neg().div(cube), l.mul(l).div(cube)),
+        );
+    }
+}
+
+/// Min expression, if parts are equal derivative is average of parts derivatives.
+#[derive(Copy, Clone, Debug)]
+pub struct MinXpr<L, R>(pub(crate) BinXpr<L, R>);
+
+/// Implement Xpr trait for MinXpr.
+impl<D: DualNumber, L: Xpr<D>, R: Xpr<D>> Xpr<D> for MinXpr<L, R> {
+    fn assign(&self, target: &mut D) {
+        let mut other = D::zero();
+        self.0.r.assign(&mut other);
+        self.0.l.assign(target);
+        let (l, r) = (target.value(), other.value());
+        let val = l.min(r);
+        target.chain_bin(&other, val, D::Scalar::select(l, r, val), zero3::<D>());
+    }
+}
+
+/// Max expression, if parts are equal derivative is average of parts derivatives.
+#[derive(Copy, Clone, Debug)]
+pub struct MaxXpr<L, R>(pub(crate) BinXpr<L, R>);
+
+/// Implement Xpr trait for MaxXpr.
+impl<D: DualNumber, L: Xpr<D>, R: Xpr<D>> Xpr<D> for MaxXpr<L, R> {
+    fn assign(&self, target: &mut D) {
+        let mut other = D::zero();
+        self.0.r.assign(&mut other);
+        self.0.l.assign(target);
+        let (l, r) = (target.value(), other.value());
+        let val = l.max(r);
+        target.chain_bin(&other, val, D::Scalar::select(l, r, val), zero3::<D>());
+    }
+}
+
+/// Logarithm expression, left part is argument and right part is base.
+#[derive(Copy, Clone, Debug)]
+pub struct LogXpr<L, R>(pub(crate) BinXpr<L, R>);
+
+/// Implement Xpr trait for LogXpr.
+impl<D: DualNumber, L: Xpr<D>, R: Xpr<D>> Xpr<D> for LogXpr<L, R> {
+    fn assign(&self, target: &mut D) {
+        let mut other = D::zero();
+        self.0.r.assign(&mut other);
+        self.0.l.assign(target);
+        let (l, r) = (target.value(), other.value());
+        let (ln_l, ln_r) = (l.ln(), r.ln());
+        let dl = l.mul(ln_r).recip();
+        let dr = ln_l.neg().div(r.mul(ln_r).mul(ln_r));
+        target.chain_bin(
+            &other,
+            ln_l.div(ln_r),
+            (dl, dr),
+            (
+                dl.div(l).neg(),
+                dl.div(r.mul(ln_r)).neg(),
+                dr.mul(ln_r.add(D::Scalar::from_f64(2.0))).div(r.mul(ln_r)).neg(),
+            ),
+        );
+    }
+}
+
+/// Copysign expression, magnitude of left part with sign of right part.
+#[derive(Copy, Clone, Debug)]
+pub struct CopysignXpr<L, R>(pub(crate) BinXpr<L, R>);
+
+/// Implement Xpr trait for CopysignXpr.
+impl<D: DualNumber, L: Xpr<D>, R: Xpr<D>> Xpr<D> for CopysignXpr<L, R> {
+    fn assign(&self, target: &mut D) {
+        let mut other = D::zero();
+        self.0.r.assign(&mut other);
+        self.0.l.assign(target);
+        let (l, r) = (target.value(), other.value());
+        let val = l.copysign(r);
+        let dl = val.signum().mul(l.signum());
+        target.chain_bin(&other, val, (dl, D::Scalar::zero()), zero3::<D>());
     }
 }
+
+/// Zero second order derivatives of piecewise linear functions.
+fn zero3<D: DualNumber>() -> (D::Scalar, D::Scalar, D::Scalar) {
+    (D::Scalar::zero(), D::Scalar::zero(), D::Scalar::zero())
+}
+
+macro_rules! bin_fn_dual(
+    ($op: ident, $Res: ident) => {
+        /// $op operation
+        pub fn $op<R, Other>(self, other: Other) -> XprWrapper<$Res<Self, R>>
+        where
+            Other: Into<XprWrapper<R>>,
+        {
+            XprWrapper {
+                xpr: $Res(BinXpr {
+                    l: self,
+                    r: other.into().xpr,
+                }),
+            }
+        }
+    };
+);
+
+macro_rules! bin_fn_xpr(
+    ($op: ident, $Res: ident, $L: ident) => {
+        /// $op operation
+        pub fn $op<R, Other>(self, other: Other) -> XprWrapper<$Res<$L, R>>
+        where
+            Other: Into<XprWrapper<R>>,
+        {
+            XprWrapper {
+                xpr: $Res(BinXpr {
+                    l: self.xpr,
+                    r: other.into().xpr,
+                }),
+            }
+        }
+    };
+);
+
+impl<T: Real> Dual<T> {
+    bin_fn_dual!(powf, PowfXpr);
+    bin_fn_dual!(atan2, Atan2Xpr);
+    bin_fn_dual!(hypot, HypotXpr);
+    bin_fn_dual!(min, MinXpr);
+    bin_fn_dual!(max, MaxXpr);
+    bin_fn_dual!(log, LogXpr);
+    bin_fn_dual!(copysign, CopysignXpr);
+}
+
+impl<const N: usize> DualN<N> {
+    bin_fn_dual!(powf, PowfXpr);
+    bin_fn_dual!(atan2, Atan2Xpr);
+    bin_fn_dual!(hypot, HypotXpr);
+    bin_fn_dual!(min, MinXpr);
+    bin_fn_dual!(max, MaxXpr);
+    bin_fn_dual!(log, LogXpr);
+    bin_fn_dual!(copysign, CopysignXpr);
+}
+
+impl HyperDual {
+    bin_fn_dual!(powf, PowfXpr);
+    bin_fn_dual!(atan2, Atan2Xpr);
+    bin_fn_dual!(hypot, HypotXpr);
+    bin_fn_dual!(min, MinXpr);
+    bin_fn_dual!(max, MaxXpr);
+    bin_fn_dual!(log, LogXpr);
+    bin_fn_dual!(copysign, CopysignXpr);
+}
+
+impl<L> XprWrapper<L> {
+    bin_fn_xpr!(powf, PowfXpr, L);
+    bin_fn_xpr!(atan2, Atan2Xpr, L);
+    bin_fn_xpr!(hypot, HypotXpr, L);
+    bin_fn_xpr!(min, MinXpr, L);
+    bin_fn_xpr!(max, MaxXpr, L);
+    bin_fn_xpr!(log, LogXpr, L);
+    bin_fn_xpr!(copysign, CopysignXpr, L);
+}
@@ -2,346 +2,431 @@ use super::{
     constant::{constant, ConstantXpr},
     expression::{BinXpr, Xpr, XprWrapper},
 };
-use crate::dual::Dual;
+use crate::{
+    dual::{Dual, DualNumber},
+    dual_n::DualN,
+    hyper_dual::HyperDual,
+    real::Real,
+};
 use std::ops::{Add, Div, Mul, Sub};
 
 /// Add expression structure which holds binary expression.
 #[derive(Copy, Clone, Debug)]
-pub struct AddXpr<L, R>(BinXpr<L, R>)
-where
-    L: Xpr,
-    R: Xpr;
+pub struct AddXpr<L, R>(pub(crate) BinXpr<L, R>);
 
 /// Implement Xpr trait for AddXpr
-impl<L, R> Xpr for AddXpr<L, R>
+impl<D, L, R> Xpr<D> for AddXpr<L, R>
 where
-    L: Xpr,
-    R: Xpr,
+    D: DualNumber,
+    L: Xpr<D>,
+    R: Xpr<D>,
 {
-    fn assign(&self, target: &mut Dual) {
-        self.0.l.assign(target);
-        self.0.r.assign_add(target);
+    fn assign(&self, target: &mut D) {
+        match (self.0.l.as_constant(), self.0.r.as_constant()) {
+            (Some(l), Some(r)) => constant(l + r).assign(target),
+            (Some(l), None) => {
+                self.0.r.assign(target);
+                constant(l).assign_add(target);
+            }
+            _ => {
+                self.0.l.assign(target);
+                self.0.r.assign_add(target);
+            }
+        }
     }
 
-    fn assign_add(&self, target: &mut Dual) {
+    fn assign_add(&self, target: &mut D) {
         self.0.l.assign_add(target);
         self.0.r.assign_add(target);
     }
 
-    fn assign_sub(&self, target: &mut Dual) {
+    fn assign_sub(&self, target: &mut D) {
         self.0.l.assign_sub(target);
         self.0.r.assign_sub(target);
     }
 
-    fn assign_mul(&self, target: &mut Dual) {
-        let mut aux: Dual = *target;
+    fn assign_mul(&self, target: &mut D) {
+        if let Some(val) = self.as_constant() {
+            return constant(val).assign_mul(target);
+        }
+        let mut aux: D = *target;
         self.0.l.assign_mul(target);
         self.0.r.assign_mul(&mut aux);
         aux.assign_add(target);
     }
+
+    fn as_constant(&self) -> Option<f64> {
+        Some(self.0.l.as_constant()? + self.0.r.as_constant()?)
+    }
 }
 
 /// Sub expression structure which holds binary expression.
 #[derive(Copy, Clone, Debug)]
-pub struct SubXpr<L, R>(BinXpr<L, R>)
-where
-    L: Xpr,
-    R: Xpr;
+pub struct SubXpr<L, R>(pub(crate) BinXpr<L, R>);
 
 /// Implement Xpr trait for SubXpr
-impl<L, R> Xpr for SubXpr<L, R> 
+impl<D, L, R> Xpr<D> for SubXpr<L, R>
 where
-    L: Xpr,
-    R: Xpr,
+    D: DualNumber,
+    L: Xpr<D>,
+    R: Xpr<D>,
 {
-    fn assign(&self, target: &mut Dual) {
-        self.0.l.assign(target);
-        self.0.r.assign_sub(target);
+    fn assign(&self, target: &mut D) {
+        match (self.0.l.as_constant(), self.0.r.as_constant()) {
+            (Some(l), Some(r)) => constant(l - r).assign(target),
+            (Some(l), None) => {
+                self.0.r.assign_neg(target);
+                constant(l).assign_add(target);
+            }
+            _ => {
+                self.0.l.assign(target);
+                self.0.r.assign_sub(target);
+            }
+        }
     }
 
-    fn assign_add(&self, target: &mut Dual) {
+    fn assign_add(&self, target: &mut D) {
         self.0.l.assign_add(target);
         self.0.r.assign_sub(target);
     }
 
-    fn assign_sub(&self, target: &mut Dual) {
+    fn assign_sub(&self, target: &mut D) {
         self.0.l.assign_sub(target);
         self.0.r.assign_add(target);
     }
 
-    fn assign_mul(&self, target: &mut Dual) {
-        let mut aux: Dual = *target;
+    fn assign_mul(&self, target: &mut D) {
+        if let Some(val) = self.as_constant() {
+            return constant(val).assign_mul(target);
+        }
+        let mut aux: D = *target;
         self.0.l.assign_mul(target);
         self.0.r.assign_mul(&mut aux);
         aux.assign_sub(target);
     }
+
+    fn as_constant(&self) -> Option<f64> {
+        Some(self.0.l.as_constant()? - self.0.r.as_constant()?)
+    }
 }
 
 /// Mul expression structure which holds binary expression.
 #[derive(Copy, Clone, Debug)]
-pub struct MulXpr<L, R>(BinXpr<L, R>)
-where
-    L: Xpr,
-    R: Xpr;
+pub struct MulXpr<L, R>(pub(crate) BinXpr<L, R>);
 
 /// Implement Xpr trait for MulXpr
-impl<L, R> Xpr for MulXpr<L, R> 
+impl<D, L, R> Xpr<D> for MulXpr<L, R>
 where
-    L: Xpr,
-    R: Xpr,
+    D: DualNumber,
+    L: Xpr<D>,
+    R: Xpr<D>,
 {
-    fn assign(&self, target: &mut Dual) {
-        self.0.l.assign(target);
-        self.0.r.assign_mul(target);
+    fn assign(&self, target: &mut D) {
+        match (self.0.l.as_constant(), self.0.r.as_constant()) {
+            (Some(l), Some(r)) => constant(l * r).assign(target),
+            (Some(l), None) => {
+                self.0.r.assign(target);
+                constant(l).assign_mul(target);
+            }
+            _ => {
+                self.0.l.assign(target);
+                self.0.r.assign_mul(target);
+            }
+        }
     }
 
-    fn assign_mul(&self, target: &mut Dual) {
+    fn assign_mul(&self, target: &mut D) {
+        if let Some(val) = self.as_constant() {
+            return constant(val).assign_mul(target);
+        }
         self.0.l.assign_mul(target);
         self.0.r.assign_mul(target);
     }
+
+    fn as_constant(&self) -> Option<f64> {
+        Some(self.0.l.as_constant()? * self.0.r.as_constant()?)
+    }
 }
 
 /// Div expression structure which holds binary expression.
 #[derive(Copy, Clone, Debug)]
-pub struct DivXpr<L, R>(BinXpr<L, R>)
-where
-    L: Xpr,
-    R: Xpr;
+pub struct DivXpr<L, R>(pub(crate) BinXpr<L, R>);
 
 /// Implement Xpr trait for DivXpr
-impl<L, R> Xpr for DivXpr<L, R> 
+impl<D, L, R> Xpr<D> for DivXpr<L, R>
 where
-    L: Xpr,
-    R: Xpr,
+    D: DualNumber,
+    L: Xpr<D>,
+    R: Xpr<D>,
 {
-    fn assign(&self, target: &mut Dual) {
-        self.0.l.assign(target);
-        self.0.r.assign_div(target);
+    fn assign(&self, target: &mut D) {
+        match (self.0.l.as_constant(), self.0.r.as_constant()) {
+            (Some(l), Some(r)) => constant(l / r).assign(target),
+            (Some(l), None) => {
+                // l / r is applied as scalar function of r
+                self.0.r.assign(target);
+                let val = target.value();
+                let div = D::Scalar::from_f64(l).div(val);
+                let d1 = div.neg().div(val);
+                target.chain(div, d1, d1.mul(D::Scalar::from_f64(-2.0)).div(val));
+            }
+            _ => {
+                self.0.l.assign(target);
+                self.0.r.assign_div(target);
+            }
+        }
     }
 
-    fn assign_mul(&self, target: &mut Dual) {
+    fn assign_mul(&self, target: &mut D) {
+        if let Some(val) = self.as_constant() {
+            return constant(val).assign_mul(target);
+        }
         self.0.l.assign_mul(target);
         self.0.r.assign_div(target);
     }
+
+    fn as_constant(&self) -> Option<f64> {
+        Some(self.0.l.as_constant()? / self.0.r.as_constant()?)
+    }
 }
 
-macro_rules! impl_bin_op(
-    ($Op: ident, $op: ident, $Res: ident) => {
-        impl $Op for Dual {
-            type Output = XprWrapper<$Res<Dual, Dual>>;
-            fn $op(self, other: Dual) -> Self::Output {
+/// Implement binary operator for dual number type '$D'
+/// combined with itself and expressions.
+macro_rules! impl_dual_bin_op(
+    ($Op: ident, $op: ident, $Res: ident, [$($gen: tt)*] $D: ty) => {
+        impl<$($gen)*> $Op for $D {
+            type Output = XprWrapper<$Res<$D, $D>>;
+            fn $op(self, other: $D) -> Self::Output {
                 Self::Output{xpr: $Res(BinXpr{l: self, r: other})}
             }
         }
 
-        impl<'l> $Op<Dual> for &'l Dual {
-            type Output = XprWrapper<$Res<Dual, Dual>>;
-            fn $op(self, other: Dual) -> Self::Output {
-                Self::Output{xpr: $Res(BinXpr{l: self.clone(), r: other})}
+        impl<'l, $($gen)*> $Op<$D> for &'l $D {
+            type Output = XprWrapper<$Res<$D, $D>>;
+            fn $op(self, other: $D) -> Self::Output {
+                Self::Output{xpr: $Res(BinXpr{l: *self, r: other})}
             }
         }
-        impl<'r> $Op<&'r Dual> for Dual {
-            type Output = XprWrapper<$Res<Dual, Dual>>;
-            fn $op(self, other: &Dual) -> Self::Output {
-                Self::Output{xpr: $Res(BinXpr{l: self, r: other.clone()})}
+        impl<'r, $($gen)*> $Op<&'r $D> for $D {
+            type Output = XprWrapper<$Res<$D, $D>>;
+            fn $op(self, other: &$D) -> Self::Output {
+                Self::Output{xpr: $Res(BinXpr{l: self, r: *other})}
             }
         }
 
-        impl<'l, 'r> $Op<&'r Dual> for &'l Dual {
-            type Output = XprWrapper<$Res<Dual, Dual>>;
-            fn $op(self, other: &Dual) -> Self::Output {
-                Self::Output{xpr: $Res(BinXpr{l: self.clone(), r: other.clone()})}
+        impl<'l, 'r, $($gen)*> $Op<&'r $D> for &'l $D {
+            type Output = XprWrapper<$Res<$D, $D>>;
+            fn $op(self, other: &$D) -> Self::Output {
+                Self::Output{xpr: $Res(BinXpr{l: *self, r: *other})}
             }
         }
 
-        impl<R: Xpr> $Op<XprWrapper<R>> for Dual {
-            type Output = XprWrapper<$Res<Dual, R>>;
+        impl<R, $($gen)*> $Op<XprWrapper<R>> for $D {
+            type Output = XprWrapper<$Res<$D, R>>;
             fn $op(self, other: XprWrapper<R>) -> Self::Output {
                 Self::Output{xpr: $Res(BinXpr{l: self, r: other.xpr})}
             }
         }
-        impl<'l, R: Xpr> $Op<XprWrapper<R>> for &'l Dual {
-            type Output = XprWrapper<$Res<Dual, R>>;
+        impl<'l, R, $($gen)*> $Op<XprWrapper<R>> for &'l $D {
+            type Output = XprWrapper<$Res<$D, R>>;
             fn $op(self, other: XprWrapper<R>) -> Self::Output {
-                Self::Output{xpr: $Res(BinXpr{l: self.clone(), r: other.xpr})}
+                Self::Output{xpr: $Res(BinXpr{l: *self, r: other.xpr})}
             }
         }
 
-        impl<'r, R: Xpr> $Op<&'r XprWrapper<R>> for Dual {
-            type Output = XprWrapper<$Res<Dual, R>>;
+        impl<'r, R: Copy, $($gen)*> $Op<&'r XprWrapper<R>> for $D {
+            type Output = XprWrapper<$Res<$D, R>>;
             fn $op(self, other: &XprWrapper<R>) -> Self::Output {
                 Self::Output{xpr: $Res(BinXpr{l: self, r: other.xpr})}
             }
         }
 
-        impl<'l, 'r, R: Xpr> $Op<&'r XprWrapper<R>> for &'l Dual {
-            type Output = XprWrapper<$Res<Dual, R>>;
+        impl<'l, 'r, R: Copy, $($gen)*> $Op<&'r XprWrapper<R>> for &'l $D {
+            type Output = XprWrapper<$Res<$D, R>>;
             fn $op(self, other: &XprWrapper<R>) -> Self::Output {
-                Self::Output{xpr: $Res(BinXpr{l: self.clone(), r: other.xpr})}
+                Self::Output{xpr: $Res(BinXpr{l: *self, r: other.xpr})}
             }
         }
 
-        impl<L: Xpr> $Op<Dual> for XprWrapper<L> {
-            type Output = XprWrapper<$Res<L, Dual>>;
-            fn $op(self, other: Dual) -> Self::Output {
+        impl<L, $($gen)*> $Op<$D> for XprWrapper<L> {
+            type Output = XprWrapper<$Res<L, $D>>;
+            fn $op(self, other: $D) -> Self::Output {
                 Self::Output{xpr: $Res(BinXpr{l: self.xpr, r: other})}
             }
         }
-        impl<'r, L: Xpr> $Op<&'r Dual> for XprWrapper<L> {
-            type Output = XprWrapper<$Res<L, Dual>>;
-            fn $op(self, other: &Dual) -> Self::Output {
-                Self::Output{xpr: $Res(BinXpr{l: self.xpr, r: other.clone()})}
+        impl<'r, L, $($gen)*> $Op<&'r $D> for XprWrapper<L> {
+            type Output = XprWrapper<$Res<L, $D>>;
+            fn $op(self, other: &$D) -> Self::Output {
+                Self::Output{xpr: $Res(BinXpr{l: self.xpr, r: *other})}
             }
         }
 
-        impl<'l, L: Xpr> $Op<Dual> for &'l XprWrapper<L> {
-            type Output = XprWrapper<$Res<L, Dual>>;
-            fn $op(self, other: Dual) -> Self::Output {
+        impl<'l, L: Copy, $($gen)*> $Op<$D> for &'l XprWrapper<L> {
+            type Output = XprWrapper<$Res<L, $D>>;
+            fn $op(self, other: $D) -> Self::Output {
                 Self::Output{xpr: $Res(BinXpr{l: self.xpr, r: other})}
             }
         }
 
-        impl<'l, 'r, L: Xpr> $Op<&'r Dual> for &'l XprWrapper<L> {
-            type Output = XprWrapper<$Res<L, Dual>>;
-            fn $op(self, other: &Dual) -> Self::Output {
-                Self::Output{xpr: $Res(BinXpr{l: self.xpr, r: other.clone()})}
+        impl<'l, 'r, L: Copy, $($gen)*> $Op<&'r $D> for &'l XprWrapper<L> {
+            type Output = XprWrapper<$Res<L, $D>>;
+            fn $op(self, other: &$D) -> Self::Output {
+                Self::Output{xpr: $Res(BinXpr{l: self.xpr, r: *other})}
             }
         }
+    }
+);
 
-        impl<L: Xpr, R: Xpr> $Op<XprWrapper<R>> for XprWrapper<L> {
-            type Output = XprWrapper<$Res<L, R>>;
-            fn $op(self, other: XprWrapper<R>) -> Self::Output {
-                Self::Output{xpr: $Res(BinXpr{l: self.xpr, r: other.xpr})}
+/// Implement binary operator for dual number type '$D'
+/// combined with constants of type '$C'.
+macro_rules! impl_dual_const_op(
+    ($Op: ident, $op: ident, $Res: ident, [$($gen: tt)*] $D: ty, $C: ty) => {
+        impl<$($gen)*> $Op<$C> for $D {
+            type Output = XprWrapper<$Res<$D, ConstantXpr>>;
+            fn $op(self, other: $C) -> Self::Output {
+                Self::Output{xpr: $Res(BinXpr{l: self, r: constant(other)})}
             }
         }
 
-        impl<'r, L: Xpr, R: Xpr> $Op<&'r XprWrapper<R>> for XprWrapper<L> {
-            type Output = XprWrapper<$Res<L, R>>;
-            fn $op(self, other: &XprWrapper<R>) -> Self::Output {
-                Self::Output{xpr: $Res(BinXpr{l: self.xpr, r: other.xpr})}
+        impl<'l, $($gen)*> $Op<$C> for &'l $D {
+            type Output = XprWrapper<$Res<$D, ConstantXpr>>;
+            fn $op(self, other: $C) -> Self::Output {
+                Self::Output{xpr: $Res(BinXpr{l: *self, r: constant(other)})}
             }
         }
 
-        impl<'l, L: Xpr, R: Xpr> $Op<XprWrapper<R>> for &'l XprWrapper<L> {
-            type Output = XprWrapper<$Res<L, R>>;
-            fn $op(self, other: XprWrapper<R>) -> Self::Output {
-                Self::Output{xpr: $Res(BinXpr{l: self.xpr, r: other.xpr})}
+        impl<'r, $($gen)*> $Op<&'r $C> for $D {
+            type Output = XprWrapper<$Res<$D, ConstantXpr>>;
+            fn $op(self, other: &$C) -> Self::Output {
+                Self::Output{xpr: $Res(BinXpr{l: self, r: constant(*other)})}
             }
         }
 
-        impl<'l, 'r, L: Xpr, R: Xpr> $Op<&'r XprWrapper<R>> for &'l XprWrapper<L> {
-            type Output = XprWrapper<$Res<L, R>>;
-            fn $op(self, other: &XprWrapper<R>) -> Self::Output {
-                Self::Output{xpr: $Res(BinXpr{l: self.xpr, r: other.xpr})}
+        impl<$($gen)*> $Op<$D> for $C {
+            type Output = XprWrapper<$Res<ConstantXpr, $D>>;
+            fn $op(self, other: $D) -> Self::Output {
+                Self::Output{xpr: $Res(BinXpr{l: constant(self), r: other})}
             }
         }
 
-        impl $Op<f64> for Dual {
-            type Output = XprWrapper<$Res<Dual, ConstantXpr>>;
-            fn $op(self, other: f64) -> Self::Output {
-                Self::Output{xpr: $Res(BinXpr{l: self, r: constant(other)})}
+        impl<'r, $($gen)*> $Op<&'r $D> for $C {
+            type Output = XprWrapper<$Res<ConstantXpr, $D>>;
+            fn $op(self, other: &$D) -> Self::Output {
+                Self::Output{xpr: $Res(BinXpr{l: constant(self), r: *other})}
             }
         }
 
-        impl<'l> $Op<f64> for &'l Dual {
-            type Output = XprWrapper<$Res<Dual, ConstantXpr>>;
-            fn $op(self, other: f64) -> Self::Output {
-                Self::Output{xpr: $Res(BinXpr{l: self.clone(), r: constant(other)})}
+        impl<'l, $($gen)*> $Op<$D> for &'l $C {
+            type Output = XprWrapper<$Res<ConstantXpr, $D>>;
+            fn $op(self, other: $D) -> Self::Output {
+                Self::Output{xpr: $Res(BinXpr{l: constant(*self), r: other})}
             }
         }
 
-        impl<'r> $Op<&'r f64> for Dual {
-            type Output = XprWrapper<$Res<Dual, ConstantXpr>>;
-            fn $op(self, other: &f64) -> Self::Output {
-                Self::Output{xpr: $Res(BinXpr{l: self, r: constant(*other)})}
+        impl<'l, 'r, $($gen)*> $Op<&'r $D> for &'l $C {
+            type Output = XprWrapper<$Res<ConstantXpr, $D>>;
+            fn $op(self, other: &$D) -> Self::Output {
+                Self::Output{xpr: $Res(BinXpr{l: constant(*self), r: *other})}
             }
         }
 
-        impl $Op<Dual> for f64 {
-            type Output = XprWrapper<$Res<ConstantXpr, Dual>>;
-            fn $op(self, other: Dual) -> Self::Output {
-                Self::Output{xpr: $Res(BinXpr{l: constant(self), r: other})}
+        impl<'l, 'r, $($gen)*> $Op<&'r $C> for &'l $D {
+            type Output = XprWrapper<$Res<$D, ConstantXpr>>;
+            fn $op(self, other: &$C) -> Self::Output {
+                Self::Output{xpr: $Res(BinXpr{l: *self, r: constant(*other)})}
             }
         }
+    }
+);
 
-        impl<'r> $Op<&'r Dual> for f64 {
-            type Output = XprWrapper<$Res<ConstantXpr, Dual>>;
-            fn $op(self, other: &Dual) -> Self::Output {
-                Self::Output{xpr: $Res(BinXpr{l: constant(self), r: other.clone()})}
+/// Implement binary operator for expressions combined
+/// with expressions.
+macro_rules! impl_bin_op(
+    ($Op: ident, $op: ident, $Res: ident) => {
+        impl<L, R> $Op<XprWrapper<R>> for XprWrapper<L> {
+            type Output = XprWrapper<$Res<L, R>>;
+            fn $op(self, other: XprWrapper<R>) -> Self::Output {
+                Self::Output{xpr: $Res(BinXpr{l: self.xpr, r: other.xpr})}
             }
         }
 
-        impl<'l> $Op<Dual> for &'l f64 {
-            type Output = XprWrapper<$Res<ConstantXpr, Dual>>;
-            fn $op(self, other: Dual) -> Self::Output {
-                Self::Output{xpr: $Res(BinXpr{l: constant(*self), r: other})}
+        impl<'r, L, R: Copy> $Op<&'r XprWrapper<R>> for XprWrapper<L> {
+            type Output = XprWrapper<$Res<L, R>>;
+            fn $op(self, other: &XprWrapper<R>) -> Self::Output {
+                Self::Output{xpr: $Res(BinXpr{l: self.xpr, r: other.xpr})}
             }
         }
 
-        impl<'l, 'r> $Op<&'r Dual> for &'l f64 {
-            type Output = XprWrapper<$Res<ConstantXpr, Dual>>;
-            fn $op(self, other: &Dual) -> Self::Output {
-                Self::Output{xpr: $Res(BinXpr{l: constant(*self), r: other.clone()})}
+        impl<'l, L: Copy, R> $Op<XprWrapper<R>> for &'l XprWrapper<L> {
+            type Output = XprWrapper<$Res<L, R>>;
+            fn $op(self, other: XprWrapper<R>) -> Self::Output {
+                Self::Output{xpr: $Res(BinXpr{l: self.xpr, r: other.xpr})}
             }
         }
 
-        impl<'l, 'r> $Op<&'r f64> for &'l Dual {
-            type Output = XprWrapper<$Res<Dual, ConstantXpr>>;
-            fn $op(self, other: &f64) -> Self::Output {
-                Self::Output{xpr: $Res(BinXpr{l: self.clone(), r: constant(*other)})}
+        impl<'l, 'r, L: Copy, R: Copy> $Op<&'r XprWrapper<R>> for &'l XprWrapper<L> {
+            type Output = XprWrapper<$Res<L, R>>;
+            fn $op(self, other: &XprWrapper<R>) -> Self::Output {
+                Self::Output{xpr: $Res(BinXpr{l: self.xpr, r: other.xpr})}
             }
         }
+    }
+);
 
-        impl<L: Xpr> $Op<f64> for XprWrapper<L> {
+/// Implement binary operator for expressions combined
+/// with constants of type '$C'.
+macro_rules! impl_const_op(
+    ($Op: ident, $op: ident, $Res: ident, $C: ty) => {
+        impl<L> $Op<$C> for XprWrapper<L> {
             type Output = XprWrapper<$Res<L, ConstantXpr>>;
-            fn $op(self, other: f64) -> Self::Output {
+            fn $op(self, other: $C) -> Self::Output {
                 Self::Output{xpr: $Res(BinXpr{l: self.xpr, r: constant(other)})}
             }
         }
 
-        impl<'r, L: Xpr> $Op<&'r f64> for XprWrapper<L> {
+        impl<'r, L> $Op<&'r $C> for XprWrapper<L> {
             type Output = XprWrapper<$Res<L, ConstantXpr>>;
-            fn $op(self, other: &f64) -> Self::Output {
+            fn $op(self, other: &$C) -> Self::Output {
                 Self::Output{xpr: $Res(BinXpr{l: self.xpr, r: constant(*other)})}
             }
         }
 
-        impl<'l, L: Xpr> $Op<f64> for &'l XprWrapper<L> {
+        impl<'l, L: Copy> $Op<$C> for &'l XprWrapper<L> {
             type Output = XprWrapper<$Res<L, ConstantXpr>>;
-            fn $op(self, other: f64) -> Self::Output {
+            fn $op(self, other: $C) -> Self::Output {
                 Self::Output{xpr: $Res(BinXpr{l: self.xpr, r: constant(other)})}
             }
         }
 
-        impl<'l, 'r, L: Xpr> $Op<&'r f64> for &'l XprWrapper<L> {
+        impl<'l, 'r, L: Copy> $Op<&'r $C> for &'l XprWrapper<L> {
             type Output = XprWrapper<$Res<L, ConstantXpr>>;
-            fn $op(self, other: &f64) -> Self::Output {
+            fn $op(self, other: &$C) -> Self::Output {
                 Self::Output{xpr: $Res(BinXpr{l: self.xpr, r: constant(*other)})}
             }
         }
 
-        impl<R: Xpr> $Op<XprWrapper<R>> for f64 {
+        impl<R> $Op<XprWrapper<R>> for $C {
             type Output = XprWrapper<$Res<ConstantXpr, R>>;
             fn $op(self, other: XprWrapper<R>) -> Self::Output {
                 Self::Output{xpr: $Res(BinXpr{l: constant(self), r: other.xpr})}
             }
         }
 
-        impl<'l, R: Xpr> $Op<XprWrapper<R>> for &'l f64 {
+        impl<'l, R> $Op<XprWrapper<R>> for &'l $C {
             type Output = XprWrapper<$Res<ConstantXpr, R>>;
             fn $op(self, other: XprWrapper<R>) -> Self::Output {
                 Self::Output{xpr: $Res(BinXpr{l: constant(*self), r: other.xpr})}
             }
         }
 
-        impl<'r, R: Xpr> $Op<&'r XprWrapper<R>> for f64 {
+        impl<'r, R: Copy> $Op<&'r XprWrapper<R>> for $C {
             type Output = XprWrapper<$Res<ConstantXpr, R>>;
             fn $op(self, other: &XprWrapper<R>) -> Self::Output {
                 Self::Output{xpr: $Res(BinXpr{l: constant(self), r: other.xpr})}
             }
         }
 
-        impl<'l, 'r, R: Xpr> $Op<&'r XprWrapper<R>> for &'l f64 {
+        impl<'l, 'r, R: Copy> $Op<&'r XprWrapper<R>> for &'l $C {
             type Output = XprWrapper<$Res<ConstantXpr, R>>;
             fn $op(self, other: &XprWrapper<R>) -> Self::Output {
                 Self::Output{xpr: $Res(BinXpr{l: constant(*self), r: other.xpr})}
@@ -350,7 +435,24 @@ macro_rules! impl_bin_op(
     }
 );
 
-impl_bin_op!(Add, add, AddXpr);
-impl_bin_op!(Sub, sub, SubXpr);
-impl_bin_op!(Mul, mul, MulXpr);
-impl_bin_op!(Div, div, DivXpr);
+/// Implement binary operator for all dual number types and expressions.
+macro_rules! impl_ops(
+    ($Op: ident, $op: ident, $Res: ident) => {
+        impl_bin_op!($Op, $op, $Res);
+        impl_const_op!($Op, $op, $Res, f64);
+
+        impl_dual_bin_op!($Op, $op, $Res, [T: Real] Dual<T>);
+        impl_dual_const_op!($Op, $op, $Res, [T: Real] Dual<T>, f64);
+
+        impl_dual_bin_op!($Op, $op, $Res, [const N: usize] DualN<N>);
+        impl_dual_const_op!($Op, $op, $Res, [const N: usize] DualN<N>, f64);
+
+        impl_dual_bin_op!($Op, $op, $Res, [] HyperDual);
+        impl_dual_const_op!($Op, $op, $Res, [] HyperDual, f64);
+    }
+);
+
+impl_ops!(Add, add, AddXpr);
+impl_ops!(Sub, sub, SubXpr);
+impl_ops!(Mul, mul, MulXpr);
+impl_ops!(Div, div, DivXpr);
@@ -1,48 +1,94 @@
 use super::expression::{Xpr, XprWrapper};
-use crate::dual::Dual;
+use crate::{dual::DualNumber, real::Real};
 
-/// Struct to hold constant expression.
+/// Struct to hold constant expression. Constant is kept
+/// as f64 and converted to scalar type of target on assign.
 #[derive(Copy, Clone, Debug)]
 pub struct ConstantXpr {
     /// 'val' - value of constant expression.
-    val: f64,
+    pub(crate) val: f64,
 }
 
-/// Xpr constant expression.
-impl Xpr for ConstantXpr {
-    fn assign(&self, other: &mut Dual) {
-        other.val = self.val;
+/// Xpr constant expression. Operations with
+/// identity element leave target untouched.
+impl<D: DualNumber> Xpr<D> for ConstantXpr {
+    fn assign(&self, other: &mut D) {
+        other.set_value(D::Scalar::from_f64(self.val));
+        other.map_der(|_| D::Scalar::zero());
     }
 
-    fn assign_add(&self, target: &mut Dual) {
-        target.val += self.val;
+    fn assign_add(&self, target: &mut D) {
+        if self.val != 0.0 {
+            target.set_value(target.value().add(D::Scalar::from_f64(self.val)));
+        }
+    }
+
+    fn assign_sub(&self, target: &mut D) {
+        if self.val != 0.0 {
+            target.set_value(target.value().sub(D::Scalar::from_f64(self.val)));
+        }
+    }
+
+    fn assign_mul(&self, target: &mut D) {
+        if self.val == 1.0 {
+            return;
+        }
+        if self.val == -1.0 {
+            return target.negate();
+        }
+        let val = D::Scalar::from_f64(self.val);
+        target.map_der(|der| der.mul(val));
+        target.set_value(target.value().mul(val));
     }
 
-    fn assign_sub(&self, target: &mut Dual) {
-        target.val -= self.val;
+    fn assign_div(&self, target: &mut D) {
+        if self.val == 1.0 {
+            return;
+        }
+        let val = D::Scalar::from_f64(self.val);
+        target.map_der(|der| der.div(val));
+        target.set_value(target.value().div(val));
     }
 
-    fn assign_mul(&self, target: &mut Dual) {
-        target.val *= self.val;
+    fn assign_pow(&self, target: &mut D) {
+        if self.val == 1.0 {
+            return;
+        }
+        let val = target.value();
+        let deg = D::Scalar::from_f64(self.val);
+        let pow = val.powf(D::Scalar::from_f64(self.val - 1.0));
+
+        target.chain(
+            pow.mul(val),
+            pow.mul(deg),
+            val.powf(D::Scalar::from_f64(self.val - 2.0))
+                .mul(deg)
+                .mul(D::Scalar::from_f64(self.val - 1.0)),
+        );
     }
 
-    fn assign_pow(&self, target: &mut Dual) {
-        let pow = target.val.powf(self.val - 1.0);
+    fn assign_neg(&self, other: &mut D) {
+        constant(-self.val).assign(other);
+    }
 
-        target.der.set(pow * self.val * target.der.get());
-        target.val = pow * target.val;
+    fn as_constant(&self) -> Option<f64> {
+        Some(self.val)
     }
 }
 
 /// Create constant expression.
-pub(crate) fn constant(val: f64) -> ConstantXpr {
-    ConstantXpr { val }
+pub(crate) fn constant<C: Into<f64>>(val: C) -> ConstantXpr {
+    ConstantXpr { val: val.into() }
 }
 
-impl Into<XprWrapper<ConstantXpr>> for f64 {
-    fn into(self) -> XprWrapper<ConstantXpr> {
-        XprWrapper {
-            xpr: constant(self),
-        }
+impl From<f64> for XprWrapper<ConstantXpr> {
+    fn from(val: f64) -> Self {
+        XprWrapper { xpr: constant(val) }
+    }
+}
+
+impl From<f32> for XprWrapper<ConstantXpr> {
+    fn from(val: f32) -> Self {
+        XprWrapper { xpr: constant(val) }
     }
 }
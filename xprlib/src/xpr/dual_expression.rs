@@ -1,58 +1,175 @@
 use super::expression::{Xpr, XprWrapper};
-use crate::dual::Dual;
+use crate::{
+    dual::{Dual, DualNumber},
+    dual_n::DualN,
+    hyper_dual::HyperDual,
+    real::Real,
+};
 
 /// Xpr Dual to Dual.
 /// Just simple implementation for Dual number.
-impl Xpr for Dual {
-    fn assign(&self, other: &mut Dual) {
+impl<T: Real> Xpr<Dual<T>> for Dual<T> {
+    fn assign(&self, other: &mut Dual<T>) {
         other.val = self.val;
         other.der = self.der;
     }
 
-    fn assign_add(&self, target: &mut Dual) {
+    fn assign_add(&self, target: &mut Dual<T>) {
+        target.val = target.val.add(self.val);
+        target.der = target.der.add(self.der);
+    }
+
+    fn assign_sub(&self, target: &mut Dual<T>) {
+        target.val = target.val.sub(self.val);
+        target.der = target.der.sub(self.der);
+    }
+
+    fn assign_mul(&self, target: &mut Dual<T>) {
+        target.der = target.der.mul(self.val).add(self.der.mul(target.val));
+        target.val = target.val.mul(self.val);
+    }
+
+    fn assign_div(&self, target: &mut Dual<T>) {
+        target.der = target.der.mul(self.val).sub(self.der.mul(target.val)).div(self.val).div(self.val);
+        target.val = target.val.div(self.val);
+    }
+
+    fn assign_pow(&self, target: &mut Dual<T>) {
+        let pow = target.val().powf(self.val().sub(T::one()));
+
+        target.der = pow.mul(
+            self.der().mul(target.val().ln()).mul(target.val()).add(self.val().mul(target.der())),
+        );
+        target.val = pow.mul(target.val());
+    }
+}
+
+impl<T: Real, E: Xpr<Dual<T>>> From<XprWrapper<E>> for Dual<T> {
+    fn from(wxpr: XprWrapper<E>) -> Self {
+        let mut val = Dual::new(T::zero());
+        wxpr.xpr.assign(&mut val);
+        val
+    }
+}
+
+impl<T> From<Dual<T>> for XprWrapper<Dual<T>> {
+    fn from(xpr: Dual<T>) -> Self {
+        XprWrapper { xpr }
+    }
+}
+
+/// Xpr DualN to DualN.
+/// Same rules as for Dual number applied to each tangent component.
+impl<const N: usize> Xpr<DualN<N>> for DualN<N> {
+    fn assign(&self, other: &mut DualN<N>) {
+        *other = *self;
+    }
+
+    fn assign_add(&self, target: &mut DualN<N>) {
         target.val += self.val;
-        target.der.set(target.der.get() + self.der.get());
+        for (t, s) in target.der.iter_mut().zip(self.der.iter()) {
+            *t += s;
+        }
     }
 
-    fn assign_sub(&self, target: &mut Dual) {
+    fn assign_sub(&self, target: &mut DualN<N>) {
         target.val -= self.val;
-        target.der.set(target.der.get() - self.der.get());
+        for (t, s) in target.der.iter_mut().zip(self.der.iter()) {
+            *t -= s;
+        }
     }
 
-    fn assign_mul(&self, target: &mut Dual) {
-        target
-            .der
-            .set(target.der.get() * self.val + self.der.get() * target.val);
+    fn assign_mul(&self, target: &mut DualN<N>) {
+        for (t, s) in target.der.iter_mut().zip(self.der.iter()) {
+            *t = *t * self.val + s * target.val;
+        }
         target.val *= self.val;
     }
 
-    fn assign_div(&self, target: &mut Dual) {
-        target.der.set(
-            -(target.der.get() * self.val - self.der.get() * target.val) / self.val / self.val,
-        );
+    fn assign_div(&self, target: &mut DualN<N>) {
+        for (t, s) in target.der.iter_mut().zip(self.der.iter()) {
+            *t = (*t * self.val - s * target.val) / self.val / self.val;
+        }
         target.val /= self.val;
     }
 
-    fn assign_pow(&self, target: &mut Dual) {
-        let pow = target.val().powf(self.val() - 1.0);
+    fn assign_pow(&self, target: &mut DualN<N>) {
+        let pow = target.val.powf(self.val - 1.0);
+        let ln = target.val.ln();
+
+        for (t, s) in target.der.iter_mut().zip(self.der.iter()) {
+            *t = pow * (s * ln * target.val + self.val * *t);
+        }
+        target.val *= pow;
+    }
+}
+
+impl<T: Xpr<DualN<N>>, const N: usize> From<XprWrapper<T>> for DualN<N> {
+    fn from(wxpr: XprWrapper<T>) -> Self {
+        let mut val = DualN::new(0.0);
+        wxpr.xpr.assign(&mut val);
+        val
+    }
+}
+
+impl<const N: usize> From<DualN<N>> for XprWrapper<DualN<N>> {
+    fn from(xpr: DualN<N>) -> Self {
+        XprWrapper { xpr }
+    }
+}
+
+/// Xpr HyperDual to HyperDual.
+/// Second order part is propagated together with tangents.
+impl Xpr<HyperDual> for HyperDual {
+    fn assign(&self, other: &mut HyperDual) {
+        *other = *self;
+    }
+
+    fn assign_add(&self, target: &mut HyperDual) {
+        target.val += self.val;
+        target.e1 += self.e1;
+        target.e2 += self.e2;
+        target.e12 += self.e12;
+    }
+
+    fn assign_sub(&self, target: &mut HyperDual) {
+        target.val -= self.val;
+        target.e1 -= self.e1;
+        target.e2 -= self.e2;
+        target.e12 -= self.e12;
+    }
+
+    fn assign_mul(&self, target: &mut HyperDual) {
+        target.mul_assign(self);
+    }
+
+    fn assign_div(&self, target: &mut HyperDual) {
+        let mut inv = *self;
+        let val = self.val;
+        inv.chain(1.0 / val, -1.0 / (val * val), 2.0 / (val * val * val));
+        target.mul_assign(&inv);
+    }
 
-        target
-            .der
-            .set(pow * (self.der() * target.val().ln() * target.val() + self.val() * target.der()));
-        target.val = pow * target.val();
+    fn assign_pow(&self, target: &mut HyperDual) {
+        // t^s = exp(s * ln(t))
+        let val = target.val;
+        let pow = val.powf(self.val);
+        target.chain(val.ln(), 1.0 / val, -1.0 / (val * val));
+        target.mul_assign(self);
+        target.chain(pow, pow, pow);
     }
 }
 
-impl<T: Xpr> From<XprWrapper<T>> for Dual {
+impl<T: Xpr<HyperDual>> From<XprWrapper<T>> for HyperDual {
     fn from(wxpr: XprWrapper<T>) -> Self {
-        let mut val = Dual::new(0.0);
+        let mut val = HyperDual::new(0.0);
         wxpr.xpr.assign(&mut val);
         val
     }
 }
 
-impl Into<XprWrapper<Dual>> for Dual {
-    fn into(self) -> XprWrapper<Dual> {
-        XprWrapper { xpr: self }
+impl From<HyperDual> for XprWrapper<HyperDual> {
+    fn from(xpr: HyperDual) -> Self {
+        XprWrapper { xpr }
     }
 }
@@ -0,0 +1,260 @@
+use super::{
+    binary_op::{AddXpr, DivXpr, MulXpr, SubXpr},
+    expression::{Xpr, XprWrapper},
+};
+use crate::dual::{Dual, DualNumber};
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+use std::rc::Rc;
+
+/// Object safe counterpart of Xpr trait, Xpr
+/// requires Clone, so it can't be used as trait object.
+trait DynAssign<D> {
+    fn assign(&self, target: &mut D);
+    fn assign_add(&self, target: &mut D);
+    fn assign_sub(&self, target: &mut D);
+    fn assign_mul(&self, target: &mut D);
+    fn assign_div(&self, target: &mut D);
+    fn assign_pow(&self, target: &mut D);
+    fn assign_neg(&self, target: &mut D);
+    fn as_constant(&self) -> Option<f64>;
+}
+
+impl<D: DualNumber, E: Xpr<D>> DynAssign<D> for E {
+    fn assign(&self, target: &mut D) {
+        Xpr::assign(self, target)
+    }
+
+    fn assign_add(&self, target: &mut D) {
+        Xpr::assign_add(self, target)
+    }
+
+    fn assign_sub(&self, target: &mut D) {
+        Xpr::assign_sub(self, target)
+    }
+
+    fn assign_mul(&self, target: &mut D) {
+        Xpr::assign_mul(self, target)
+    }
+
+    fn assign_div(&self, target: &mut D) {
+        Xpr::assign_div(self, target)
+    }
+
+    fn assign_pow(&self, target: &mut D) {
+        Xpr::assign_pow(self, target)
+    }
+
+    fn assign_neg(&self, target: &mut D) {
+        Xpr::assign_neg(self, target)
+    }
+
+    fn as_constant(&self) -> Option<f64> {
+        Xpr::as_constant(self)
+    }
+}
+
+/// Compound assignment applied to runtime expression.
+#[derive(Copy, Clone, Debug)]
+enum DynOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+/// Expression followed by compound assignments in order they were
+/// applied. Flat list keeps evaluation and drop of long chains built
+/// in a loop free of recursion.
+struct DynChain<D> {
+    /// 'head' - expression chain starts with.
+    head: Rc<dyn DynAssign<D>>,
+    /// 'tail' - operations applied to head, with their operands.
+    tail: Vec<(DynOp, Rc<dyn DynAssign<D>>)>,
+}
+
+impl<D> Clone for DynChain<D> {
+    fn clone(&self) -> Self {
+        Self {
+            head: Rc::clone(&self.head),
+            tail: self.tail.clone(),
+        }
+    }
+}
+
+/// Heap allocated expression node with erased type. Cloning
+/// shares the node, so subexpressions may be reused.
+/// 'D' - type of dual number expression is assigned to.
+pub struct DynNode<D = Dual> {
+    /// 'node' - underlying expression.
+    node: Rc<DynChain<D>>,
+}
+
+/// Expression which size is known at runtime only, e.g. sum built
+/// in a loop. It is combined with Dual numbers, constants and static
+/// expressions by the same operators and collapses into Dual number
+/// by 'From'.
+///
+/// Compound assignments, e.g. 'sum += x', are appended to the node,
+/// so evaluation and drop don't depend on number of them. Runtime
+/// expression used as operand of another one which is put on the
+/// heap again, e.g. 'DynXpr::new(sum + x)', adds one level of
+/// recursion, so depth of such nesting is limited by stack size.
+/// 'D' - type of dual number expression is assigned to.
+pub type DynXpr<D = Dual> = XprWrapper<DynNode<D>>;
+
+impl<D> Clone for DynNode<D> {
+    fn clone(&self) -> Self {
+        Self {
+            node: Rc::clone(&self.node),
+        }
+    }
+}
+
+impl<D> fmt::Debug for DynNode<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("DynNode")
+    }
+}
+
+impl<D: DualNumber> DynNode<D> {
+    /// Evaluate chain into temporary dual number.
+    fn eval(&self) -> D {
+        let mut aux = D::zero();
+        Xpr::assign(self, &mut aux);
+        aux
+    }
+}
+
+impl<D: DualNumber> Xpr<D> for DynNode<D> {
+    fn assign(&self, target: &mut D) {
+        self.node.head.assign(target);
+        for (op, xpr) in &self.node.tail {
+            match op {
+                DynOp::Add => xpr.assign_add(target),
+                DynOp::Sub => xpr.assign_sub(target),
+                DynOp::Mul => xpr.assign_mul(target),
+                DynOp::Div => xpr.assign_div(target),
+            }
+        }
+    }
+
+    fn assign_add(&self, target: &mut D) {
+        if self.node.tail.is_empty() {
+            self.node.head.assign_add(target);
+        } else {
+            self.eval().assign_add(target);
+        }
+    }
+
+    fn assign_sub(&self, target: &mut D) {
+        if self.node.tail.is_empty() {
+            self.node.head.assign_sub(target);
+        } else {
+            self.eval().assign_sub(target);
+        }
+    }
+
+    fn assign_mul(&self, target: &mut D) {
+        if self.node.tail.is_empty() {
+            self.node.head.assign_mul(target);
+        } else {
+            self.eval().assign_mul(target);
+        }
+    }
+
+    fn assign_div(&self, target: &mut D) {
+        if self.node.tail.is_empty() {
+            self.node.head.assign_div(target);
+        } else {
+            self.eval().assign_div(target);
+        }
+    }
+
+    fn assign_pow(&self, target: &mut D) {
+        if self.node.tail.is_empty() {
+            self.node.head.assign_pow(target);
+        } else {
+            self.eval().assign_pow(target);
+        }
+    }
+
+    fn assign_neg(&self, target: &mut D) {
+        if self.node.tail.is_empty() {
+            self.node.head.assign_neg(target);
+        } else {
+            Xpr::assign(self, target);
+            target.negate();
+        }
+    }
+
+    fn as_constant(&self) -> Option<f64> {
+        if self.node.tail.is_empty() {
+            self.node.head.as_constant()
+        } else {
+            None
+        }
+    }
+}
+
+impl<D: DualNumber> XprWrapper<DynNode<D>> {
+    /// Create runtime expression from Dual number,
+    /// constant or static expression.
+    ///
+    /// # Arguments
+    ///
+    /// 'xpr' - expression to put on the heap.
+    pub fn new<E, X>(xpr: X) -> Self
+    where
+        E: Xpr<D> + 'static,
+        X: Into<XprWrapper<E>>,
+    {
+        xpr.into().into_dyn()
+    }
+
+    /// Evaluate expression into dual number.
+    pub fn eval(&self) -> D {
+        self.xpr.eval()
+    }
+}
+
+impl<E> XprWrapper<E> {
+    /// Put expression on the heap and erase its type.
+    pub fn into_dyn<D>(self) -> DynXpr<D>
+    where
+        D: DualNumber,
+        E: Xpr<D> + 'static,
+    {
+        XprWrapper {
+            xpr: DynNode {
+                node: Rc::new(DynChain {
+                    head: Rc::new(self.xpr),
+                    tail: Vec::new(),
+                }),
+            },
+        }
+    }
+}
+
+/// Implement assign operator for runtime expressions, operand of
+/// 'other' is appended to the node, which is copied only if shared.
+macro_rules! impl_dyn_assign_op(
+    ($Op: ident, $op: ident, $OpAssign: ident, $op_assign: ident, $Res: ident, $DynOp: ident) => {
+        impl<D, X, E> $OpAssign<X> for DynXpr<D>
+        where
+            D: DualNumber,
+            DynXpr<D>: $Op<X, Output = XprWrapper<$Res<DynNode<D>, E>>>,
+            E: Xpr<D> + 'static,
+        {
+            fn $op_assign(&mut self, other: X) {
+                let xpr: Rc<dyn DynAssign<D>> = Rc::new(self.clone().$op(other).xpr.0.r);
+                Rc::make_mut(&mut self.xpr.node).tail.push((DynOp::$DynOp, xpr));
+            }
+        }
+    }
+);
+
+impl_dyn_assign_op!(Add, add, AddAssign, add_assign, AddXpr, Add);
+impl_dyn_assign_op!(Sub, sub, SubAssign, sub_assign, SubXpr, Sub);
+impl_dyn_assign_op!(Mul, mul, MulAssign, mul_assign, MulXpr, Mul);
+impl_dyn_assign_op!(Div, div, DivAssign, div_assign, DivXpr, Div);
@@ -1,14 +1,15 @@
-use crate::dual::Dual;
+use crate::dual::{Dual, DualNumber};
 
 /// Trait to assign expression to Dual number. By
 /// default assign_add, assign_sub create temporary
 /// variables, for some cases we can optimize that.
-pub trait Xpr: Copy + Clone {
+/// 'D' - type of dual number expression is assigned to.
+pub trait Xpr<D: DualNumber = Dual>: Clone {
     /// Assign expression to Dual number.
     ///
     /// # Arguments
     /// 'target' - assign expression into target.
-    fn assign(&self, target: &mut Dual);
+    fn assign(&self, target: &mut D);
 
     /// Assign sub to Dual number.
     ///
@@ -18,8 +19,8 @@ pub trait Xpr: Copy + Clone {
     /// # Node
     /// Should be overridden if operation
     /// can avoid temporary variables
-    fn assign_add(&self, target: &mut Dual) {
-        let mut aux: Dual = 0.0.into();
+    fn assign_add(&self, target: &mut D) {
+        let mut aux = D::zero();
         self.assign(&mut aux);
         aux.assign_add(target);
     }
@@ -31,8 +32,8 @@ pub trait Xpr: Copy + Clone {
     /// # Node
     /// Should be overridden if operation
     /// can avoid temporary variables
-    fn assign_sub(&self, target: &mut Dual) {
-        let mut aux: Dual = 0.0.into();
+    fn assign_sub(&self, target: &mut D) {
+        let mut aux = D::zero();
         self.assign(&mut aux);
         aux.assign_sub(target);
     }
@@ -44,8 +45,8 @@ pub trait Xpr: Copy + Clone {
     /// # Node
     /// Should be overridden if operation
     /// can avoid temporary variables
-    fn assign_mul(&self, target: &mut Dual) {
-        let mut aux: Dual = 0.0.into();
+    fn assign_mul(&self, target: &mut D) {
+        let mut aux = D::zero();
         self.assign(&mut aux);
         aux.assign_mul(target);
     }
@@ -57,8 +58,8 @@ pub trait Xpr: Copy + Clone {
     /// # Node
     /// Should be overridden if operation
     /// can avoid temporary variables
-    fn assign_div(&self, target: &mut Dual) {
-        let mut aux: Dual = 0.0.into();
+    fn assign_div(&self, target: &mut D) {
+        let mut aux = D::zero();
         self.assign(&mut aux);
         aux.assign_div(target);
     }
@@ -70,20 +71,34 @@ pub trait Xpr: Copy + Clone {
     /// # Node
     /// Should be overridden if operation
     /// can avoid temporary variables
-    fn assign_pow(&self, target: &mut Dual) {
-        let mut aux: Dual = 0.0.into();
+    fn assign_pow(&self, target: &mut D) {
+        let mut aux = D::zero();
         self.assign(&mut aux);
         aux.assign_pow(target);
     }
+
+    /// Assign negated expression to Dual number.
+    ///
+    /// # Arguments
+    /// 'target' - assign expression into target.
+    /// # Node
+    /// Overridden by negation, so double
+    /// negation is not evaluated at all.
+    fn assign_neg(&self, target: &mut D) {
+        self.assign(target);
+        target.negate();
+    }
+
+    /// Value of expression if it doesn't depend on dual
+    /// numbers, such expression is folded into one constant.
+    fn as_constant(&self) -> Option<f64> {
+        None
+    }
 }
 
 /// Structure which represents binary expression
 #[derive(Copy, Clone, Debug)]
-pub struct BinXpr<L, R>
-where
-    L: Xpr,
-    R: Xpr,
-{
+pub struct BinXpr<L, R> {
     /// 'l' - the left part of expression.
     pub(crate) l: L,
     /// 'r' - the right part of expression.
@@ -92,10 +107,7 @@ where
 
 /// Unary expression holder.
 #[derive(Copy, Clone, Debug)]
-pub struct UnXpr<Op>
-where
-    Op: Xpr,
-{
+pub struct UnXpr<Op> {
     /// operand of current expression.
     pub(crate) op: Op,
 }
@@ -106,7 +118,7 @@ where
 /// wrapper class as a result of of it. All expressions
 /// in this crate should implement Xpr trait.
 #[derive(Copy, Clone, Debug)]
-pub struct XprWrapper<T: Xpr> {
+pub struct XprWrapper<T> {
     /// 'xpr' - underlying expression.
     pub(crate) xpr: T,
 }
@@ -0,0 +1,266 @@
+use super::{
+    expression::XprWrapper,
+    visit::{Operation, Visit, Visitor},
+};
+use crate::dual::Dual;
+use std::fmt;
+
+/// Precedence of printed expression, operands
+/// with lower precedence are put in parentheses.
+const ADD: u8 = 1;
+const MUL: u8 = 2;
+const NEG: u8 = 3;
+const POW: u8 = 4;
+const ATOM: u8 = 5;
+
+/// Tangent part of dual number, 'eps' is symbol of dual unit.
+fn tangent(der: &[f64], eps: &str) -> String {
+    match der {
+        [der] if *der < 0.0 => format!(" - {}{}", -der, eps),
+        [der] => format!(" + {}{}", der, eps),
+        ders => {
+            let ders: Vec<String> = ders.iter().map(f64::to_string).collect();
+            format!(" + [{}]{}", ders.join(", "), eps)
+        }
+    }
+}
+
+/// Precedence of constant, negative one is printed with sign.
+fn constant_prec(val: f64) -> u8 {
+    if val.is_sign_negative() {
+        NEG
+    } else {
+        ATOM
+    }
+}
+
+/// Renders expression as infix text.
+struct TextPrinter;
+
+impl TextPrinter {
+    fn wrap((text, prec): (String, u8), min: u8) -> String {
+        if prec < min {
+            format!("({})", text)
+        } else {
+            text
+        }
+    }
+}
+
+impl Visitor for TextPrinter {
+    type Output = (String, u8);
+
+    fn leaf(&mut self, val: f64, der: &[f64]) -> Self::Output {
+        (format!("{}{}", val, tangent(der, "ε")), ADD)
+    }
+
+    fn constant(&mut self, val: f64) -> Self::Output {
+        (val.to_string(), constant_prec(val))
+    }
+
+    fn unary(&mut self, op: Operation, arg: Self::Output) -> Self::Output {
+        match op {
+            Operation::Neg => (format!("-{}", Self::wrap(arg, NEG + 1)), NEG),
+            Operation::Powi(n) if n < 0 => (format!("{}^({})", Self::wrap(arg, ATOM), n), POW),
+            Operation::Powi(n) => (format!("{}^{}", Self::wrap(arg, ATOM), n), POW),
+            op => (format!("{}({})", op.name(), arg.0), ATOM),
+        }
+    }
+
+    fn binary(&mut self, op: Operation, l: Self::Output, r: Self::Output) -> Self::Output {
+        let (sign, prec, right) = match op {
+            Operation::Add => (" + ", ADD, ADD),
+            Operation::Sub => (" - ", ADD, MUL),
+            Operation::Mul => (" * ", MUL, MUL),
+            Operation::Div => (" / ", MUL, NEG),
+            // right associative
+            Operation::Powf => ("^", POW, POW),
+            op => return (format!("{}({}, {})", op.name(), l.0, r.0), ATOM),
+        };
+        let l = Self::wrap(l, if op == Operation::Powf { ATOM } else { prec });
+        (format!("{}{}{}", l, sign, Self::wrap(r, right)), prec)
+    }
+}
+
+/// Renders expression as LaTeX formula.
+struct LatexPrinter;
+
+impl LatexPrinter {
+    fn wrap((text, prec): (String, u8), min: u8) -> String {
+        if prec < min {
+            format!("\\left({}\\right)", text)
+        } else {
+            text
+        }
+    }
+
+    /// LaTeX command of function.
+    fn function(op: Operation) -> String {
+        match op {
+            Operation::Sin => "\\sin".to_string(),
+            Operation::Cos => "\\cos".to_string(),
+            Operation::Tan => "\\tan".to_string(),
+            Operation::Asin => "\\arcsin".to_string(),
+            Operation::Acos => "\\arccos".to_string(),
+            Operation::Atan => "\\arctan".to_string(),
+            Operation::Sinh => "\\sinh".to_string(),
+            Operation::Cosh => "\\cosh".to_string(),
+            Operation::Tanh => "\\tanh".to_string(),
+            Operation::Ln => "\\ln".to_string(),
+            Operation::Log2 => "\\log_{2}".to_string(),
+            Operation::Log10 => "\\log_{10}".to_string(),
+            Operation::Min => "\\min".to_string(),
+            Operation::Max => "\\max".to_string(),
+            Operation::Gamma => "\\Gamma".to_string(),
+            Operation::LnGamma => "\\ln\\Gamma".to_string(),
+            Operation::Digamma => "\\psi".to_string(),
+            Operation::BesselJ0 => "J_{0}".to_string(),
+            Operation::BesselJ1 => "J_{1}".to_string(),
+            op => format!("\\operatorname{{{}}}", op.name()),
+        }
+    }
+}
+
+impl Visitor for LatexPrinter {
+    type Output = (String, u8);
+
+    fn leaf(&mut self, val: f64, der: &[f64]) -> Self::Output {
+        (format!("{}{}", val, tangent(der, "\\varepsilon")), ADD)
+    }
+
+    fn constant(&mut self, val: f64) -> Self::Output {
+        (val.to_string(), constant_prec(val))
+    }
+
+    fn unary(&mut self, op: Operation, arg: Self::Output) -> Self::Output {
+        match op {
+            Operation::Neg => (format!("-{}", Self::wrap(arg, NEG + 1)), NEG),
+            Operation::Powi(n) => (format!("{}^{{{}}}", Self::wrap(arg, ATOM), n), POW),
+            Operation::Sqrt => (format!("\\sqrt{{{}}}", arg.0), ATOM),
+            Operation::Cbrt => (format!("\\sqrt[3]{{{}}}", arg.0), ATOM),
+            Operation::Abs => (format!("\\left|{}\\right|", arg.0), ATOM),
+            Operation::Exp => (format!("e^{{{}}}", arg.0), POW),
+            Operation::Exp2 => (format!("2^{{{}}}", arg.0), POW),
+            Operation::ExpM1 => (format!("e^{{{}}} - 1", arg.0), ADD),
+            Operation::Ln1p => (format!("\\ln\\left(1 + {}\\right)", arg.0), ATOM),
+            Operation::Recip => (format!("\\frac{{1}}{{{}}}", arg.0), ATOM),
+            op => (format!("{}\\left({}\\right)", Self::function(op), arg.0), ATOM),
+        }
+    }
+
+    fn binary(&mut self, op: Operation, l: Self::Output, r: Self::Output) -> Self::Output {
+        match op {
+            Operation::Add => (format!("{} + {}", l.0, Self::wrap(r, ADD)), ADD),
+            Operation::Sub => (format!("{} - {}", l.0, Self::wrap(r, MUL)), ADD),
+            Operation::Mul => (
+                format!("{} \\cdot {}", Self::wrap(l, MUL), Self::wrap(r, MUL)),
+                MUL,
+            ),
+            Operation::Div => (format!("\\frac{{{}}}{{{}}}", l.0, r.0), ATOM),
+            Operation::Powf => (format!("{}^{{{}}}", Self::wrap(l, ATOM), r.0), POW),
+            Operation::Log => (format!("\\log_{{{}}}\\left({}\\right)", r.0, l.0), ATOM),
+            op => (
+                format!("{}\\left({}, {}\\right)", Self::function(op), l.0, r.0),
+                ATOM,
+            ),
+        }
+    }
+}
+
+/// Renders expression as Graphviz DOT graph, leaves are
+/// annotated with values and derivatives.
+struct DotPrinter {
+    /// 'lines' - statements of graph.
+    lines: Vec<String>,
+    /// 'nodes' - count of added nodes.
+    nodes: usize,
+}
+
+impl DotPrinter {
+    /// Add node and return its name.
+    fn node(&mut self, attrs: String) -> String {
+        let name = format!("n{}", self.nodes);
+        self.nodes += 1;
+        self.lines.push(format!("    {} [{}];", name, attrs));
+        name
+    }
+
+    /// Label of operation node.
+    fn label(op: Operation) -> String {
+        match op {
+            Operation::Add => "+".to_string(),
+            Operation::Sub => "-".to_string(),
+            Operation::Mul => "*".to_string(),
+            Operation::Div => "/".to_string(),
+            Operation::Neg => "neg".to_string(),
+            Operation::Powi(n) => format!("powi({})", n),
+            op => op.name().to_string(),
+        }
+    }
+}
+
+impl Visitor for DotPrinter {
+    type Output = String;
+
+    fn leaf(&mut self, val: f64, der: &[f64]) -> Self::Output {
+        let der: Vec<String> = der.iter().map(f64::to_string).collect();
+        let der = match der.len() {
+            1 => der[0].clone(),
+            _ => format!("[{}]", der.join(", ")),
+        };
+        self.node(format!("shape=box, label=\"val = {}\\nder = {}\"", val, der))
+    }
+
+    fn constant(&mut self, val: f64) -> Self::Output {
+        self.node(format!("shape=plaintext, label=\"{}\"", val))
+    }
+
+    fn unary(&mut self, op: Operation, arg: Self::Output) -> Self::Output {
+        let name = self.node(format!("label=\"{}\"", Self::label(op)));
+        self.lines.push(format!("    {} -> {};", name, arg));
+        name
+    }
+
+    fn binary(&mut self, op: Operation, l: Self::Output, r: Self::Output) -> Self::Output {
+        let name = self.node(format!("label=\"{}\"", Self::label(op)));
+        self.lines.push(format!("    {} -> {} [label=\"l\"];", name, l));
+        self.lines.push(format!("    {} -> {} [label=\"r\"];", name, r));
+        name
+    }
+}
+
+impl<E: Visit> XprWrapper<E> {
+    /// Expression as LaTeX formula, dual numbers
+    /// are written as 'val + der\varepsilon'.
+    pub fn to_latex(&self) -> String {
+        self.accept(&mut LatexPrinter).0
+    }
+
+    /// Expression as Graphviz DOT graph.
+    pub fn to_dot(&self) -> String {
+        let mut printer = DotPrinter {
+            lines: Vec::new(),
+            nodes: 0,
+        };
+        self.accept(&mut printer);
+        format!("digraph xpr {{\n{}\n}}\n", printer.lines.join("\n"))
+    }
+}
+
+/// Expression as infix text, dual numbers are written as 'val + derε'.
+impl<E: Visit> fmt::Display for XprWrapper<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.accept(&mut TextPrinter).0)
+    }
+}
+
+/// Dual number as 'val + derε'.
+impl<T: fmt::Display> fmt::Display for Dual<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let der = self.der.to_string();
+        match der.strip_prefix('-') {
+            Some(abs) => write!(f, "{} - {}ε", self.val, abs),
+            None => write!(f, "{} + {}ε", self.val, der),
+        }
+    }
+}
@@ -1,104 +1,534 @@
 use super::expression::{UnXpr, Xpr, XprWrapper};
-use crate::dual::Dual;
+use crate::{
+    dual::{Dual, DualNumber},
+    dual_n::DualN,
+    hyper_dual::HyperDual,
+    real::Real,
+};
 
 /// Sinus expression
 #[derive(Copy, Clone, Debug)]
-pub struct SinXpr<Op>(UnXpr<Op>)
-where
-    Op: Xpr;
+pub struct SinXpr<Op>(pub(crate) UnXpr<Op>);
 
-impl<E> Xpr for SinXpr<E>
+impl<D, E> Xpr<D> for SinXpr<E>
 where
-    E: Xpr,
+    D: DualNumber,
+    E: Xpr<D>,
 {
-    fn assign(&self, other: &mut Dual) {
+    fn assign(&self, other: &mut D) {
         self.0.op.assign(other);
-        other.der.set(other.der.get() * other.val.cos());
-        other.val = other.val.sin();
+        let val = other.value();
+        other.chain(val.sin(), val.cos(), val.sin().neg());
     }
 }
 
 /// Cosinus expression
 #[derive(Copy, Clone, Debug)]
-pub struct CosXpr<Op>(UnXpr<Op>)
-where
-    Op: Xpr;
+pub struct CosXpr<Op>(pub(crate) UnXpr<Op>);
 
-impl<E> Xpr for CosXpr<E>
+impl<D, E> Xpr<D> for CosXpr<E>
 where
-    E: Xpr,
+    D: DualNumber,
+    E: Xpr<D>,
 {
-    fn assign(&self, other: &mut Dual) {
+    fn assign(&self, other: &mut D) {
         self.0.op.assign(other);
-        other.der.set(-other.der.get() * other.val.sin());
-        other.val = other.val.cos();
+        let val = other.value();
+        other.chain(val.cos(), val.sin().neg(), val.cos().neg());
     }
 }
 
 /// Sqrt expression
 #[derive(Copy, Clone, Debug)]
-pub struct SqrtXpr<Op>(UnXpr<Op>)
-where
-    Op: Xpr;
+pub struct SqrtXpr<Op>(pub(crate) UnXpr<Op>);
 
-impl<E> Xpr for SqrtXpr<E>
+impl<D, E> Xpr<D> for SqrtXpr<E>
 where
-    E: Xpr,
+    D: DualNumber,
+    E: Xpr<D>,
 {
-    fn assign(&self, other: &mut Dual) {
+    fn assign(&self, other: &mut D) {
         self.0.op.assign(other);
-        other.val = other.val.sqrt();
-        other.der.set(other.der.get() / (2.0 * other.val));
+        let val = other.value();
+        let sqrt = val.sqrt();
+        let two = D::Scalar::from_f64(2.0);
+        let d1 = D::Scalar::one().div(two.mul(sqrt));
+        other.chain(sqrt, d1, d1.neg().div(two.mul(val)));
     }
 }
 
 /// Ln expression
 #[derive(Copy, Clone, Debug)]
-pub struct LnXpr<Op>(UnXpr<Op>)
-where
-    Op: Xpr;
+pub struct LnXpr<Op>(pub(crate) UnXpr<Op>);
 
-impl<E> Xpr for LnXpr<E>
+impl<D, E> Xpr<D> for LnXpr<E>
 where
-    E: Xpr,
+    D: DualNumber,
+    E: Xpr<D>,
 {
-    fn assign(&self, other: &mut Dual) {
+    fn assign(&self, other: &mut D) {
         self.0.op.assign(other);
-        other.der.set(other.der.get() / other.val);
-        other.val = other.val.ln();
+        let val = other.value();
+        let d1 = D::Scalar::one().div(val);
+        other.chain(val.ln(), d1, d1.mul(d1).neg());
     }
 }
 
 /// Exponent expression
 #[derive(Copy, Clone, Debug)]
-pub struct ExpXpr<Op>(UnXpr<Op>)
+pub struct ExpXpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for ExpXpr<E>
+where
+    D: DualNumber,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value().exp();
+        other.chain(val, val, val);
+    }
+}
+
+/// Tangent expression
+#[derive(Copy, Clone, Debug)]
+pub struct TanXpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for TanXpr<E>
+where
+    D: DualNumber,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let tan = other.value().tan();
+        let d1 = D::Scalar::one().add(tan.mul(tan));
+        other.chain(tan, d1, D::Scalar::from_f64(2.0).mul(tan).mul(d1));
+    }
+}
+
+/// Arcsine expression
+#[derive(Copy, Clone, Debug)]
+pub struct AsinXpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for AsinXpr<E>
+where
+    D: DualNumber,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        let d1 = D::Scalar::one().sub(val.mul(val)).sqrt().recip();
+        other.chain(val.asin(), d1, val.mul(d1).mul(d1).mul(d1));
+    }
+}
+
+/// Arccosine expression
+#[derive(Copy, Clone, Debug)]
+pub struct AcosXpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for AcosXpr<E>
 where
-    Op: Xpr;
+    D: DualNumber,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        let d1 = D::Scalar::one().sub(val.mul(val)).sqrt().recip().neg();
+        other.chain(val.acos(), d1, val.mul(d1).mul(d1).mul(d1));
+    }
+}
+
+/// Arctangent expression
+#[derive(Copy, Clone, Debug)]
+pub struct AtanXpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for AtanXpr<E>
+where
+    D: DualNumber,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        let d1 = D::Scalar::one().add(val.mul(val)).recip();
+        other.chain(val.atan(), d1, D::Scalar::from_f64(-2.0).mul(val).mul(d1).mul(d1));
+    }
+}
+
+/// Hyperbolic sinus expression
+#[derive(Copy, Clone, Debug)]
+pub struct SinhXpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for SinhXpr<E>
+where
+    D: DualNumber,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        other.chain(val.sinh(), val.cosh(), val.sinh());
+    }
+}
+
+/// Hyperbolic cosinus expression
+#[derive(Copy, Clone, Debug)]
+pub struct CoshXpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for CoshXpr<E>
+where
+    D: DualNumber,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        other.chain(val.cosh(), val.sinh(), val.cosh());
+    }
+}
+
+/// Hyperbolic tangent expression
+#[derive(Copy, Clone, Debug)]
+pub struct TanhXpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for TanhXpr<E>
+where
+    D: DualNumber,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let tanh = other.value().tanh();
+        let d1 = D::Scalar::one().sub(tanh.mul(tanh));
+        other.chain(tanh, d1, D::Scalar::from_f64(-2.0).mul(tanh).mul(d1));
+    }
+}
+
+/// Inverse hyperbolic sinus expression
+#[derive(Copy, Clone, Debug)]
+pub struct AsinhXpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for AsinhXpr<E>
+where
+    D: DualNumber,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        let d1 = val.mul(val).add(D::Scalar::one()).sqrt().recip();
+        other.chain(val.asinh(), d1, val.neg().mul(d1).mul(d1).mul(d1));
+    }
+}
+
+/// Inverse hyperbolic cosinus expression
+#[derive(Copy, Clone, Debug)]
+pub struct AcoshXpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for AcoshXpr<E>
+where
+    D: DualNumber,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        let d1 = val.mul(val).sub(D::Scalar::one()).sqrt().recip();
+        other.chain(val.acosh(), d1, val.neg().mul(d1).mul(d1).mul(d1));
+    }
+}
+
+/// Inverse hyperbolic tangent expression
+#[derive(Copy, Clone, Debug)]
+pub struct AtanhXpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for AtanhXpr<E>
+where
+    D: DualNumber,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        let d1 = D::Scalar::one().sub(val.mul(val)).recip();
+        other.chain(val.atanh(), d1, D::Scalar::from_f64(2.0).mul(val).mul(d1).mul(d1));
+    }
+}
+
+/// Base 2 exponent expression
+#[derive(Copy, Clone, Debug)]
+pub struct Exp2Xpr<Op>(pub(crate) UnXpr<Op>);
 
-impl<E> Xpr for ExpXpr<E>
+impl<D, E> Xpr<D> for Exp2Xpr<E>
 where
-    E: Xpr,
+    D: DualNumber,
+    E: Xpr<D>,
 {
-    fn assign(&self, other: &mut Dual) {
+    fn assign(&self, other: &mut D) {
         self.0.op.assign(other);
-        other.val = other.val.exp();
-        other.der.set(other.der.get() * other.val);
+        let exp2 = other.value().exp2();
+        let ln2 = D::Scalar::from_f64(std::f64::consts::LN_2);
+        other.chain(exp2, exp2.mul(ln2), exp2.mul(ln2).mul(ln2));
+    }
+}
+
+/// Exponent minus one expression
+#[derive(Copy, Clone, Debug)]
+pub struct ExpM1Xpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for ExpM1Xpr<E>
+where
+    D: DualNumber,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        let exp = val.exp();
+        other.chain(val.exp_m1(), exp, exp);
+    }
+}
+
+/// Logarithm of one plus value expression
+#[derive(Copy, Clone, Debug)]
+pub struct Ln1pXpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for Ln1pXpr<E>
+where
+    D: DualNumber,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        let d1 = D::Scalar::one().add(val).recip();
+        other.chain(val.ln_1p(), d1, d1.mul(d1).neg());
+    }
+}
+
+/// Base 2 logarithm expression
+#[derive(Copy, Clone, Debug)]
+pub struct Log2Xpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for Log2Xpr<E>
+where
+    D: DualNumber,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        let d1 = val.mul(D::Scalar::from_f64(std::f64::consts::LN_2)).recip();
+        other.chain(val.log2(), d1, d1.div(val).neg());
+    }
+}
+
+/// Base 10 logarithm expression
+#[derive(Copy, Clone, Debug)]
+pub struct Log10Xpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for Log10Xpr<E>
+where
+    D: DualNumber,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        let d1 = val.mul(D::Scalar::from_f64(std::f64::consts::LN_10)).recip();
+        other.chain(val.log10(), d1, d1.div(val).neg());
+    }
+}
+
+/// Cube root expression
+#[derive(Copy, Clone, Debug)]
+pub struct CbrtXpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for CbrtXpr<E>
+where
+    D: DualNumber,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        let cbrt = val.cbrt();
+        let d1 = D::Scalar::from_f64(3.0).mul(cbrt).mul(cbrt).recip();
+        other.chain(cbrt, d1, D::Scalar::from_f64(-2.0 / 3.0).mul(d1).div(val));
+    }
+}
+
+/// Absolute value expression, derivative at zero is taken from the right
+#[derive(Copy, Clone, Debug)]
+pub struct AbsXpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for AbsXpr<E>
+where
+    D: DualNumber,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        other.chain(val.abs(), val.signum(), D::Scalar::zero());
+    }
+}
+
+/// Sign expression, derivative is zero everywhere
+#[derive(Copy, Clone, Debug)]
+pub struct SignumXpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for SignumXpr<E>
+where
+    D: DualNumber,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        other.chain(val.signum(), D::Scalar::zero(), D::Scalar::zero());
+    }
+}
+
+/// Reciprocal expression
+#[derive(Copy, Clone, Debug)]
+pub struct RecipXpr<Op>(pub(crate) UnXpr<Op>);
+
+impl<D, E> Xpr<D> for RecipXpr<E>
+where
+    D: DualNumber,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let recip = other.value().recip();
+        let d1 = recip.mul(recip).neg();
+        other.chain(recip, d1, D::Scalar::from_f64(-2.0).mul(recip).mul(d1));
+    }
+}
+
+/// Integer power expression
+#[derive(Copy, Clone, Debug)]
+pub struct PowiXpr<Op>(pub(crate) UnXpr<Op>, pub(crate) i32);
+
+impl<D, E> Xpr<D> for PowiXpr<E>
+where
+    D: DualNumber,
+    E: Xpr<D>,
+{
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign(other);
+        let val = other.value();
+        let n = self.1;
+        // 0 * 0^(-1) would give NaN at zero for lowest powers
+        let (d1, d2) = match n {
+            0 => (D::Scalar::from_f64(0.0), D::Scalar::from_f64(0.0)),
+            1 => (D::Scalar::from_f64(1.0), D::Scalar::from_f64(0.0)),
+            _ => (
+                D::Scalar::from_f64(n as f64).mul(val.powi(n - 1)),
+                D::Scalar::from_f64(n as f64 * (n as f64 - 1.0)).mul(val.powi(n - 2)),
+            ),
+        };
+        other.chain(val.powi(n), d1, d2);
     }
 }
 
 macro_rules! un_op_dual(
     ($op: ident, $Res: ident) => {
         /// $op operation
-        pub fn $op(self) -> XprWrapper<$Res<Dual>> { XprWrapper{xpr: $Res(UnXpr{ op: self })}}
+        pub fn $op(self) -> XprWrapper<$Res<Self>> { XprWrapper{xpr: $Res(UnXpr{ op: self })}}
+    };
+    ($op: ident, $Res: ident, $arg: ident) => {
+        /// $op operation
+        pub fn $op(self, $arg: i32) -> XprWrapper<$Res<Self>> { XprWrapper{xpr: $Res(UnXpr{ op: self }, $arg)}}
     };
 );
 
-impl Dual {
+impl<T: Real> Dual<T> {
+    un_op_dual!(sin, SinXpr);
+    un_op_dual!(cos, CosXpr);
+    un_op_dual!(sqrt, SqrtXpr);
+    un_op_dual!(ln, LnXpr);
+    un_op_dual!(exp, ExpXpr);
+    un_op_dual!(tan, TanXpr);
+    un_op_dual!(asin, AsinXpr);
+    un_op_dual!(acos, AcosXpr);
+    un_op_dual!(atan, AtanXpr);
+    un_op_dual!(sinh, SinhXpr);
+    un_op_dual!(cosh, CoshXpr);
+    un_op_dual!(tanh, TanhXpr);
+    un_op_dual!(asinh, AsinhXpr);
+    un_op_dual!(acosh, AcoshXpr);
+    un_op_dual!(atanh, AtanhXpr);
+    un_op_dual!(exp2, Exp2Xpr);
+    un_op_dual!(exp_m1, ExpM1Xpr);
+    un_op_dual!(ln_1p, Ln1pXpr);
+    un_op_dual!(log2, Log2Xpr);
+    un_op_dual!(log10, Log10Xpr);
+    un_op_dual!(cbrt, CbrtXpr);
+    un_op_dual!(abs, AbsXpr);
+    un_op_dual!(signum, SignumXpr);
+    un_op_dual!(recip, RecipXpr);
+    un_op_dual!(powi, PowiXpr, n);
+}
+
+impl<const N: usize> DualN<N> {
     un_op_dual!(sin, SinXpr);
     un_op_dual!(cos, CosXpr);
     un_op_dual!(sqrt, SqrtXpr);
     un_op_dual!(ln, LnXpr);
     un_op_dual!(exp, ExpXpr);
+    un_op_dual!(tan, TanXpr);
+    un_op_dual!(asin, AsinXpr);
+    un_op_dual!(acos, AcosXpr);
+    un_op_dual!(atan, AtanXpr);
+    un_op_dual!(sinh, SinhXpr);
+    un_op_dual!(cosh, CoshXpr);
+    un_op_dual!(tanh, TanhXpr);
+    un_op_dual!(asinh, AsinhXpr);
+    un_op_dual!(acosh, AcoshXpr);
+    un_op_dual!(atanh, AtanhXpr);
+    un_op_dual!(exp2, Exp2Xpr);
+    un_op_dual!(exp_m1, ExpM1Xpr);
+    un_op_dual!(ln_1p, Ln1pXpr);
+    un_op_dual!(log2, Log2Xpr);
+    un_op_dual!(log10, Log10Xpr);
+    un_op_dual!(cbrt, CbrtXpr);
+    un_op_dual!(abs, AbsXpr);
+    un_op_dual!(signum, SignumXpr);
+    un_op_dual!(recip, RecipXpr);
+    un_op_dual!(powi, PowiXpr, n);
+}
+
+impl HyperDual {
+    un_op_dual!(sin, SinXpr);
+    un_op_dual!(cos, CosXpr);
+    un_op_dual!(sqrt, SqrtXpr);
+    un_op_dual!(ln, LnXpr);
+    un_op_dual!(exp, ExpXpr);
+    un_op_dual!(tan, TanXpr);
+    un_op_dual!(asin, AsinXpr);
+    un_op_dual!(acos, AcosXpr);
+    un_op_dual!(atan, AtanXpr);
+    un_op_dual!(sinh, SinhXpr);
+    un_op_dual!(cosh, CoshXpr);
+    un_op_dual!(tanh, TanhXpr);
+    un_op_dual!(asinh, AsinhXpr);
+    un_op_dual!(acosh, AcoshXpr);
+    un_op_dual!(atanh, AtanhXpr);
+    un_op_dual!(exp2, Exp2Xpr);
+    un_op_dual!(exp_m1, ExpM1Xpr);
+    un_op_dual!(ln_1p, Ln1pXpr);
+    un_op_dual!(log2, Log2Xpr);
+    un_op_dual!(log10, Log10Xpr);
+    un_op_dual!(cbrt, CbrtXpr);
+    un_op_dual!(abs, AbsXpr);
+    un_op_dual!(signum, SignumXpr);
+    un_op_dual!(recip, RecipXpr);
+    un_op_dual!(powi, PowiXpr, n);
 }
 
 macro_rules! un_op_xpr(
@@ -106,12 +536,36 @@ macro_rules! un_op_xpr(
         /// $op operation
         pub fn $op(self) -> XprWrapper<$Res<$E>> { XprWrapper{xpr: $Res(UnXpr{ op: self.xpr })}}
     };
+    ($op: ident, $Res: ident, $E: ident, $arg: ident) => {
+        /// $op operation
+        pub fn $op(self, $arg: i32) -> XprWrapper<$Res<$E>> { XprWrapper{xpr: $Res(UnXpr{ op: self.xpr }, $arg)}}
+    };
 );
 
-impl<E: Xpr> XprWrapper<E> {
+impl<E> XprWrapper<E> {
     un_op_xpr!(sin, SinXpr, E);
     un_op_xpr!(cos, CosXpr, E);
     un_op_xpr!(sqrt, SqrtXpr, E);
     un_op_xpr!(ln, LnXpr, E);
     un_op_xpr!(exp, ExpXpr, E);
+    un_op_xpr!(tan, TanXpr, E);
+    un_op_xpr!(asin, AsinXpr, E);
+    un_op_xpr!(acos, AcosXpr, E);
+    un_op_xpr!(atan, AtanXpr, E);
+    un_op_xpr!(sinh, SinhXpr, E);
+    un_op_xpr!(cosh, CoshXpr, E);
+    un_op_xpr!(tanh, TanhXpr, E);
+    un_op_xpr!(asinh, AsinhXpr, E);
+    un_op_xpr!(acosh, AcoshXpr, E);
+    un_op_xpr!(atanh, AtanhXpr, E);
+    un_op_xpr!(exp2, Exp2Xpr, E);
+    un_op_xpr!(exp_m1, ExpM1Xpr, E);
+    un_op_xpr!(ln_1p, Ln1pXpr, E);
+    un_op_xpr!(log2, Log2Xpr, E);
+    un_op_xpr!(log10, Log10Xpr, E);
+    un_op_xpr!(cbrt, CbrtXpr, E);
+    un_op_xpr!(abs, AbsXpr, E);
+    un_op_xpr!(signum, SignumXpr, E);
+    un_op_xpr!(recip, RecipXpr, E);
+    un_op_xpr!(powi, PowiXpr, E, n);
 }
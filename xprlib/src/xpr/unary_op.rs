@@ -1,43 +1,73 @@
 use super::expression::{UnXpr, Xpr, XprWrapper};
-use crate::dual::Dual;
+use crate::{
+    dual::{Dual, DualNumber},
+    dual_n::DualN,
+    hyper_dual::HyperDual,
+    real::Real,
+};
 use std::ops::Neg;
 
-/// Negate expression
+/// Negate expression, negation of
+/// negation assigns operand as is.
 #[derive(Copy, Clone, Debug)]
-pub struct NegXpr<Op>(UnXpr<Op>)
-where
-    Op: Xpr;
+pub struct NegXpr<Op>(pub(crate) UnXpr<Op>);
 
-impl<E> Xpr for NegXpr<E>
+impl<D, E> Xpr<D> for NegXpr<E>
 where
-    E: Xpr,
+    D: DualNumber,
+    E: Xpr<D>,
 {
-    fn assign(&self, other: &mut Dual) {
-        self.0.op.assign(other);
-        other.neagate();
+    fn assign(&self, other: &mut D) {
+        self.0.op.assign_neg(other);
     }
 
-    fn assign_add(&self, target: &mut Dual) {
+    fn assign_add(&self, target: &mut D) {
         self.0.op.assign_sub(target);
     }
 
-    fn assign_sub(&self, target: &mut Dual) {
+    fn assign_sub(&self, target: &mut D) {
         self.0.op.assign_add(target);
     }
 
-    fn assign_mul(&self, target: &mut Dual) {
+    fn assign_mul(&self, target: &mut D) {
         self.0.op.assign_mul(target);
-        target.neagate();
+        target.negate();
     }
 
-    fn assign_div(&self, target: &mut Dual) {
+    fn assign_div(&self, target: &mut D) {
         self.0.op.assign_div(target);
-        target.neagate();
+        target.negate();
+    }
+
+    fn assign_neg(&self, other: &mut D) {
+        self.0.op.assign(other);
+    }
+
+    fn as_constant(&self) -> Option<f64> {
+        self.0.op.as_constant().map(|val| -val)
+    }
+}
+
+impl<T: Real> Neg for Dual<T> {
+    type Output = XprWrapper<NegXpr<Dual<T>>>;
+    fn neg(self) -> Self::Output {
+        Self::Output {
+            xpr: NegXpr(UnXpr { op: self }),
+        }
+    }
+}
+
+impl<const N: usize> Neg for DualN<N> {
+    type Output = XprWrapper<NegXpr<DualN<N>>>;
+    fn neg(self) -> Self::Output {
+        Self::Output {
+            xpr: NegXpr(UnXpr { op: self }),
+        }
     }
 }
 
-impl Neg for Dual {
-    type Output = XprWrapper<NegXpr<Dual>>;
+impl Neg for HyperDual {
+    type Output = XprWrapper<NegXpr<HyperDual>>;
     fn neg(self) -> Self::Output {
         Self::Output {
             xpr: NegXpr(UnXpr { op: self }),
@@ -45,7 +75,7 @@ impl Neg for Dual {
     }
 }
 
-impl<E: Xpr> Neg for XprWrapper<E> {
+impl<E> Neg for XprWrapper<E> {
     type Output = XprWrapper<NegXpr<E>>;
     fn neg(self) -> Self::Output {
         Self::Output {
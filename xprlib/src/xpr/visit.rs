@@ -0,0 +1,245 @@
+use super::{
+    binary_fn::{Atan2Xpr, CopysignXpr, HypotXpr, LogXpr, MaxXpr, MinXpr, PowfXpr},
+    binary_op::{AddXpr, DivXpr, MulXpr, SubXpr},
+    constant::ConstantXpr,
+    expression::XprWrapper,
+    unary_fn::*,
+    unary_op::NegXpr,
+};
+#[cfg(feature = "special")]
+use crate::special::{BesselJ0Xpr, BesselJ1Xpr, DigammaXpr, ErfXpr, ErfcXpr, GammaXpr, LnGammaXpr};
+use crate::{dual::Dual, dual_n::DualN, hyper_dual::HyperDual, real::Real};
+
+/// Operation of expression node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Operation {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Neg,
+    Powf,
+    Powi(i32),
+    Atan2,
+    Hypot,
+    Min,
+    Max,
+    Log,
+    Copysign,
+    Sin,
+    Cos,
+    Sqrt,
+    Ln,
+    Exp,
+    Tan,
+    Asin,
+    Acos,
+    Atan,
+    Sinh,
+    Cosh,
+    Tanh,
+    Asinh,
+    Acosh,
+    Atanh,
+    Exp2,
+    ExpM1,
+    Ln1p,
+    Log2,
+    Log10,
+    Cbrt,
+    Abs,
+    Signum,
+    Recip,
+    Erf,
+    Erfc,
+    Gamma,
+    LnGamma,
+    Digamma,
+    BesselJ0,
+    BesselJ1,
+}
+
+impl Operation {
+    /// Name of method which creates node of this operation.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Sub => "sub",
+            Self::Mul => "mul",
+            Self::Div => "div",
+            Self::Neg => "neg",
+            Self::Powf => "powf",
+            Self::Powi(_) => "powi",
+            Self::Atan2 => "atan2",
+            Self::Hypot => "hypot",
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::Log => "log",
+            Self::Copysign => "copysign",
+            Self::Sin => "sin",
+            Self::Cos => "cos",
+            Self::Sqrt => "sqrt",
+            Self::Ln => "ln",
+            Self::Exp => "exp",
+            Self::Tan => "tan",
+            Self::Asin => "asin",
+            Self::Acos => "acos",
+            Self::Atan => "atan",
+            Self::Sinh => "sinh",
+            Self::Cosh => "cosh",
+            Self::Tanh => "tanh",
+            Self::Asinh => "asinh",
+            Self::Acosh => "acosh",
+            Self::Atanh => "atanh",
+            Self::Exp2 => "exp2",
+            Self::ExpM1 => "exp_m1",
+            Self::Ln1p => "ln_1p",
+            Self::Log2 => "log2",
+            Self::Log10 => "log10",
+            Self::Cbrt => "cbrt",
+            Self::Abs => "abs",
+            Self::Signum => "signum",
+            Self::Recip => "recip",
+            Self::Erf => "erf",
+            Self::Erfc => "erfc",
+            Self::Gamma => "gamma",
+            Self::LnGamma => "ln_gamma",
+            Self::Digamma => "digamma",
+            Self::BesselJ0 => "bessel_j0",
+            Self::BesselJ1 => "bessel_j1",
+        }
+    }
+}
+
+/// Visitor of expression tree. Tree is folded bottom up,
+/// results of operands are passed to their node.
+pub trait Visitor {
+    /// Result of visiting node.
+    type Output;
+
+    /// Visit dual number.
+    ///
+    /// # Arguments
+    ///
+    /// 'val' - value of dual number.
+    /// 'der' - tangent components of dual number.
+    fn leaf(&mut self, val: f64, der: &[f64]) -> Self::Output;
+
+    /// Visit constant.
+    ///
+    /// # Arguments
+    ///
+    /// 'val' - value of constant.
+    fn constant(&mut self, val: f64) -> Self::Output;
+
+    /// Visit node with one operand.
+    ///
+    /// # Arguments
+    ///
+    /// 'op' - operation of node.
+    /// 'arg' - result of operand.
+    fn unary(&mut self, op: Operation, arg: Self::Output) -> Self::Output;
+
+    /// Visit node with two operands.
+    ///
+    /// # Arguments
+    ///
+    /// 'op' - operation of node.
+    /// 'l' - result of left operand.
+    /// 'r' - result of right operand.
+    fn binary(&mut self, op: Operation, l: Self::Output, r: Self::Output) -> Self::Output;
+}
+
+/// Expression node which can be visited.
+pub trait Visit {
+    /// Fold node and its operands with visitor.
+    ///
+    /// # Arguments
+    ///
+    /// 'visitor' - visitor of the tree.
+    fn accept<V: Visitor>(&self, visitor: &mut V) -> V::Output;
+}
+
+impl<T: Real + Into<f64>> Visit for Dual<T> {
+    fn accept<V: Visitor>(&self, visitor: &mut V) -> V::Output {
+        visitor.leaf(self.val.into(), &[self.der.into()])
+    }
+}
+
+impl<const N: usize> Visit for DualN<N> {
+    fn accept<V: Visitor>(&self, visitor: &mut V) -> V::Output {
+        visitor.leaf(self.val, &self.der)
+    }
+}
+
+impl Visit for HyperDual {
+    fn accept<V: Visitor>(&self, visitor: &mut V) -> V::Output {
+        visitor.leaf(self.val, &[self.e1, self.e2, self.e12])
+    }
+}
+
+impl Visit for ConstantXpr {
+    fn accept<V: Visitor>(&self, visitor: &mut V) -> V::Output {
+        visitor.constant(self.val)
+    }
+}
+
+impl<E: Visit> Visit for XprWrapper<E> {
+    fn accept<V: Visitor>(&self, visitor: &mut V) -> V::Output {
+        self.xpr.accept(visitor)
+    }
+}
+
+impl<E: Visit> Visit for PowiXpr<E> {
+    fn accept<V: Visitor>(&self, visitor: &mut V) -> V::Output {
+        let arg = self.0.op.accept(visitor);
+        visitor.unary(Operation::Powi(self.1), arg)
+    }
+}
+
+macro_rules! impl_visit_unary(
+    ($($Xpr: ident => $Op: ident),*) => {
+        $(
+            impl<E: Visit> Visit for $Xpr<E> {
+                fn accept<V: Visitor>(&self, visitor: &mut V) -> V::Output {
+                    let arg = self.0.op.accept(visitor);
+                    visitor.unary(Operation::$Op, arg)
+                }
+            }
+        )*
+    }
+);
+
+macro_rules! impl_visit_binary(
+    ($($Xpr: ident => $Op: ident),*) => {
+        $(
+            impl<L: Visit, R: Visit> Visit for $Xpr<L, R> {
+                fn accept<V: Visitor>(&self, visitor: &mut V) -> V::Output {
+                    let l = self.0.l.accept(visitor);
+                    let r = self.0.r.accept(visitor);
+                    visitor.binary(Operation::$Op, l, r)
+                }
+            }
+        )*
+    }
+);
+
+impl_visit_unary!(
+    NegXpr => Neg, SinXpr => Sin, CosXpr => Cos, SqrtXpr => Sqrt, LnXpr => Ln, ExpXpr => Exp,
+    TanXpr => Tan, AsinXpr => Asin, AcosXpr => Acos, AtanXpr => Atan, SinhXpr => Sinh,
+    CoshXpr => Cosh, TanhXpr => Tanh, AsinhXpr => Asinh, AcoshXpr => Acosh, AtanhXpr => Atanh,
+    Exp2Xpr => Exp2, ExpM1Xpr => ExpM1, Ln1pXpr => Ln1p, Log2Xpr => Log2, Log10Xpr => Log10,
+    CbrtXpr => Cbrt, AbsXpr => Abs, SignumXpr => Signum, RecipXpr => Recip
+);
+
+#[cfg(feature = "special")]
+impl_visit_unary!(
+    ErfXpr => Erf, ErfcXpr => Erfc, GammaXpr => Gamma, LnGammaXpr => LnGamma,
+    DigammaXpr => Digamma, BesselJ0Xpr => BesselJ0, BesselJ1Xpr => BesselJ1
+);
+
+impl_visit_binary!(
+    AddXpr => Add, SubXpr => Sub, MulXpr => Mul, DivXpr => Div, PowfXpr => Powf,
+    Atan2Xpr => Atan2, HypotXpr => Hypot, MinXpr => Min, MaxXpr => Max, LogXpr => Log,
+    CopysignXpr => Copysign
+);